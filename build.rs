@@ -32,6 +32,10 @@ fn main() {
         // structs, the zeroed default is not a good solution here. (It'd kind of work, but
         // it'd produce incorrect debug output).
         "CONFIG_AUTO_INIT_ENABLE_DEBUG",
+        // These decide how riot_assert behaves, mirroring RIOT's assert.
+        "NDEBUG",
+        "DEBUG_ASSERT_VERBOSE",
+        "DEBUG_ASSERT_BREAKPOINT",
     ];
 
     for marker in BOOLEAN_FLAGS {
@@ -41,6 +45,26 @@ fn main() {
         );
     }
 
+    // RIOT's LOG_LEVEL, which is typically set as `CFLAGS += -DLOG_LEVEL=LOG_DEBUG` (or
+    // numerically), and otherwise defaults to LOG_INFO
+    let log_level = match defines
+        .get("LOG_LEVEL")
+        .map(|l| l.trim_start_matches('(').trim_end_matches(')'))
+    {
+        None | Some("LOG_INFO") | Some("3") => "info",
+        Some("LOG_NONE") | Some("0") => "none",
+        Some("LOG_ERROR") | Some("1") => "error",
+        Some("LOG_WARNING") | Some("2") => "warning",
+        Some("LOG_DEBUG") | Some("4") => "debug",
+        Some("LOG_ALL") | Some("5") => "all",
+        Some(other) => {
+            println!("cargo:warning=Unrecognized LOG_LEVEL value {other:?}, assuming LOG_INFO");
+            "info"
+        }
+    };
+    println!("cargo::rustc-check-cfg=cfg(riot_log_level, values(\"none\", \"error\", \"warning\", \"info\", \"debug\", \"all\"))");
+    println!("cargo:rustc-cfg=riot_log_level=\"{log_level}\"");
+
     for (def, val) in defines {
         if val != "1" {
             // So far, only processing boolean flags
@@ -65,15 +89,38 @@ fn main() {
 
     // FIXME: This list is currently maintained manually;
     let known_modules = &[
+        "apa102",
         "auto_init",
         "auto_init_random",
+        "base64",
+        "bloom",
         "bluetil_ad",
+        "c25519",
+        "checksum",
+        "cipher_modes",
+        "color",
         "core_msg",
+        "core_thread_flags",
+        "credman",
+        "crypto",
+        "crypto_aes_128",
+        "crypto_aes_192",
+        "crypto_aes_256",
+        "dbgpin",
+        "disp_dev",
+        "disp_dev_backlight",
+        "dsm",
+        "event",
+        "fmt",
         "gcoap",
+        "gcoap_dtls",
         "gnrc",
         "gnrc_icmpv6",
+        "gnrc_icmpv6_echo",
+        "gnrc_ipv6",
         "gnrc_ipv6_nib",
         "gnrc_netapi_callbacks",
+        "gnrc_netif_bus",
         "gnrc_nettype_ccn",
         "gnrc_nettype_custom",
         "gnrc_nettype_gomach",
@@ -87,14 +134,27 @@ fn main() {
         "gnrc_nettype_udp",
         "gnrc_pktbuf",
         "gnrc_udp",
+        "hashes",
         "ipv6",
+        "liboscore",
+        "log",
+        "malloc_monitor",
         "microbit",
+        "motor_driver",
+        "mtd",
+        "mtd_mapper",
+        "mtd_write_page",
+        "nanocbor",
+        "nanocoap_cache",
         "nimble_host",
         "periph_adc",
         "periph_dac",
+        "periph_freqm",
         "periph_gpio",
         "periph_i2c",
         "periph_spi",
+        "periph_timer",
+        "periph_timer_periodic",
         "periph_uart",
         "periph_uart_collision",
         "periph_uart_hw_fc",
@@ -104,18 +164,48 @@ fn main() {
         "periph_uart_rxstart_irq",
         "periph_uart_tx_ondemand",
         "prng_shaxprng",
+        "psa_crypto",
         "pthread",
         "random",
+        "riotboot_flashwrite",
+        "riotboot_slot",
         "saul",
+        "saul_observer",
+        "sched_cb",
+        "schedstatistics",
+        "senml",
+        "senml_cbor",
+        "senml_phydat",
+        "senml_saul",
+        "servo",
         "shell",
+        "shell_lock",
         "sock",
         "sock_aux_local",
+        "sock_aux_rssi",
+        "sock_aux_timestamp",
+        "sock_aux_ttl",
         "sock_tcp",
         "sock_udp",
+        "stdio_cdc_acm",
+        "stdio_native",
+        "stdio_null",
+        "stdio_rtt",
+        "stdio_semihosting",
+        "stdio_telnet",
+        "stdio_uart",
+        "telnet",
+        "test_utils_interactive_sync",
+        "test_utils_result_output",
         "tiny_strerror",
         "tiny_strerror_minimal",
+        "touch_dev",
         "udp",
+        "usbus",
+        "usbus_msc",
+        "uuid",
         "vfs",
+        "vfs_util",
         "ws281x",
         "ztimer",
         "ztimer_msec",
@@ -173,6 +263,13 @@ fn main() {
             &bindgen_output_file.as_str(),
             &"spi_clk_t_SPI_CLK_100KHZ",
         ),
+        // Not a version compatibility item, but the only way to tell from Rust whether the CPU
+        // has a backup RAM section to place data in.
+        (
+            &"CPU_HAS_BACKUP_RAM",
+            &bindgen_output_file.as_str(),
+            &"CPU_HAS_BACKUP_RAM",
+        ),
     ];
 
     for (rust_name, header_file, header_search_string) in emulate_accessible {
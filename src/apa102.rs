@@ -0,0 +1,109 @@
+//! Tools for using the [APA102 RGB LED](https://doc.riot-os.org/group__drivers__apa102.html)
+//! driver
+//!
+//! Unlike WS281x LEDs, APA102 LEDs have a global brightness setting per LED, which is used for the
+//! [brightness](BufferedApa102::set_brightness) of the strip; this keeps the full color resolution
+//! at low brightness.
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::apa102::BufferedApa102;
+//! # use riot_wrappers::color::Rgb;
+//! # fn f(data: riot_wrappers::gpio::GPIO, clock: riot_wrappers::gpio::GPIO) {
+//! let mut strip: BufferedApa102<8> = BufferedApa102::init(data, clock);
+//! strip.set_brightness(64);
+//! strip.set_pixel(0, Rgb::new(0, 0, 255));
+//! strip.write();
+//! # }
+//! ```
+
+use riot_sys::{apa102_t, color_rgba_t};
+
+use crate::color::Rgb;
+
+/// An APA102 chain backed by an owned buffer
+pub struct BufferedApa102<const N: usize> {
+    buffer: [color_rgba_t; N],
+    dev: apa102_t,
+}
+
+impl<const N: usize> BufferedApa102<N> {
+    /// Initialize a device and associated buffer, with all LEDs off and at full brightness
+    #[doc(alias = "apa102_init")]
+    pub fn init(data_pin: crate::gpio::GPIO, clock_pin: crate::gpio::GPIO) -> Self {
+        let params = riot_sys::apa102_params_t {
+            led_numof: N
+                .try_into()
+                .expect("Chain length exceeds expressible range"),
+            data_pin: data_pin.to_c(),
+            clk_pin: clock_pin.to_c(),
+        };
+        // unsafe: All-zero is a valid uninitialized device, which is initialized right away
+        let mut dev: apa102_t = unsafe { core::mem::zeroed() };
+        // unsafe: C API as documented
+        unsafe { riot_sys::apa102_init(&mut dev, &params) };
+
+        Self {
+            buffer: [color_rgba_t {
+                color: Rgb::default().to_c(),
+                alpha: u8::MAX,
+            }; N],
+            dev,
+        }
+    }
+
+    /// Color of the LED at `index`
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn pixel(&self, index: usize) -> Rgb {
+        Rgb::from_c(self.buffer[index].color)
+    }
+
+    /// Set the color of the LED at `index`
+    ///
+    /// The change becomes visible at the next [.write()](Self::write).
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn set_pixel(&mut self, index: usize, color: Rgb) {
+        self.buffer[index].color = color.to_c();
+    }
+
+    /// Set all LEDs to the given color
+    pub fn fill(&mut self, color: Rgb) {
+        for led in self.buffer.iter_mut() {
+            led.color = color.to_c();
+        }
+    }
+
+    /// Set the brightness (from 0 to 255) of all LEDs
+    ///
+    /// The change becomes visible on all LEDs at the next [.write()](Self::write), as with
+    /// [`BufferedWs281x::set_brightness()`](crate::ws281x::BufferedWs281x::set_brightness). The
+    /// LEDs only support 32 brightness levels, so the lowest 3 bits are ignored.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        for led in self.buffer.iter_mut() {
+            led.alpha = brightness;
+        }
+    }
+
+    /// Set the brightness (from 0 to 255) of the LED at `index`
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn set_pixel_brightness(&mut self, index: usize, brightness: u8) {
+        self.buffer[index].alpha = brightness;
+    }
+
+    /// Send the buffer to the LEDs
+    #[doc(alias = "apa102_load_rgba")]
+    pub fn write(&mut self) {
+        // unsafe: C API as documented; the buffer has the length configured at initialization.
+        unsafe { riot_sys::apa102_load_rgba(&self.dev, self.buffer.as_ptr()) };
+    }
+}
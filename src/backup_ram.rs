@@ -0,0 +1,166 @@
+//! Values that survive deep sleep and soft resets in the CPU's backup RAM
+//!
+//! Some CPUs provide a RAM area that retains its content while the rest of the system is powered
+//! down, and which is not cleared on startup. Declaring a [BackupCell] through the
+//! [`backup_ram!`](crate::backup_ram!) macro places it in that area.
+//!
+//! As the content of that memory is arbitrary after a cold boot (or may have been written by a
+//! different firmware), every cell carries a checksum, and values are only returned when the
+//! checksum matches.
+//!
+//! ```
+//! # #![no_std]
+//! # #![feature(start)]
+//! # #[start]
+//! # fn main(_argc: isize, _argv: *const *const u8) -> isize {
+//! riot_wrappers::backup_ram!(static BOOT_COUNT: BackupCell<u32>;);
+//!
+//! let count = BOOT_COUNT.get().unwrap_or(0) + 1;
+//! BOOT_COUNT.set(count);
+//! riot_wrappers::println!("Booted {} times since the last cold start", count);
+//! # 0
+//! # }
+//! ```
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+
+/// Types that can be stored in a [BackupCell]
+///
+/// ## Safety
+///
+/// Implementers must not contain padding bytes, and every bit pattern must be a valid value of the
+/// type. (The checksum makes it unlikely that arbitrary data is read, but does not exclude it).
+pub unsafe trait Retainable: Copy {}
+
+unsafe impl Retainable for u8 {}
+unsafe impl Retainable for u16 {}
+unsafe impl Retainable for u32 {}
+unsafe impl Retainable for u64 {}
+unsafe impl Retainable for i8 {}
+unsafe impl Retainable for i16 {}
+unsafe impl Retainable for i32 {}
+unsafe impl Retainable for i64 {}
+unsafe impl<T: Retainable, const N: usize> Retainable for [T; N] {}
+
+/// A value of type `T` with a checksum
+///
+/// This is typically placed in backup RAM using the [`backup_ram!`](crate::backup_ram!) macro.
+/// All accesses happen with interrupts disabled, so the cell can be shared between threads and
+/// interrupts.
+#[repr(C)]
+pub struct BackupCell<T: Retainable> {
+    value: UnsafeCell<MaybeUninit<T>>,
+    checksum: UnsafeCell<MaybeUninit<u32>>,
+}
+
+// unsafe: All access goes through critical sections, and T is plain data.
+unsafe impl<T: Retainable + Send> Sync for BackupCell<T> {}
+
+impl<T: Retainable> BackupCell<T> {
+    /// Create a cell without initializing it
+    ///
+    /// Do not call this directly unless the result is placed in retained memory by other means;
+    /// use [`backup_ram!`](crate::backup_ram!) instead.
+    pub const fn new() -> Self {
+        Self {
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+            checksum: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    fn checksum_of(value: &T) -> u32 {
+        // unsafe: Retainable types have no padding, so all bytes are initialized.
+        let bytes = unsafe {
+            core::slice::from_raw_parts(value as *const T as *const u8, core::mem::size_of::<T>())
+        };
+        // FNV-1a, seeded with the size so that cells of different types don't accept each
+        // other's content after a firmware update
+        let mut hash = 0x811c9dc5 ^ (core::mem::size_of::<T>() as u32);
+        for b in bytes {
+            hash ^= u32::from(*b);
+            hash = hash.wrapping_mul(0x01000193);
+        }
+        hash
+    }
+
+    /// Read the stored value, if there is a valid one
+    pub fn get(&self) -> Option<T> {
+        crate::interrupt::free(|_| {
+            // unsafe: Memory is exclusively accessed in critical sections. Reading the values
+            // uses volatile operations as the compiler can't know that the content may be
+            // anything left over from before the reset; the Retainable requirements ensure that
+            // any content is a valid value.
+            let (value, checksum) = unsafe {
+                (
+                    core::ptr::read_volatile(self.value.get() as *const T),
+                    core::ptr::read_volatile(self.checksum.get() as *const u32),
+                )
+            };
+            (Self::checksum_of(&value) == checksum).then_some(value)
+        })
+    }
+
+    /// Store a value
+    pub fn set(&self, value: T) {
+        let checksum = Self::checksum_of(&value);
+        crate::interrupt::free(|_| {
+            // unsafe: Memory is exclusively accessed in critical sections
+            unsafe {
+                core::ptr::write_volatile(self.value.get() as *mut T, value);
+                core::ptr::write_volatile(self.checksum.get() as *mut u32, checksum);
+            }
+        })
+    }
+
+    /// Change the stored value using a function
+    ///
+    /// The function receives the current value (or None if there is no valid one), and is run in
+    /// a critical section.
+    pub fn update(&self, f: impl FnOnce(Option<T>) -> T) -> T {
+        crate::interrupt::free(|_| {
+            let new = f(self.get());
+            self.set(new);
+            new
+        })
+    }
+
+    /// Remove any stored value
+    ///
+    /// This only makes the checksum mismatch; the previous value is not overwritten.
+    pub fn invalidate(&self) {
+        crate::interrupt::free(|_| {
+            // unsafe: Memory is exclusively accessed in critical sections; see get() for the
+            // reading.
+            unsafe {
+                let value = core::ptr::read_volatile(self.value.get() as *const T);
+                // Complementing the value's checksum (rather than the stored one, which may be
+                // garbage) guarantees a mismatch
+                core::ptr::write_volatile(
+                    self.checksum.get() as *mut u32,
+                    !Self::checksum_of(&value),
+                );
+            }
+        })
+    }
+}
+
+/// Declare a static [BackupCell] that is placed in the CPU's backup RAM
+///
+/// The syntax is that of a static declaration without initializer:
+///
+/// ```ignore
+/// riot_wrappers::backup_ram!(static CRASH_REASON: BackupCell<[u8; 16]>;);
+/// ```
+///
+/// As the section is not zeroed on startup, the cell's content is only valid once it has been
+/// [set](BackupCell::set) (possibly before a previous reset).
+#[macro_export]
+macro_rules! backup_ram {
+    ($(#[$meta:meta])* $vis:vis static $name:ident: BackupCell<$t:ty>;) => {
+        $(#[$meta])*
+        #[link_section = ".backup.bss"]
+        $vis static $name: $crate::backup_ram::BackupCell<$t> =
+            $crate::backup_ram::BackupCell::new();
+    };
+}
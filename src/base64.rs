@@ -0,0 +1,104 @@
+//! Base64 encoding and decoding through RIOT's [base64] module
+//!
+//! All functions write into caller provided buffers; the `*_len` functions calculate how large
+//! those need to be.
+//!
+//! [base64]: https://doc.riot-os.org/base64_8h.html
+
+/// Error type of base64 operations
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The output buffer is too small; see [`encoded_len()`] and [`decode_buffer_len()`] for the
+    /// required sizes.
+    BufferTooSmall,
+    /// The input could not be processed
+    InvalidInput,
+}
+
+fn result(code: i32) -> Result<(), Error> {
+    match code {
+        // BASE64_SUCCESS
+        0 => Ok(()),
+        // BASE64_ERROR_BUFFER_OUT_SIZE
+        -2 => Err(Error::BufferTooSmall),
+        _ => Err(Error::InvalidInput),
+    }
+}
+
+/// Number of bytes produced by [`encode()`] or [`encode_url()`] from `data_len` bytes of data
+///
+/// This is exact; both alphabets pad the output to a multiple of 4.
+pub const fn encoded_len(data_len: usize) -> usize {
+    4 * ((data_len + 2) / 3)
+}
+
+/// Size of the buffer [`decode()`] requires for decoding `encoded_len` bytes of base64 data
+///
+/// This may be up to two bytes more than the decoded data; use [`decoded_len()`] to find the
+/// exact size.
+pub const fn decode_buffer_len(encoded_len: usize) -> usize {
+    ((encoded_len + 3) / 4) * 3
+}
+
+/// Exact number of bytes the given base64 data decodes into
+pub fn decoded_len(encoded: &[u8]) -> usize {
+    let padding = encoded.iter().rev().take_while(|c| **c == b'=').count();
+    (encoded.len() - padding) * 3 / 4
+}
+
+fn encode_with<'b>(url_safe: bool, data: &[u8], out: &'b mut [u8]) -> Result<&'b str, Error> {
+    if data.is_empty() {
+        return Ok("");
+    }
+    let data_in = data.as_ptr() as _;
+    let data_in_size = data.len() as _;
+    let base64_out = out.as_mut_ptr() as _;
+    let mut out_len = out.len() as _;
+    // unsafe: C API as documented; the output length is in-out.
+    result(unsafe {
+        if url_safe {
+            riot_sys::base64url_encode(data_in, data_in_size, base64_out, &mut out_len)
+        } else {
+            riot_sys::base64_encode(data_in, data_in_size, base64_out, &mut out_len)
+        }
+    })?;
+    let out = &out[..out_len as usize];
+    // unsafe: base64 output consists of ASCII characters only
+    Ok(unsafe { core::str::from_utf8_unchecked(out) })
+}
+
+/// Encode data with the standard base64 alphabet, returning the written part of the output
+/// buffer
+#[doc(alias = "base64_encode")]
+pub fn encode<'b>(data: &[u8], out: &'b mut [u8]) -> Result<&'b str, Error> {
+    encode_with(false, data, out)
+}
+
+/// Encode data with the URL and file name safe alphabet of RFC 4648 Section 5, returning the
+/// written part of the output buffer
+#[doc(alias = "base64url_encode")]
+pub fn encode_url<'b>(data: &[u8], out: &'b mut [u8]) -> Result<&'b str, Error> {
+    encode_with(true, data, out)
+}
+
+/// Decode base64 data, returning the written part of the output buffer
+///
+/// Both the standard and the URL safe alphabet are accepted. The output buffer needs to be at
+/// least [`decode_buffer_len()`] long.
+#[doc(alias = "base64_decode")]
+pub fn decode<'b>(encoded: &[u8], out: &'b mut [u8]) -> Result<&'b [u8], Error> {
+    if encoded.is_empty() {
+        return Ok(&[]);
+    }
+    let mut out_len = out.len() as _;
+    // unsafe: C API as documented; the output length is in-out.
+    result(unsafe {
+        riot_sys::base64_decode(
+            encoded.as_ptr() as _,
+            encoded.len() as _,
+            out.as_mut_ptr() as _,
+            &mut out_len,
+        )
+    })?;
+    Ok(&out[..out_len as usize])
+}
@@ -0,0 +1,109 @@
+//! Bloom filters through RIOT's [bloom] module
+//!
+//! A [BloomFilter] answers whether some data has been added to it before, with a configurable
+//! chance of false positives, but no false negatives. Typical uses are duplicate suppression of
+//! packets (by remembering their IDs) and caches.
+//!
+//! [bloom]: https://doc.riot-os.org/group__sys__bloom.html
+
+/// Number of entries in [HASHES]
+///
+/// This is a separate constant because statics can not be referenced from const contexts (such as
+/// [`BloomFilter::new()`]) in the supported Rust versions.
+const HASH_COUNT: usize = 8;
+
+/// The hash functions used in the filter, in the order in which they are used.
+static HASHES: [riot_sys::hashfp_t; HASH_COUNT] = [
+    Some(riot_sys::fnv_hash),
+    Some(riot_sys::sax_hash),
+    Some(riot_sys::sdbm_hash),
+    Some(riot_sys::djb2_hash),
+    Some(riot_sys::kr_hash),
+    Some(riot_sys::dek_hash),
+    Some(riot_sys::rotating_hash),
+    Some(riot_sys::one_at_a_time_hash),
+];
+
+/// A Bloom filter with `N` bytes (`8 * N` bits) of storage
+///
+/// The filter is plain data, and can be placed in a static or on the stack.
+///
+/// With `n` items in the filter, the optimal number of hash functions is `8 * N / n * ln(2)`, at
+/// which the false positive rate is roughly `0.6185 ^ (8 * N / n)`.
+#[derive(Clone)]
+pub struct BloomFilter<const N: usize> {
+    bits: [u8; N],
+    hashes: u8,
+}
+
+impl<const N: usize> BloomFilter<N> {
+    /// Number of hash functions available
+    ///
+    /// These are the functions of [crate::hash::non_crypto].
+    pub const MAX_HASHES: usize = HASH_COUNT;
+
+    /// Create an empty filter that uses a given number of hash functions
+    ///
+    /// ## Panics
+    ///
+    /// This panics if `hashes` is 0 or exceeds [`MAX_HASHES`](Self::MAX_HASHES).
+    pub const fn new(hashes: usize) -> Self {
+        assert!(hashes > 0 && hashes <= HASH_COUNT);
+        Self {
+            bits: [0; N],
+            hashes: hashes as _,
+        }
+    }
+
+    /// Build a `bloom_t` around the bits
+    ///
+    /// This is not going through bloom_init, as that would clear the bits; the struct is set up as
+    /// bloom_init would.
+    fn as_c(&self, bits: *mut u8) -> riot_sys::bloom_t {
+        riot_sys::bloom_t {
+            m: (N * 8) as _,
+            a: bits,
+            // The C code does not write through the pointer
+            hash: HASHES.as_ptr() as *mut _,
+            k: self.hashes as _,
+        }
+    }
+
+    /// Add data to the filter
+    #[doc(alias = "bloom_add")]
+    pub fn add(&mut self, data: &[u8]) {
+        let bits = self.bits.as_mut_ptr();
+        let mut bloom = self.as_c(bits);
+        // unsafe: The struct is set up to describe self.bits
+        unsafe { riot_sys::bloom_add(&mut bloom, data.as_ptr(), data.len() as _) };
+    }
+
+    /// Check whether data was added to the filter
+    ///
+    /// This may return `true` for data that was never added (at a rate that depends on how full
+    /// the filter is), but never returns `false` for data that was added.
+    #[doc(alias = "bloom_check")]
+    pub fn check(&self, data: &[u8]) -> bool {
+        // The C function does not write through the pointer
+        let mut bloom = self.as_c(self.bits.as_ptr() as *mut _);
+        // unsafe: The struct is set up to describe self.bits
+        unsafe { riot_sys::bloom_check(&mut bloom, data.as_ptr(), data.len() as _) }
+    }
+
+    /// Check whether data was added to the filter, and add it
+    ///
+    /// This is the typical operation for duplicate suppression: it returns `true` if the data has
+    /// (most probably) been seen before.
+    pub fn check_and_add(&mut self, data: &[u8]) -> bool {
+        let result = self.check(data);
+        if !result {
+            self.add(data);
+        }
+        result
+    }
+
+    /// Remove all data from the filter
+    pub fn clear(&mut self) {
+        self.bits = [0; N];
+    }
+}
@@ -0,0 +1,258 @@
+//! Ed25519 signatures and X25519 key exchange through the [c25519] package
+//!
+//! The package is small and slow; it is well suited for occasional operations such as verifying
+//! the signature of a configuration blob or a firmware image.
+//!
+//! All key material is held in fixed-size newtypes. Secrets are cleared when they are dropped,
+//! and comparisons of secrets are performed in constant time (see [`ct_eq()`]).
+//!
+//! [c25519]: https://doc.riot-os.org/group__pkg__c25519.html
+
+use rand_core_06::{CryptoRng, RngCore};
+
+/// Compare two byte slices in time that only depends on their length
+///
+/// Slices of different length compare unequal.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0;
+    for (a, b) in a.iter().zip(b.iter()) {
+        diff |= a ^ b;
+    }
+    // Keep the compiler from short-circuiting the loop
+    core::hint::black_box(diff) == 0
+}
+
+fn clear(data: &mut [u8]) {
+    for byte in data.iter_mut() {
+        // unsafe: Pointer is from a reference. Volatile so that the compiler does not elide the
+        // write to memory that is about to be released.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+}
+
+/// Error returned when a signature does not match the message and key
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InvalidSignature;
+
+/// Ed25519 secret key
+pub struct SecretKey([u8; riot_sys::EDSIGN_SECRET_KEY_SIZE as usize]);
+
+impl SecretKey {
+    /// Use some bytes as a secret key
+    ///
+    /// Any 32 bytes form a valid key; they should come from a cryptographically secure random
+    /// source, or from a key derivation function.
+    pub fn from_bytes(bytes: [u8; riot_sys::EDSIGN_SECRET_KEY_SIZE as usize]) -> Self {
+        Self(bytes)
+    }
+
+    /// Create a new key from a random number generator
+    pub fn generate(rng: &mut (impl RngCore + CryptoRng)) -> Self {
+        let mut bytes = [0; riot_sys::EDSIGN_SECRET_KEY_SIZE as usize];
+        rng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Access the secret key's bytes, eg. for storing them
+    pub fn as_bytes(&self) -> &[u8; riot_sys::EDSIGN_SECRET_KEY_SIZE as usize] {
+        &self.0
+    }
+
+    /// Derive the public key
+    #[doc(alias = "edsign_sec_to_pub")]
+    pub fn public_key(&self) -> PublicKey {
+        let mut public = [0; riot_sys::EDSIGN_PUBLIC_KEY_SIZE as usize];
+        // unsafe: C API as documented, buffers have the right sizes
+        unsafe { riot_sys::edsign_sec_to_pub(public.as_mut_ptr(), self.0.as_ptr()) };
+        PublicKey(public)
+    }
+
+    /// Sign a message
+    ///
+    /// This derives the public key internally; use [`.sign_with_public()`](Self::sign_with_public)
+    /// when signing many messages.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_with_public(&self.public_key(), message)
+    }
+
+    /// Sign a message, using a public key that was derived from this key earlier
+    ///
+    /// Passing in a public key that does not belong to this key produces an invalid signature
+    /// (and may leak information about the secret key).
+    #[doc(alias = "edsign_sign")]
+    pub fn sign_with_public(&self, public: &PublicKey, message: &[u8]) -> Signature {
+        let mut signature = [0; riot_sys::EDSIGN_SIGNATURE_SIZE as usize];
+        // unsafe: C API as documented, buffers have the right sizes
+        unsafe {
+            riot_sys::edsign_sign(
+                signature.as_mut_ptr(),
+                public.0.as_ptr(),
+                self.0.as_ptr(),
+                message.as_ptr(),
+                message.len() as _,
+            )
+        };
+        Signature(signature)
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        clear(&mut self.0);
+    }
+}
+
+/// Ed25519 public key
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PublicKey([u8; riot_sys::EDSIGN_PUBLIC_KEY_SIZE as usize]);
+
+impl PublicKey {
+    /// Use some bytes as a public key
+    ///
+    /// No check is performed on whether the bytes are a valid point; an invalid key will just
+    /// not verify any signatures.
+    pub fn from_bytes(bytes: [u8; riot_sys::EDSIGN_PUBLIC_KEY_SIZE as usize]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; riot_sys::EDSIGN_PUBLIC_KEY_SIZE as usize] {
+        &self.0
+    }
+
+    /// Check that a signature was created for this message by the owner of the key
+    #[doc(alias = "edsign_verify")]
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), InvalidSignature> {
+        // unsafe: C API as documented, buffers have the right sizes
+        let valid = unsafe {
+            riot_sys::edsign_verify(
+                signature.0.as_ptr(),
+                self.0.as_ptr(),
+                message.as_ptr(),
+                message.len() as _,
+            )
+        };
+        match valid {
+            0 => Err(InvalidSignature),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Ed25519 signature
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Signature([u8; riot_sys::EDSIGN_SIGNATURE_SIZE as usize]);
+
+impl Signature {
+    pub fn from_bytes(bytes: [u8; riot_sys::EDSIGN_SIGNATURE_SIZE as usize]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; riot_sys::EDSIGN_SIGNATURE_SIZE as usize] {
+        &self.0
+    }
+}
+
+/// X25519 secret key
+pub struct X25519SecretKey([u8; riot_sys::C25519_EXPONENT_SIZE as usize]);
+
+impl X25519SecretKey {
+    /// Use some bytes as a secret key
+    ///
+    /// Any 32 bytes can be used; they are clamped as required by X25519.
+    #[doc(alias = "c25519_prepare")]
+    pub fn from_bytes(mut bytes: [u8; riot_sys::C25519_EXPONENT_SIZE as usize]) -> Self {
+        // unsafe: C API as documented, buffer has the right size
+        unsafe { riot_sys::c25519_prepare(bytes.as_mut_ptr()) };
+        let result = Self(bytes);
+        clear(&mut bytes);
+        result
+    }
+
+    /// Create a new key from a random number generator
+    pub fn generate(rng: &mut (impl RngCore + CryptoRng)) -> Self {
+        let mut bytes = [0; riot_sys::C25519_EXPONENT_SIZE as usize];
+        rng.fill_bytes(&mut bytes);
+        let result = Self::from_bytes(bytes);
+        clear(&mut bytes);
+        result
+    }
+
+    /// Access the (clamped) secret key's bytes, eg. for storing them
+    pub fn as_bytes(&self) -> &[u8; riot_sys::C25519_EXPONENT_SIZE as usize] {
+        &self.0
+    }
+
+    /// Derive the public key that is sent to the peer
+    pub fn public_key(&self) -> X25519PublicKey {
+        let mut public = [0; riot_sys::F25519_SIZE as usize];
+        // unsafe: C API as documented, buffers have the right sizes
+        unsafe {
+            riot_sys::c25519_smult(
+                public.as_mut_ptr(),
+                riot_sys::c25519_base_x.as_ptr(),
+                self.0.as_ptr(),
+            )
+        };
+        X25519PublicKey(public)
+    }
+
+    /// Compute the secret shared with the owner of the peer's public key
+    ///
+    /// The result should not be used as a key directly, but be passed through a key derivation
+    /// function.
+    #[doc(alias = "c25519_smult")]
+    pub fn diffie_hellman(&self, peer: &X25519PublicKey) -> SharedSecret {
+        let mut shared = [0; riot_sys::F25519_SIZE as usize];
+        // unsafe: C API as documented, buffers have the right sizes
+        unsafe { riot_sys::c25519_smult(shared.as_mut_ptr(), peer.0.as_ptr(), self.0.as_ptr()) };
+        SharedSecret(shared)
+    }
+}
+
+impl Drop for X25519SecretKey {
+    fn drop(&mut self) {
+        clear(&mut self.0);
+    }
+}
+
+/// X25519 public key
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct X25519PublicKey([u8; riot_sys::F25519_SIZE as usize]);
+
+impl X25519PublicKey {
+    pub fn from_bytes(bytes: [u8; riot_sys::F25519_SIZE as usize]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; riot_sys::F25519_SIZE as usize] {
+        &self.0
+    }
+}
+
+/// Result of an X25519 key exchange
+///
+/// Comparison is performed in constant time.
+pub struct SharedSecret([u8; riot_sys::F25519_SIZE as usize]);
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; riot_sys::F25519_SIZE as usize] {
+        &self.0
+    }
+}
+
+impl PartialEq for SharedSecret {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedSecret {}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        clear(&mut self.0);
+    }
+}
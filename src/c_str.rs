@@ -0,0 +1,256 @@
+//! Conversions between Rust strings and the NUL-terminated strings used throughout RIOT
+//!
+//! RIOT structs often contain fixed-size `char` arrays (thread names, file names, interface names
+//! etc.), and RIOT functions take and return `char` pointers. This module provides checked
+//! conversions for both directions, and a [CStrBuf] for building NUL-terminated strings on the
+//! stack (eg. to pass a `&str` to a C function).
+//!
+//! (This module is not called `cstr` to not collide with the re-exported [cstr](crate::cstr)
+//! crate.)
+
+use core::ffi::{c_char, CStr};
+use core::fmt;
+
+use crate::error::NumericError;
+
+/// Error converting between Rust and C strings
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The string (with its terminating NUL byte) does not fit in the available space
+    TooLong,
+    /// The Rust string contains a NUL byte, which would terminate it early in C
+    InteriorNul,
+    /// The C buffer contains no terminating NUL byte
+    Unterminated,
+}
+
+impl From<Error> for NumericError {
+    fn from(e: Error) -> NumericError {
+        match e {
+            Error::TooLong => crate::error::ENOMEM,
+            Error::InteriorNul | Error::Unterminated => crate::error::EINVAL,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::TooLong => "String too long",
+            Error::InteriorNul => "String contains NUL byte",
+            Error::Unterminated => "String is not NUL-terminated",
+        })
+    }
+}
+
+fn as_bytes(buf: &[c_char]) -> &[u8] {
+    // unsafe: c_char is either u8 or i8, which have the same layout
+    unsafe { core::slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len()) }
+}
+
+fn as_bytes_mut(buf: &mut [c_char]) -> &mut [u8] {
+    // unsafe: c_char is either u8 or i8, which have the same layout, and any byte is valid in both
+    unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, buf.len()) }
+}
+
+/// Read a NUL-terminated string from a fixed-size C buffer
+///
+/// Anything after the first NUL byte is ignored.
+pub fn from_c_buf(buf: &[c_char]) -> Result<&CStr, Error> {
+    CStr::from_bytes_until_nul(as_bytes(buf)).map_err(|_| Error::Unterminated)
+}
+
+/// Read a NUL-terminated UTF-8 string from a fixed-size C buffer
+///
+/// This returns None if the buffer is not terminated or not valid UTF-8.
+pub fn str_from_c_buf(buf: &[c_char]) -> Option<&str> {
+    from_c_buf(buf).ok()?.to_str().ok()
+}
+
+/// Copy a string into a fixed-size C buffer, terminating it with a NUL byte
+///
+/// Any remaining bytes of the buffer are left unmodified.
+pub fn copy_to_c_buf<'a>(s: &str, buf: &'a mut [c_char]) -> Result<&'a CStr, Error> {
+    if s.as_bytes().contains(&0) {
+        return Err(Error::InteriorNul);
+    }
+    let buf = as_bytes_mut(buf);
+    if s.len() >= buf.len() {
+        return Err(Error::TooLong);
+    }
+    buf[..s.len()].copy_from_slice(s.as_bytes());
+    buf[s.len()] = 0;
+    CStr::from_bytes_with_nul(&buf[..=s.len()]).map_err(|_| Error::InteriorNul)
+}
+
+/// Format into a fixed-size C buffer, terminating the output with a NUL byte
+///
+/// Any remaining bytes of the buffer are left unmodified.
+pub fn format_to_c_buf<'a>(buf: &'a mut [c_char], args: fmt::Arguments) -> Result<&'a CStr, Error> {
+    struct Cursor<'b> {
+        buf: &'b mut [u8],
+        len: usize,
+        error: Option<Error>,
+    }
+
+    impl fmt::Write for Cursor<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if s.as_bytes().contains(&0) {
+                self.error = Some(Error::InteriorNul);
+                return Err(fmt::Error);
+            }
+            // Leaving space for the NUL byte
+            if self.len + s.len() >= self.buf.len() {
+                self.error = Some(Error::TooLong);
+                return Err(fmt::Error);
+            }
+            self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            Ok(())
+        }
+    }
+
+    let mut cursor = Cursor {
+        buf: as_bytes_mut(buf),
+        len: 0,
+        error: None,
+    };
+    if cursor.buf.is_empty() {
+        return Err(Error::TooLong);
+    }
+    if fmt::write(&mut cursor, args).is_err() {
+        // Errors from Display implementations are not distinguishable from our own; reporting
+        // them as length errors is the best approximation.
+        return Err(cursor.error.unwrap_or(Error::TooLong));
+    }
+    let Cursor { buf, len, .. } = cursor;
+    buf[len] = 0;
+    CStr::from_bytes_with_nul(&buf[..=len]).map_err(|_| Error::InteriorNul)
+}
+
+/// Turn a C string pointer into a CStr, or None if it is the null pointer
+///
+/// # Safety
+///
+/// Unless null, the pointer needs to satisfy the requirements of [`CStr::from_ptr()`] for the
+/// chosen lifetime.
+pub unsafe fn from_ptr<'a>(ptr: *const c_char) -> Option<&'a CStr> {
+    if ptr.is_null() {
+        None
+    } else {
+        // unsafe: Requirements forwarded
+        Some(unsafe { CStr::from_ptr(ptr) })
+    }
+}
+
+/// A NUL-terminated string of at most `N - 1` bytes stored inline
+///
+/// This is typically used to pass a `&str` to a C function:
+///
+/// ```
+/// # #![no_std]
+/// # #![no_main]
+/// # use riot_wrappers::c_str::CStrBuf;
+/// # fn f(name: &str) -> Result<(), riot_wrappers::c_str::Error> {
+/// let name: CStrBuf<16> = CStrBuf::try_from_str(name)?;
+/// # let _ = name.as_ptr();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// It also implements [fmt::Write], so it can be the target of `write!`.
+#[derive(Clone)]
+pub struct CStrBuf<const N: usize> {
+    // Invariant: buf[len] is 0, and there is no 0 in buf[..len]
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> CStrBuf<N> {
+    /// Create an empty string
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "A CStrBuf needs space for at least the NUL byte") };
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Create a buffer containing `s`
+    pub fn try_from_str(s: &str) -> Result<Self, Error> {
+        let mut result = Self::new();
+        result.push_str(s)?;
+        Ok(result)
+    }
+
+    /// Append `s` to the string
+    ///
+    /// On error, the string is left unmodified.
+    pub fn push_str(&mut self, s: &str) -> Result<(), Error> {
+        if s.as_bytes().contains(&0) {
+            return Err(Error::InteriorNul);
+        }
+        if self.len + s.len() >= N {
+            return Err(Error::TooLong);
+        }
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        self.buf[self.len] = 0;
+        Ok(())
+    }
+
+    /// Remove all content
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.buf[0] = 0;
+    }
+
+    /// Length of the string in bytes, excluding the terminating NUL byte
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_cstr(&self) -> &CStr {
+        // unsafe: Guaranteed by the struct's invariant
+        unsafe { CStr::from_bytes_with_nul_unchecked(&self.buf[..=self.len]) }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // unsafe: Only ever populated from str
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    /// Pointer to the NUL-terminated string, valid for as long as self is not modified or moved
+    pub fn as_ptr(&self) -> *const c_char {
+        self.buf.as_ptr() as _
+    }
+}
+
+impl<const N: usize> Default for CStrBuf<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AsRef<CStr> for CStrBuf<N> {
+    fn as_ref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl<const N: usize> fmt::Write for CStrBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Debug for CStrBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_cstr().fmt(f)
+    }
+}
@@ -0,0 +1,198 @@
+//! Checksums from RIOT's [checksum] module
+//!
+//! The CRCs are available as streaming types with `update()` / `finish()` methods. There is no
+//! dedicated CRC trait; the common interface is [core::hash::Hasher], which they all implement, and
+//! through which they can be used where any hashing state is accepted. Note that its `finish()`
+//! widens the result to a `u64`; the inherent `finish()` methods return the CRC's own width.
+//! Fletcher checksums are available as one-shot functions.
+//!
+//! [checksum]: https://doc.riot-os.org/group__sys__checksum.html
+
+macro_rules! crc16_ccitt {
+    (
+        $(#[$meta:meta])*
+        $name:ident,
+        $update:path,
+        $init:expr,
+        $xorout:expr,
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone)]
+        pub struct $name(u16);
+
+        impl $name {
+            /// Start a new checksum computation
+            pub fn new() -> Self {
+                Self($init)
+            }
+
+            /// Feed data into the checksum computation
+            pub fn update(&mut self, data: &[u8]) {
+                // unsafe: C API as documented
+                self.0 = unsafe { $update(self.0, data.as_ptr(), data.len() as _) };
+            }
+
+            /// Obtain the checksum of all data that was fed in so far
+            pub fn finish(&self) -> u16 {
+                self.0 ^ $xorout
+            }
+
+            /// Compute the checksum of some data in a single call
+            pub fn checksum(data: &[u8]) -> u16 {
+                let mut crc = Self::new();
+                crc.update(data);
+                crc.finish()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl core::hash::Hasher for $name {
+            fn write(&mut self, bytes: &[u8]) {
+                self.update(bytes)
+            }
+
+            fn finish(&self) -> u64 {
+                $name::finish(self).into()
+            }
+        }
+    };
+}
+
+crc16_ccitt!(
+    /// CRC-16/KERMIT (reflected CCITT polynomial, initial value 0)
+    #[doc(alias = "crc16_ccitt_kermit_update")]
+    Crc16CcittKermit,
+    riot_sys::crc16_ccitt_kermit_update,
+    0,
+    0,
+);
+
+crc16_ccitt!(
+    /// CRC-16/IBM-SDLC, the frame check sequence of HDLC and IEEE 802.15.4 (reflected CCITT
+    /// polynomial, initial value and output XOR 0xffff)
+    #[doc(alias = "crc16_ccitt_fcs_update")]
+    Crc16CcittFcs,
+    // The fcs functions are inline wrappers around this
+    riot_sys::crc16_ccitt_kermit_update,
+    0xffff,
+    0xffff,
+);
+
+crc16_ccitt!(
+    /// CRC-16/CCITT-FALSE (non-reflected CCITT polynomial, initial value 0xffff)
+    #[doc(alias = "crc16_ccitt_false_update")]
+    Crc16CcittFalse,
+    riot_sys::crc16_ccitt_false_update,
+    0xffff,
+    0,
+);
+
+/// CRC-8 with a custom polynomial
+///
+/// The polynomial is given in non-reflected notation without the leading term (eg. 0x07 for
+/// CRC-8/SMBUS, or 0x31 for the CRC used by Sensirion sensors).
+#[doc(alias = "crc8")]
+#[derive(Debug, Copy, Clone)]
+pub struct Crc8 {
+    poly: u8,
+    crc: u8,
+}
+
+impl Crc8 {
+    /// Start a new checksum computation with a given polynomial and initial value
+    pub fn new(poly: u8, seed: u8) -> Self {
+        Self { poly, crc: seed }
+    }
+
+    /// Feed data into the checksum computation
+    pub fn update(&mut self, data: &[u8]) {
+        // The CRC state is the seed for the continued computation
+        // unsafe: C API as documented
+        self.crc = unsafe { riot_sys::crc8(data.as_ptr(), data.len() as _, self.poly, self.crc) };
+    }
+
+    /// Obtain the checksum of all data that was fed in so far
+    pub fn finish(&self) -> u8 {
+        self.crc
+    }
+}
+
+impl core::hash::Hasher for Crc8 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        Crc8::finish(self).into()
+    }
+}
+
+/// CRC-32 as used in Ethernet, zlib and PNG (CRC-32/ISO-HDLC: reflected polynomial 0x04c11db7,
+/// initial value and output XOR 0xffffffff)
+#[doc(alias = "crc32")]
+#[doc(alias = "crc32_update")]
+#[derive(Debug, Copy, Clone)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    /// Start a new checksum computation
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Feed data into the checksum computation
+    pub fn update(&mut self, data: &[u8]) {
+        // The finished CRC of the data so far is the seed for the continued computation
+        // unsafe: C API as documented
+        self.0 =
+            unsafe { riot_sys::crc32_update(self.0, data.as_ptr() as *const _, data.len() as _) };
+    }
+
+    /// Obtain the checksum of all data that was fed in so far
+    pub fn finish(&self) -> u32 {
+        self.0
+    }
+
+    /// Compute the checksum of some data in a single call
+    pub fn checksum(data: &[u8]) -> u32 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::crc32(data.as_ptr() as *const _, data.len() as _) }
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::hash::Hasher for Crc32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        Crc32::finish(self).into()
+    }
+}
+
+/// Fletcher-16 checksum of some data
+#[doc(alias = "fletcher16")]
+pub fn fletcher16(data: &[u8]) -> u16 {
+    // unsafe: C API as documented
+    unsafe { riot_sys::fletcher16(data.as_ptr(), data.len() as _) }
+}
+
+/// Fletcher-32 checksum of some data
+///
+/// The data is processed in 16-bit words in host byte order.
+#[doc(alias = "fletcher32")]
+pub fn fletcher32(data: &[u16]) -> u32 {
+    // unsafe: C API as documented
+    unsafe { riot_sys::fletcher32(data.as_ptr(), data.len() as _) }
+}
@@ -0,0 +1,217 @@
+//! Color representations and conversions from [RIOT's color module](https://doc.riot-os.org/group__sys__color.html)
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::color::{Hsv, Rgb};
+//! # fn f() {
+//! let orange: Rgb = "ff8000".parse().unwrap();
+//! let mut hsv = Hsv::from(orange);
+//! hsv.v /= 2.0;
+//! let dimmed = Rgb::from(hsv);
+//! # }
+//! ```
+
+use core::fmt;
+
+use riot_sys::{color_hsv_t, color_rgb_t};
+
+/// A color in the RGB color space, with 8 bits per channel
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// A color in the HSV color space
+///
+/// The hue is given in degrees (0 to 360), saturation and value in the range from 0 to 1.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+/// Error parsing a color from a string
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError;
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    pub(crate) fn to_c(self) -> color_rgb_t {
+        color_rgb_t {
+            r: self.r,
+            g: self.g,
+            b: self.b,
+        }
+    }
+
+    pub(crate) fn from_c(c: color_rgb_t) -> Self {
+        Self {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+        }
+    }
+
+    /// The color with all channels inverted
+    #[doc(alias = "color_rgb_invert")]
+    pub fn invert(self) -> Self {
+        let input = self.to_c();
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::inline::color_rgb_invert(
+                crate::inline_cast(&input as *const _),
+                crate::inline_cast_mut(&mut output as *mut _),
+            )
+        };
+        Self::from_c(output)
+    }
+
+    /// The color with all channels shifted left (for positive values) or right (for negative
+    /// values) by the given number of bits
+    #[doc(alias = "color_rgb_shift")]
+    pub fn shift(self, shift: i8) -> Self {
+        let input = self.to_c();
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::inline::color_rgb_shift(
+                crate::inline_cast(&input as *const _),
+                crate::inline_cast_mut(&mut output as *mut _),
+                shift,
+            )
+        };
+        Self::from_c(output)
+    }
+
+    /// The color with all channels scaled by `level / 255`
+    #[doc(alias = "color_rgb_set_brightness")]
+    pub fn with_brightness(self, level: u8) -> Self {
+        let input = self.to_c();
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::inline::color_rgb_set_brightness(
+                crate::inline_cast(&input as *const _),
+                crate::inline_cast_mut(&mut output as *mut _),
+                level,
+            )
+        };
+        Self::from_c(output)
+    }
+
+    /// The complementary color (the one with opposite hue)
+    #[doc(alias = "color_rgb_complementary")]
+    pub fn complementary(self) -> Self {
+        let input = self.to_c();
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented
+        unsafe { riot_sys::color_rgb_complementary(&input, &mut output) };
+        Self::from_c(output)
+    }
+
+    /// A color on the linear gradient between `self` (at position 0) and `other` (at position 255)
+    ///
+    /// Unlike the other operations, this is not provided by RIOT but implemented in Rust.
+    pub fn mix(self, other: Self, position: u8) -> Self {
+        let channel = |a: u8, b: u8| {
+            let (a, b, position) = (a as u16, b as u16, position as u16);
+            ((a * (255 - position) + b * position + 127) / 255) as u8
+        };
+        Self {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+        }
+    }
+}
+
+impl From<Hsv> for Rgb {
+    #[doc(alias = "color_hsv2rgb")]
+    fn from(hsv: Hsv) -> Self {
+        let mut input = color_hsv_t {
+            h: hsv.h,
+            s: hsv.s,
+            v: hsv.v,
+        };
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented (input is not written to despite being a mut pointer)
+        unsafe { riot_sys::color_hsv2rgb(&mut input, &mut output) };
+        Self::from_c(output)
+    }
+}
+
+impl From<Rgb> for Hsv {
+    #[doc(alias = "color_rgb2hsv")]
+    fn from(rgb: Rgb) -> Self {
+        let mut input = rgb.to_c();
+        let mut output = color_hsv_t::default();
+        // unsafe: C API as documented (input is not written to despite being a mut pointer)
+        unsafe { riot_sys::color_rgb2hsv(&mut input, &mut output) };
+        Self {
+            h: output.h,
+            s: output.s,
+            v: output.v,
+        }
+    }
+}
+
+/// Conversion from a number of the form `0xRRGGBB`
+impl From<u32> for Rgb {
+    #[doc(alias = "color_hex2rgb")]
+    fn from(hex: u32) -> Self {
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented
+        unsafe { riot_sys::color_hex2rgb(hex, &mut output) };
+        Self::from_c(output)
+    }
+}
+
+/// Conversion into a number of the form `0xRRGGBB`
+impl From<Rgb> for u32 {
+    #[doc(alias = "color_rgb2hex")]
+    fn from(rgb: Rgb) -> Self {
+        let input = rgb.to_c();
+        let mut hex = 0;
+        // unsafe: C API as documented
+        unsafe { riot_sys::color_rgb2hex(&input, &mut hex) };
+        hex
+    }
+}
+
+/// Parsing of six hex digits in the form `RRGGBB`, optionally preceded by a `#`
+impl core::str::FromStr for Rgb {
+    type Err = ParseError;
+
+    #[doc(alias = "color_str2rgb")]
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.strip_prefix('#').unwrap_or(s);
+        // color_str2rgb does no validation
+        if s.len() != 6 || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseError);
+        }
+        let mut output = color_rgb_t::default();
+        // unsafe: C API as documented; it reads exactly 6 characters
+        unsafe { riot_sys::color_str2rgb(s.as_ptr() as _, &mut output) };
+        Ok(Self::from_c(output))
+    }
+}
+
+/// Formatting as six hex digits in the form `RRGGBB`
+impl fmt::Display for Rgb {
+    #[doc(alias = "color_rgb2str")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = self.to_c();
+        let mut buf = [0u8; 6];
+        // unsafe: C API as documented; it writes exactly 6 characters
+        unsafe { riot_sys::color_rgb2str(&input, buf.as_mut_ptr() as _) };
+        f.write_str(core::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
@@ -0,0 +1,201 @@
+//! Registration of (D)TLS credentials with [RIOT's credential
+//! manager](https://doc.riot-os.org/group__net__credman.html)
+//!
+//! Credentials are identified by a tag; components such as DTLS sockets are then told which tag's
+//! credentials to use. As credman only stores references to the key material, all keys need to be
+//! `'static`:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::credman::{self, Credential};
+//! # fn f() -> Result<(), credman::Error> {
+//! static KEY: &[u8] = b"secretPSK";
+//! static ID: &[u8] = b"Client_identity";
+//!
+//! credman::add(&Credential::psk(10, KEY, Some(ID), None))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use riot_sys::{credman_credential_t, credman_tag_t};
+
+use crate::error::NumericError;
+
+/// Error from a credman operation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A credential with the same tag and type is already registered
+    #[doc(alias = "CREDMAN_EXIST")]
+    Exists,
+    /// The credential pool is full
+    #[doc(alias = "CREDMAN_NO_SPACE")]
+    NoSpace,
+    /// No credential with the given tag and type is registered
+    #[doc(alias = "CREDMAN_NOT_FOUND")]
+    NotFound,
+    /// The credential is invalid (eg. lacks a key)
+    #[doc(alias = "CREDMAN_INVALID")]
+    Invalid,
+    /// Any other error, given by its credman status code
+    Other(i32),
+}
+
+impl Error {
+    fn from_status(status: riot_sys::credman_status_t) -> Result<(), Self> {
+        Err(match status {
+            riot_sys::credman_status_t_CREDMAN_OK => return Ok(()),
+            riot_sys::credman_status_t_CREDMAN_EXIST => Error::Exists,
+            riot_sys::credman_status_t_CREDMAN_NO_SPACE => Error::NoSpace,
+            riot_sys::credman_status_t_CREDMAN_NOT_FOUND => Error::NotFound,
+            riot_sys::credman_status_t_CREDMAN_INVALID => Error::Invalid,
+            other => Error::Other(other as _),
+        })
+    }
+}
+
+impl From<Error> for NumericError {
+    fn from(e: Error) -> NumericError {
+        match e {
+            Error::Exists => crate::error::EEXIST,
+            Error::NoSpace => crate::error::ENOMEM,
+            Error::NotFound => crate::error::ENOENT,
+            Error::Invalid | Error::Other(_) => crate::error::EINVAL,
+        }
+    }
+}
+
+/// Tag identifying a set of credentials
+pub type Tag = credman_tag_t;
+
+/// Kind of a credential, used together with the tag to identify it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// Pre-shared key
+    #[doc(alias = "CREDMAN_TYPE_PSK")]
+    Psk,
+    /// Raw public key on the ECDSA P-256 curve
+    #[doc(alias = "CREDMAN_TYPE_ECDSA")]
+    Ecdsa,
+}
+
+impl Type {
+    fn to_c(self) -> riot_sys::credman_type_t {
+        match self {
+            Type::Psk => riot_sys::credman_type_t_CREDMAN_TYPE_PSK,
+            Type::Ecdsa => riot_sys::credman_type_t_CREDMAN_TYPE_ECDSA,
+        }
+    }
+}
+
+/// A public key on the ECDSA P-256 curve, given by its coordinates
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+#[doc(alias = "ecdsa_public_key_t")]
+pub struct EcdsaPublicKey(riot_sys::ecdsa_public_key_t);
+
+impl EcdsaPublicKey {
+    pub fn new(x: &'static [u8; 32], y: &'static [u8; 32]) -> Self {
+        Self(riot_sys::ecdsa_public_key_t {
+            x: x.as_ptr() as _,
+            y: y.as_ptr() as _,
+        })
+    }
+}
+
+/// A credential that can be [added](add) to credman
+#[doc(alias = "credman_credential_t")]
+pub struct Credential(credman_credential_t);
+
+fn buffer(data: &'static [u8]) -> riot_sys::credman_buffer_t {
+    riot_sys::credman_buffer_t {
+        s: data.as_ptr() as _,
+        len: data.len() as _,
+    }
+}
+
+fn optional_buffer(data: Option<&'static [u8]>) -> riot_sys::credman_buffer_t {
+    data.map(buffer).unwrap_or_default()
+}
+
+impl Credential {
+    /// A pre-shared key, optionally with the identity (for clients) and the identity hint (for
+    /// servers) that accompany it
+    pub fn psk(
+        tag: Tag,
+        key: &'static [u8],
+        id: Option<&'static [u8]>,
+        hint: Option<&'static [u8]>,
+    ) -> Self {
+        let mut credential = credman_credential_t::default();
+        credential.type_ = Type::Psk.to_c();
+        credential.tag = tag;
+        credential.params.psk = riot_sys::psk_params_t {
+            key: buffer(key),
+            id: optional_buffer(id),
+            hint: optional_buffer(hint),
+        };
+        Self(credential)
+    }
+
+    /// A raw public key pair, along with the public keys of peers that are accepted
+    ///
+    /// The client keys are not written to, but RIOT's API only takes them as a mutable pointer.
+    pub fn ecdsa(
+        tag: Tag,
+        private_key: &'static [u8; 32],
+        public_key: EcdsaPublicKey,
+        client_keys: &'static mut [EcdsaPublicKey],
+    ) -> Self {
+        let mut credential = credman_credential_t::default();
+        credential.type_ = Type::Ecdsa.to_c();
+        credential.tag = tag;
+        credential.params.ecdsa = riot_sys::ecdsa_params_t {
+            private_key: private_key.as_ptr() as _,
+            public_key: public_key.0,
+            // repr(transparent) allows the cast
+            client_keys: client_keys.as_mut_ptr() as *mut riot_sys::ecdsa_public_key_t,
+            client_keys_size: client_keys.len() as _,
+        };
+        Self(credential)
+    }
+
+    pub fn tag(&self) -> Tag {
+        self.0.tag
+    }
+}
+
+/// Register a credential
+///
+/// The credential data is copied; the key material it references is static anyway.
+#[doc(alias = "credman_add")]
+pub fn add(credential: &Credential) -> Result<(), Error> {
+    // unsafe: C API as documented
+    Error::from_status(unsafe { riot_sys::credman_add(&credential.0) })
+}
+
+/// Remove a credential by its tag and type
+///
+/// Removing a credential that is not registered has no effect.
+#[doc(alias = "credman_delete")]
+pub fn delete(tag: Tag, type_: Type) {
+    // unsafe: C API as documented
+    unsafe { riot_sys::credman_delete(tag, type_.to_c()) };
+}
+
+/// Whether a credential of the given tag and type is registered
+#[doc(alias = "credman_get")]
+pub fn contains(tag: Tag, type_: Type) -> bool {
+    let mut credential = credman_credential_t::default();
+    // unsafe: C API as documented
+    let status = unsafe { riot_sys::credman_get(&mut credential, tag, type_.to_c()) };
+    status == riot_sys::credman_status_t_CREDMAN_OK
+}
+
+/// Number of registered credentials
+#[doc(alias = "credman_get_used_count")]
+pub fn count() -> usize {
+    // unsafe: C API as documented
+    unsafe { riot_sys::credman_get_used_count() as _ }
+}
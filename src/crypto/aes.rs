@@ -0,0 +1,148 @@
+//! AES block ciphers
+//!
+//! The types in here are available depending on which of the `crypto_aes_128`,
+//! `crypto_aes_192` and `crypto_aes_256` modules are enabled in RIOT.
+//!
+//! Blocks are en- and decrypted through the [cipher::BlockEncrypt] and [cipher::BlockDecrypt]
+//! traits.
+
+use cipher::consts::U16;
+use cipher::inout::InOut;
+use cipher::{Block, BlockBackend, BlockClosure, BlockSizeUser, ParBlocksSizeUser};
+
+use crate::error::{NegativeErrorExt, NumericError};
+
+/// Initialize a `cipher_t` for AES with a key of the given length
+fn init(key: &[u8]) -> Result<riot_sys::cipher_t, NumericError> {
+    let mut cipher = core::mem::MaybeUninit::uninit();
+    // unsafe: C API as documented; CIPHER_AES is a constant pointer to the AES interface.
+    unsafe {
+        riot_sys::cipher_init(
+            cipher.as_mut_ptr(),
+            riot_sys::CIPHER_AES,
+            key.as_ptr(),
+            key.len() as _,
+        )
+    }
+    .negative_to_error()?;
+    // unsafe: Initialized as the call was successful
+    Ok(unsafe { cipher.assume_init() })
+}
+
+struct EncryptBackend<'a>(&'a riot_sys::cipher_t);
+struct DecryptBackend<'a>(&'a riot_sys::cipher_t);
+
+impl BlockSizeUser for EncryptBackend<'_> {
+    type BlockSize = U16;
+}
+
+impl BlockSizeUser for DecryptBackend<'_> {
+    type BlockSize = U16;
+}
+
+// RIOT's cipher API processes single blocks.
+impl ParBlocksSizeUser for EncryptBackend<'_> {
+    type ParBlocksSize = cipher::consts::U1;
+}
+
+impl ParBlocksSizeUser for DecryptBackend<'_> {
+    type ParBlocksSize = cipher::consts::U1;
+}
+
+impl BlockBackend for EncryptBackend<'_> {
+    #[doc(alias = "cipher_encrypt")]
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+        let mut out = Block::<Self>::default();
+        // unsafe: C API as documented; input and output are a block long. The function can not
+        // fail on an initialized AES cipher.
+        unsafe { riot_sys::cipher_encrypt(self.0, block.get_in().as_ptr(), out.as_mut_ptr()) };
+        *block.get_out() = out;
+    }
+}
+
+impl BlockBackend for DecryptBackend<'_> {
+    #[doc(alias = "cipher_decrypt")]
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+        let mut out = Block::<Self>::default();
+        // unsafe: as in encryption
+        unsafe { riot_sys::cipher_decrypt(self.0, block.get_in().as_ptr(), out.as_mut_ptr()) };
+        *block.get_out() = out;
+    }
+}
+
+macro_rules! aes {
+    (#[cfg($cfg:meta)] $(#[$meta:meta])* $name:ident, $keysize:ty) => {
+        #[cfg($cfg)]
+        $(#[$meta])*
+        pub struct $name(riot_sys::cipher_t);
+
+        #[cfg($cfg)]
+        impl $name {
+            /// Access to the underlying `cipher_t`, eg. for use with RIOT's cipher modes
+            pub fn as_cipher(&self) -> &riot_sys::cipher_t {
+                &self.0
+            }
+        }
+
+        #[cfg($cfg)]
+        impl cipher::KeySizeUser for $name {
+            type KeySize = $keysize;
+        }
+
+        #[cfg($cfg)]
+        impl cipher::KeyInit for $name {
+            #[doc(alias = "cipher_init")]
+            fn new(key: &cipher::Key<Self>) -> Self {
+                // The key length is checked through its type, and the module providing this type
+                // is enabled.
+                Self(
+                    init(key.as_slice())
+                        .expect("AES initialization can not fail with correct key length"),
+                )
+            }
+        }
+
+        #[cfg($cfg)]
+        impl BlockSizeUser for $name {
+            type BlockSize = U16;
+        }
+
+        #[cfg($cfg)]
+        impl cipher::BlockCipher for $name {}
+
+        #[cfg($cfg)]
+        impl cipher::BlockEncrypt for $name {
+            fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+                f.call(&mut EncryptBackend(&self.0))
+            }
+        }
+
+        #[cfg($cfg)]
+        impl cipher::BlockDecrypt for $name {
+            fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+                f.call(&mut DecryptBackend(&self.0))
+            }
+        }
+    };
+}
+
+aes!(
+    #[cfg(riot_module_crypto_aes_128)]
+    /// AES with a 128 bit key
+    Aes128,
+    cipher::consts::U16
+);
+
+aes!(
+    #[cfg(riot_module_crypto_aes_192)]
+    /// AES with a 192 bit key
+    Aes192,
+    cipher::consts::U24
+);
+
+aes!(
+    #[cfg(riot_module_crypto_aes_256)]
+    /// AES with a 256 bit key
+    Aes256,
+    cipher::consts::U32
+);
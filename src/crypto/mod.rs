@@ -0,0 +1,19 @@
+//! Symmetric cryptography from RIOT's [crypto] module
+//!
+//! The block ciphers in [aes] implement the traits of the [cipher] crate, so they can be used with
+//! RustCrypto based protocol implementations. Where RIOT uses hardware acceleration for them, that
+//! is transparent to users of this module.
+//!
+//! The [modes] turn them into usable encryption schemes, including authenticated encryption.
+//!
+//! [crypto]: https://doc.riot-os.org/group__sys__crypto.html
+
+#[cfg(any(
+    riot_module_crypto_aes_128,
+    riot_module_crypto_aes_192,
+    riot_module_crypto_aes_256
+))]
+pub mod aes;
+
+#[cfg(riot_module_cipher_modes)]
+pub mod modes;
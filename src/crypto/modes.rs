@@ -0,0 +1,313 @@
+//! Block cipher modes of operation from RIOT's [cipher_modes] module
+//!
+//! All modes work on a `cipher_t` as obtained from the block ciphers' `as_cipher()` method (eg.
+//! [`Aes128::as_cipher()`](super::aes::Aes128::as_cipher)), and operate in place on the user's
+//! buffer:
+//!
+//! * [Ccm] provides authenticated encryption, with an API modelled after the `aead` crate's
+//!   `AeadInPlace` trait (but with the tag stored in the buffer after the message, as RIOT's
+//!   implementation requires).
+//! * [Ctr] provides unauthenticated stream encryption.
+//! * [`cbc_encrypt_in_place()`] and [`cbc_decrypt_in_place()`] provide unauthenticated encryption
+//!   of whole blocks; padding is left to the user.
+//!
+//! [cipher_modes]: https://doc.riot-os.org/group__sys__crypto__modes.html
+
+use riot_sys::cipher_t;
+
+/// Block size of all ciphers usable with the modes
+///
+/// (RIOT's cipher modes are hardcoded to 128 bit block ciphers).
+pub const BLOCK_SIZE: usize = 16;
+
+/// Error type of the cipher modes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A nonce, tag, message or buffer length was not acceptable for the operation
+    InvalidLength,
+    /// Decryption failed because the message was not authentic
+    ///
+    /// The buffer content is not usable in that case (and is cleared where possible).
+    AuthenticationFailed,
+}
+
+/// Authenticated encryption in Counter with CBC-MAC mode ([RFC 3610])
+///
+/// The nonce length and tag length are fixed for an instance. A nonce must never be used twice
+/// with the same key.
+///
+/// [RFC 3610]: https://www.rfc-editor.org/rfc/rfc3610
+pub struct Ccm<'a> {
+    cipher: &'a cipher_t,
+    tag_len: u8,
+    // "L" in RFC 3610
+    length_encoding: u8,
+}
+
+impl<'a> Ccm<'a> {
+    /// Set up CCM mode with a given tag length (4 to 16, even) and nonce length (7 to 13)
+    ///
+    /// The nonce length determines the maximum message length: a 13 byte nonce allows for messages
+    /// up to 64KiB.
+    pub fn new(cipher: &'a cipher_t, tag_len: usize, nonce_len: usize) -> Result<Self, Error> {
+        if !(4..=16).contains(&tag_len) || tag_len % 2 != 0 || !(7..=13).contains(&nonce_len) {
+            return Err(Error::InvalidLength);
+        }
+        Ok(Self {
+            cipher,
+            tag_len: tag_len as _,
+            length_encoding: (15 - nonce_len) as _,
+        })
+    }
+
+    /// Length of the authentication tag appended to every message
+    pub fn tag_len(&self) -> usize {
+        self.tag_len.into()
+    }
+
+    /// Length of the nonces accepted by this instance
+    pub fn nonce_len(&self) -> usize {
+        15 - usize::from(self.length_encoding)
+    }
+
+    fn check_lengths(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        message_len: usize,
+    ) -> Result<(), Error> {
+        // The message length needs to be expressible in L bytes, and the result in an int
+        let max_message_len = 1u64
+            .checked_shl(8 * u32::from(self.length_encoding))
+            .unwrap_or(u64::MAX)
+            .min(i32::MAX as u64 - BLOCK_SIZE as u64);
+        if nonce.len() != self.nonce_len()
+            // RIOT only implements the short encodings of the associated data length
+            || associated_data.len() >= 0xff00
+            || message_len as u64 >= max_message_len
+        {
+            return Err(Error::InvalidLength);
+        }
+        Ok(())
+    }
+
+    /// Encrypt the first `plaintext_len` bytes of `buffer` in place, and write the tag right after
+    /// them
+    ///
+    /// The buffer needs to be at least [`.tag_len()`](Self::tag_len) bytes longer than the
+    /// plaintext. On success, the length of the ciphertext (including the tag) is returned.
+    #[doc(alias = "cipher_encrypt_ccm")]
+    pub fn encrypt_in_place(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        plaintext_len: usize,
+    ) -> Result<usize, Error> {
+        self.check_lengths(nonce, associated_data, plaintext_len)?;
+        if plaintext_len
+            .checked_add(self.tag_len())
+            .map_or(true, |l| l > buffer.len())
+        {
+            return Err(Error::InvalidLength);
+        }
+        // A single pointer for input and output, as a shared one would be invalidated by the
+        // mutable one
+        let data = buffer.as_mut_ptr();
+        // unsafe: C API as documented. The lengths were checked above. Operating in place is
+        // supported as the MAC is calculated before any output is written, and the CTR step
+        // reads every input byte before writing the output byte at the same position.
+        let result = unsafe {
+            riot_sys::cipher_encrypt_ccm(
+                self.cipher,
+                associated_data.as_ptr(),
+                associated_data.len() as _,
+                self.tag_len,
+                self.length_encoding,
+                nonce.as_ptr(),
+                nonce.len() as _,
+                data,
+                plaintext_len as _,
+                data,
+            )
+        };
+        if result < 0 {
+            return Err(Error::InvalidLength);
+        }
+        Ok(result as _)
+    }
+
+    /// Decrypt a message (ciphertext followed by the tag) in place
+    ///
+    /// On success, the plaintext is in the first bytes of the buffer, and their number is
+    /// returned. If authentication fails, the buffer is zeroed.
+    #[doc(alias = "cipher_decrypt_ccm")]
+    pub fn decrypt_in_place(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<usize, Error> {
+        let plaintext_len = buffer
+            .len()
+            .checked_sub(self.tag_len())
+            .ok_or(Error::InvalidLength)?;
+        self.check_lengths(nonce, associated_data, plaintext_len)?;
+        let len = buffer.len();
+        let data = buffer.as_mut_ptr();
+        // unsafe: C API as documented. The lengths were checked above. Operating in place is
+        // supported as the CTR step reads every input byte before writing the output byte at the
+        // same position, and the MAC is calculated over the output.
+        let result = unsafe {
+            riot_sys::cipher_decrypt_ccm(
+                self.cipher,
+                associated_data.as_ptr(),
+                associated_data.len() as _,
+                self.tag_len,
+                self.length_encoding,
+                nonce.as_ptr(),
+                nonce.len() as _,
+                data,
+                len as _,
+                data,
+            )
+        };
+        if result < 0 {
+            // All length errors were excluded by the checks above.
+            buffer.fill(0);
+            return Err(Error::AuthenticationFailed);
+        }
+        Ok(result as _)
+    }
+}
+
+/// Encryption in Counter mode
+///
+/// The counter block is advanced with every processed block, so a message can be processed in
+/// multiple calls to [`.apply_keystream()`](Self::apply_keystream) as long as all but the last
+/// pass in whole blocks.
+pub struct Ctr<'a> {
+    cipher: &'a cipher_t,
+    nonce_counter: [u8; BLOCK_SIZE],
+    nonce_len: u8,
+}
+
+impl<'a> Ctr<'a> {
+    /// Set up counter mode with an initial counter block
+    ///
+    /// The first `nonce_len` bytes of the counter block stay fixed; the remaining bytes are the
+    /// big-endian counter.
+    pub fn new(
+        cipher: &'a cipher_t,
+        nonce_counter: [u8; BLOCK_SIZE],
+        nonce_len: usize,
+    ) -> Result<Self, Error> {
+        if nonce_len >= BLOCK_SIZE {
+            return Err(Error::InvalidLength);
+        }
+        Ok(Self {
+            cipher,
+            nonce_counter,
+            nonce_len: nonce_len as _,
+        })
+    }
+
+    /// The counter block that will be used for the next block of data
+    pub fn nonce_counter(&self) -> &[u8; BLOCK_SIZE] {
+        &self.nonce_counter
+    }
+
+    /// En- or decrypt data in place (both are the same operation in counter mode)
+    #[doc(alias = "cipher_encrypt_ctr")]
+    pub fn apply_keystream(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        if buffer.len() > i32::MAX as usize {
+            return Err(Error::InvalidLength);
+        }
+        let len = buffer.len();
+        let data = buffer.as_mut_ptr();
+        // unsafe: C API as documented. Operating in place is supported as every input byte is
+        // read before the output byte at the same position is written.
+        let result = unsafe {
+            riot_sys::cipher_encrypt_ctr(
+                self.cipher,
+                self.nonce_counter.as_mut_ptr(),
+                self.nonce_len,
+                data,
+                len as _,
+                data,
+            )
+        };
+        if result < 0 {
+            return Err(Error::InvalidLength);
+        }
+        Ok(())
+    }
+}
+
+fn check_cbc_length(buffer: &[u8]) -> Result<(), Error> {
+    if buffer.len() % BLOCK_SIZE != 0 || buffer.len() > i32::MAX as usize {
+        return Err(Error::InvalidLength);
+    }
+    Ok(())
+}
+
+/// Encrypt data in place in Cipher Block Chaining mode
+///
+/// The buffer's length needs to be a multiple of [BLOCK_SIZE].
+#[doc(alias = "cipher_encrypt_cbc")]
+pub fn cbc_encrypt_in_place(
+    cipher: &cipher_t,
+    iv: &[u8; BLOCK_SIZE],
+    buffer: &mut [u8],
+) -> Result<(), Error> {
+    check_cbc_length(buffer)?;
+    if buffer.is_empty() {
+        return Ok(());
+    }
+    // The C function does not promise to leave the IV untouched
+    let mut iv = *iv;
+    let len = buffer.len();
+    let data = buffer.as_mut_ptr();
+    // unsafe: C API as documented. Operating in place is supported as every block is read before
+    // the same block is written, and only output blocks are used for chaining.
+    let result =
+        unsafe { riot_sys::cipher_encrypt_cbc(cipher, iv.as_mut_ptr(), data, len as _, data) };
+    if result < 0 {
+        return Err(Error::InvalidLength);
+    }
+    Ok(())
+}
+
+/// Decrypt data in place in Cipher Block Chaining mode
+///
+/// The buffer's length needs to be a multiple of [BLOCK_SIZE].
+#[doc(alias = "cipher_decrypt_cbc")]
+pub fn cbc_decrypt_in_place(
+    cipher: &cipher_t,
+    iv: &[u8; BLOCK_SIZE],
+    buffer: &mut [u8],
+) -> Result<(), Error> {
+    check_cbc_length(buffer)?;
+    let mut iv = *iv;
+    // Decryption chains with the ciphertext blocks, which would be overwritten if the whole
+    // buffer were processed in place at once; thus, go block by block and keep the ciphertext
+    // for the next IV.
+    for block in buffer.chunks_exact_mut(BLOCK_SIZE) {
+        let ciphertext: [u8; BLOCK_SIZE] = (*block).try_into().unwrap();
+        // unsafe: C API as documented; input and output are a single block
+        let result = unsafe {
+            riot_sys::cipher_decrypt_cbc(
+                cipher,
+                iv.as_mut_ptr(),
+                ciphertext.as_ptr(),
+                BLOCK_SIZE as _,
+                block.as_mut_ptr(),
+            )
+        };
+        if result < 0 {
+            return Err(Error::InvalidLength);
+        }
+        iv = ciphertext;
+    }
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! Debug pins from [RIOT's dbgpin module](https://doc.riot-os.org/group__sys__dbgpin.html)
+//!
+//! Debug pins are GPIOs configured for the purpose of observing the timing of code paths with a
+//! logic analyzer or oscilloscope. The pins are set up by RIOT at startup (from the board's or the
+//! application's `DBGPIN_PINS`), and are referred to by their index in that list.
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::dbgpin::DebugPin;
+//! # fn f() {
+//! let pin = DebugPin::new(0).expect("No debug pins configured");
+//! pin.high_during(|| {
+//!     // code whose execution time is measured
+//! });
+//! # }
+//! ```
+
+/// Number of configured debug pins
+#[doc(alias = "dbgpin_count")]
+pub fn count() -> usize {
+    // unsafe: C API as documented
+    unsafe { riot_sys::inline::dbgpin_count() as usize }
+}
+
+/// One configured debug pin
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DebugPin(riot_sys::libc::c_uint);
+
+impl DebugPin {
+    /// Access the debug pin with the given index, if it is configured
+    pub fn new(index: usize) -> Option<Self> {
+        if index < count() {
+            Some(Self(index as _))
+        } else {
+            None
+        }
+    }
+
+    /// Index of the pin in the list of debug pins
+    pub fn index(&self) -> usize {
+        self.0 as _
+    }
+
+    /// Set the pin to high level
+    #[doc(alias = "dbgpin_set")]
+    pub fn set(&self) {
+        // unsafe: C API as documented; index was checked at construction
+        unsafe { riot_sys::inline::dbgpin_set(self.0) }
+    }
+
+    /// Set the pin to low level
+    #[doc(alias = "dbgpin_clear")]
+    pub fn clear(&self) {
+        // unsafe: C API as documented; index was checked at construction
+        unsafe { riot_sys::inline::dbgpin_clear(self.0) }
+    }
+
+    /// Change the pin's level
+    #[doc(alias = "dbgpin_toggle")]
+    pub fn toggle(&self) {
+        // unsafe: C API as documented; index was checked at construction
+        unsafe { riot_sys::inline::dbgpin_toggle(self.0) }
+    }
+
+    /// Toggle the pin twice
+    #[doc(alias = "dbgpin_pulse")]
+    pub fn pulse(&self) {
+        // unsafe: C API as documented; index was checked at construction
+        unsafe { riot_sys::inline::dbgpin_pulse(self.0) }
+    }
+
+    /// Output a series of `count` pulses, eg. to mark which of several code paths was taken
+    #[doc(alias = "dbgpin_signal")]
+    pub fn signal(&self, count: u32) {
+        // unsafe: C API as documented; index was checked at construction
+        unsafe { riot_sys::inline::dbgpin_signal(self.0, count as _) }
+    }
+
+    /// Run a closure with the pin set to high level
+    pub fn high_during<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.high();
+        f()
+    }
+
+    /// Set the pin to high level until the returned guard is dropped
+    ///
+    /// This is useful for code paths with multiple exit points.
+    pub fn high(&self) -> HighGuard {
+        self.set();
+        HighGuard(*self)
+    }
+}
+
+/// Guard created by [`DebugPin::high()`] that sets the pin to low level when dropped
+#[must_use = "The pin goes low again when the guard is dropped"]
+pub struct HighGuard(DebugPin);
+
+impl Drop for HighGuard {
+    fn drop(&mut self) {
+        self.0.clear();
+    }
+}
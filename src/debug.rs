@@ -0,0 +1,116 @@
+//! Debug output and assertions that follow RIOT's [debug] conventions
+//!
+//! In C, RIOT modules `#define ENABLE_DEBUG 1` before including `debug.h` to make their `DEBUG()`
+//! statements produce output. The [`debug!`](crate::debug!) macro works the same way: it prints
+//! only if a constant `ENABLE_DEBUG` in the calling module is true, and otherwise compiles to
+//! nothing (while still checking the arguments):
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! const ENABLE_DEBUG: bool = false;
+//!
+//! # fn f(state: u8) {
+//! riot_wrappers::debug!("Entering state {}\n", state);
+//! # }
+//! ```
+//!
+//! [`riot_assert!`](crate::riot_assert!) behaves like RIOT's `assert()`: it is disabled when
+//! `NDEBUG` is set, and on failure it panics the whole system through `core_panic` (rather than
+//! just the thread, as a Rust panic typically does). `DEBUG_ASSERT_VERBOSE` and
+//! `DEBUG_ASSERT_BREAKPOINT` are respected.
+//!
+//! [debug]: https://doc.riot-os.org/debug_8h.html
+
+/// Whether assertions through [`riot_assert!`](crate::riot_assert!) are checked, which is the
+/// case unless RIOT is built with `NDEBUG`
+pub const ASSERTIONS_ENABLED: bool = cfg!(not(marker_ndebug));
+
+/// Print debug output if `ENABLE_DEBUG` is set in the calling module
+///
+/// Like RIOT's `DEBUG()` (and unlike `println!`), this does not add a newline.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if ENABLE_DEBUG {
+            $crate::debug::print(format_args!($($arg)*));
+        }
+    };
+}
+
+/// Check a condition like RIOT's `assert()`, and panic the system if it is false
+///
+/// With `DEBUG_ASSERT_VERBOSE`, the location of the failed assertion and the message (if one is
+/// given) are printed.
+#[macro_export]
+macro_rules! riot_assert {
+    ($cond:expr $(,)?) => {
+        if $crate::debug::ASSERTIONS_ENABLED && !$cond {
+            $crate::debug::assert_failure(file!(), line!(), None);
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if $crate::debug::ASSERTIONS_ENABLED && !$cond {
+            $crate::debug::assert_failure(file!(), line!(), Some(format_args!($($arg)+)));
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn print(args: core::fmt::Arguments) {
+    use core::fmt::Write;
+    let _ = crate::stdio::Stdio {}.write_fmt(args);
+}
+
+#[doc(hidden)]
+#[cold]
+#[inline(never)]
+pub fn assert_failure(file: &str, line: u32, message: Option<core::fmt::Arguments>) -> ! {
+    #[cfg(marker_debug_assert_verbose)]
+    {
+        use core::fmt::Write;
+        let mut stdio = crate::stdio::Stdio {};
+        if let Some(message) = message {
+            let _ = writeln!(stdio, "{}", message);
+        }
+        // Same format as RIOT's _assert_failure
+        let _ = write!(stdio, "{}:{} => ", file, line);
+    }
+    #[cfg(not(marker_debug_assert_verbose))]
+    let _ = (file, line, message);
+
+    #[cfg(marker_debug_assert_breakpoint)]
+    breakpoint();
+
+    // unsafe: C API as documented
+    unsafe {
+        riot_sys::core_panic(
+            riot_sys::core_panic_t_PANIC_ASSERT_FAIL,
+            c"FAILED ASSERTION.".as_ptr() as _,
+        )
+    }
+}
+
+/// Stop execution in the debugger, like RIOT's `DEBUG_BREAKPOINT()`
+///
+/// Where no debugger is attached, this typically results in a hard fault. On architectures for
+/// which no breakpoint instruction is known, this does nothing.
+#[doc(alias = "DEBUG_BREAKPOINT")]
+#[inline(always)]
+pub fn breakpoint() {
+    // unsafe: Breakpoint instructions have no effect on the program state
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        core::arch::asm!("bkpt #1")
+    };
+    // unsafe: Breakpoint instructions have no effect on the program state
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    unsafe {
+        core::arch::asm!("ebreak")
+    };
+    // unsafe: Breakpoint instructions have no effect on the program state
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    unsafe {
+        core::arch::asm!("int3")
+    };
+}
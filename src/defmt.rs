@@ -0,0 +1,66 @@
+//! Global logger for [defmt] that sends its frames through RIOT's stdio
+//!
+//! With this module enabled, any `defmt::info!()` etc. in the application or its dependencies
+//! produces (rzCOBS encoded, zero delimited) defmt frames on stdio. Which transport that is is
+//! decided by the stdio module selected in RIOT: With `stdio_rtt`, the frames go out over SEGGER
+//! RTT and can be read with `probe-rs` or `defmt-print`; with `stdio_uart`, `defmt-print` can be
+//! used on the serial port.
+//!
+//! As with any defmt logger, the application needs to link with `-Tdefmt.x`. Regular text on
+//! stdio would corrupt the frames, so applications using this should not print otherwise.
+//!
+//! Frames are written with interrupts disabled, so that log messages from different threads or
+//! interrupts do not get interleaved.
+
+use core::ptr::addr_of_mut;
+
+#[defmt::global_logger]
+struct Logger;
+
+/// Set while a frame is being written; defmt requires that acquire is not called reentrantly.
+static mut TAKEN: bool = false;
+/// Interrupt state to restore once the frame is complete
+static mut IRQ_STATE: riot_sys::libc::c_uint = 0;
+static mut ENCODER: defmt::Encoder = defmt::Encoder::new();
+
+fn write_stdio(bytes: &[u8]) {
+    // Nothing sensible can be done on errors of the transport
+    // unsafe: C API as documented
+    unsafe { riot_sys::stdio_write(bytes.as_ptr() as _, bytes.len() as _) };
+}
+
+// unsafe: Acquire and release are guarded by disabled interrupts, and reentrant use is caught
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {
+        // unsafe: C API makes no requirements
+        let irq_state = unsafe { riot_sys::irq_disable() };
+        // unsafe: With interrupts disabled, nothing else accesses the statics
+        unsafe {
+            if TAKEN {
+                riot_sys::irq_restore(irq_state);
+                panic!("defmt logger taken reentrantly");
+            }
+            TAKEN = true;
+            IRQ_STATE = irq_state;
+            (*addr_of_mut!(ENCODER)).start_frame(write_stdio);
+        }
+    }
+
+    unsafe fn flush() {
+        // stdio_write only returns once the data is handed to the transport
+    }
+
+    unsafe fn release() {
+        // unsafe: Acquire was called before, so interrupts are still disabled
+        unsafe {
+            (*addr_of_mut!(ENCODER)).end_frame(write_stdio);
+            TAKEN = false;
+            riot_sys::irq_restore(IRQ_STATE);
+        }
+    }
+
+    unsafe fn write(bytes: &[u8]) {
+        // unsafe: Acquire was called before, so interrupts are still disabled
+        unsafe { (*addr_of_mut!(ENCODER)).write(bytes, write_stdio) };
+    }
+}
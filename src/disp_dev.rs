@@ -0,0 +1,225 @@
+//! Access to displays through [RIOT's generic display device
+//! API](https://doc.riot-os.org/group__drivers__disp__dev.html)
+//!
+//! Displays registered with `disp_dev` (eg. ILI9341 or ST7735 based screens) are found by their
+//! screen ID. They implement [embedded_graphics]' `DrawTarget` for RGB565 colors, so any
+//! embedded-graphics based UI can render on them:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::disp_dev::DisplayDevice;
+//! use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+//!
+//! # fn f() {
+//! let mut display = DisplayDevice::by_screen_id(0).unwrap();
+//! display.clear(Rgb565::BLACK);
+//! # }
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    drawable::Pixel,
+    geometry::{Point, Size},
+    pixelcolor::{raw::RawU16, Rgb565},
+    DrawTarget,
+};
+use riot_sys::{disp_dev_area_t, disp_dev_reg_t, disp_dev_t};
+
+/// Number of pixels sent to the display at once when filling areas
+const CHUNK: usize = 64;
+
+/// A rectangular region of the display, with inclusive corner coordinates
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[doc(alias = "disp_dev_area_t")]
+pub struct Area {
+    pub x1: u16,
+    pub y1: u16,
+    pub x2: u16,
+    pub y2: u16,
+}
+
+impl Area {
+    /// Number of pixels in the area
+    pub fn pixels(&self) -> usize {
+        (self.x2 - self.x1 + 1) as usize * (self.y2 - self.y1 + 1) as usize
+    }
+
+    fn to_c(self) -> disp_dev_area_t {
+        disp_dev_area_t {
+            x1: self.x1,
+            x2: self.x2,
+            y1: self.y1,
+            y2: self.y2,
+        }
+    }
+}
+
+/// A display registered with the display device registry
+pub struct DisplayDevice {
+    dev: *mut disp_dev_t,
+}
+
+impl DisplayDevice {
+    /// Find a display by its screen ID
+    #[doc(alias = "disp_dev_reg_find_screen")]
+    pub fn by_screen_id(screen_id: u8) -> Option<Self> {
+        // unsafe: C API as documented
+        let reg: *mut disp_dev_reg_t = unsafe { riot_sys::disp_dev_reg_find_screen(screen_id) };
+        if reg.is_null() {
+            return None;
+        }
+        Some(Self {
+            // unsafe: Registry entries are static and valid
+            dev: unsafe { (*reg).dev },
+        })
+    }
+
+    /// Display width in pixels
+    #[doc(alias = "disp_dev_width")]
+    pub fn width(&self) -> u16 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::disp_dev_width(self.dev) }
+    }
+
+    /// Display height in pixels
+    #[doc(alias = "disp_dev_height")]
+    pub fn height(&self) -> u16 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::disp_dev_height(self.dev) }
+    }
+
+    /// Bits per pixel
+    #[doc(alias = "disp_dev_color_depth")]
+    pub fn color_depth(&self) -> u8 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::disp_dev_color_depth(self.dev) }
+    }
+
+    /// Invert (or restore) the display's colors
+    #[doc(alias = "disp_dev_set_invert")]
+    pub fn set_invert(&mut self, invert: bool) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::disp_dev_set_invert(self.dev, invert) }
+    }
+
+    /// Write pixel data (in RGB565, row by row) into an area of the display
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the number of colors does not match the area's size, or if the area is not
+    /// inside the display.
+    #[doc(alias = "disp_dev_map")]
+    pub fn map(&mut self, area: Area, colors: &[u16]) {
+        assert!(
+            area.x1 <= area.x2
+                && area.y1 <= area.y2
+                && area.x2 < self.width()
+                && area.y2 < self.height(),
+            "Area exceeds display"
+        );
+        assert_eq!(
+            colors.len(),
+            area.pixels(),
+            "Color data does not match area"
+        );
+        let area = area.to_c();
+        // unsafe: C API as documented; the checks above ensure the color data covers the area.
+        unsafe { riot_sys::disp_dev_map(self.dev, &area, colors.as_ptr()) }
+    }
+
+    /// Fill an area of the display with a single color
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the area is not inside the display.
+    pub fn fill(&mut self, area: Area, color: u16) {
+        let buffer = [color; CHUNK];
+        for y in area.y1..=area.y2 {
+            let mut x = area.x1;
+            while x <= area.x2 {
+                let count = core::cmp::min(CHUNK, (area.x2 - x + 1) as usize);
+                let x2 = x + count as u16 - 1;
+                self.map(
+                    Area {
+                        x1: x,
+                        y1: y,
+                        x2,
+                        y2: y,
+                    },
+                    &buffer[..count],
+                );
+                // Saturating for displays that are 65536 pixels wide
+                x = x2.saturating_add(1);
+                if x2 == u16::MAX {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Switch the backlight of the display on
+#[cfg(riot_module_disp_dev_backlight)]
+#[doc(alias = "disp_dev_backlight_on")]
+pub fn backlight_on() {
+    // unsafe: C API as documented
+    unsafe { riot_sys::inline::disp_dev_backlight_on() }
+}
+
+/// Switch the backlight of the display off
+#[cfg(riot_module_disp_dev_backlight)]
+#[doc(alias = "disp_dev_backlight_off")]
+pub fn backlight_off() {
+    // unsafe: C API as documented
+    unsafe { riot_sys::inline::disp_dev_backlight_off() }
+}
+
+fn raw(color: Rgb565) -> u16 {
+    RawU16::from(color).into_inner()
+}
+
+impl DrawTarget<Rgb565> for DisplayDevice {
+    type Error = Infallible;
+
+    fn draw_pixel(&mut self, pixel: Pixel<Rgb565>) -> Result<(), Infallible> {
+        let Pixel(Point { x, y }, color) = pixel;
+        // Pixels outside the display are ignored, as with other draw targets
+        if x < 0 || y < 0 || x >= self.width() as i32 || y >= self.height() as i32 {
+            return Ok(());
+        }
+        let (x, y) = (x as u16, y as u16);
+        self.map(
+            Area {
+                x1: x,
+                y1: y,
+                x2: x,
+                y2: y,
+            },
+            &[raw(color)],
+        );
+        Ok(())
+    }
+
+    fn size(&self) -> Size {
+        Size::new(self.width().into(), self.height().into())
+    }
+
+    fn clear(&mut self, color: Rgb565) -> Result<(), Infallible> {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        self.fill(
+            Area {
+                x1: 0,
+                y1: 0,
+                x2: width - 1,
+                y2: height - 1,
+            },
+            raw(color),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! An [embassy-executor](embassy_executor) that runs in a RIOT thread
+//!
+//! The [Executor] polls its tasks in the thread it was created in, and blocks the thread on a
+//! thread flag while all tasks are pending. Wakers (eg. of [ztimer](crate::ztimer) sleeps or of
+//! network sockets) set that flag, so tasks can be woken from interrupts and other threads alike.
+//!
+//! This requires the `core_thread_flags` module.
+
+use core::marker::PhantomData;
+use embassy_executor::{raw, Spawner};
+
+use crate::thread::KernelPID;
+
+/// Thread flag that wakes the executor's thread
+///
+/// Other users of thread flags in the executor's thread need to leave this flag alone.
+pub const THREAD_FLAG: riot_sys::thread_flags_t = 1 << 0;
+
+/// Executor that polls embassy tasks in a RIOT thread
+///
+/// ```
+/// # #![no_std]
+/// # #![no_main]
+/// # use riot_wrappers::embassy::Executor;
+/// # fn f() -> ! {
+/// static EXECUTOR: static_cell::StaticCell<Executor> = static_cell::StaticCell::new();
+/// let executor: &'static mut _ = EXECUTOR.init(Executor::new());
+/// executor.run(|spawner| {
+///     spawner.spawn(amain()).unwrap();
+/// })
+/// # }
+/// # #[embassy_executor::task]
+/// # async fn amain() {}
+/// ```
+pub struct Executor {
+    executor: raw::Executor,
+    pid: KernelPID,
+    // The executor needs to be run in the thread it was created in.
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Executor {
+    /// Create an executor for the current thread
+    pub fn new() -> Self {
+        let pid = crate::thread::get_pid();
+        let raw_pid: riot_sys::kernel_pid_t = pid.into();
+        Self {
+            executor: raw::Executor::new(raw_pid as usize as *mut ()),
+            pid,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Run the executor, after spawning initial tasks through the `init` function
+    ///
+    /// ## Panics
+    ///
+    /// This panics if called in a different thread than the executor was created in.
+    pub fn run(&'static mut self, init: impl FnOnce(Spawner)) -> ! {
+        assert!(
+            crate::thread::get_pid() == self.pid,
+            "Executor needs to run in the thread it was created in"
+        );
+        init(self.executor.spawner());
+        loop {
+            // unsafe: This is the only place poll is called, and it is not called reentrantly
+            // because the executor is only ever run in this one thread.
+            unsafe { self.executor.poll() };
+            // Wakes that happened while polling have set the flag already, so there is no race.
+            // unsafe: C API makes no requirements
+            unsafe { riot_sys::thread_flags_wait_any(THREAD_FLAG) };
+        }
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[export_name = "__pender"]
+fn __pender(context: *mut ()) {
+    let pid = context as usize as riot_sys::kernel_pid_t;
+    // unsafe: The PID was valid when the executor was created, and the executor does not return
+    // from its thread once it runs.
+    let thread = unsafe { riot_sys::thread_get_unchecked(pid) };
+    // unsafe: C API as documented; may be called from interrupts.
+    unsafe { riot_sys::thread_flags_set(crate::inline_cast_mut(thread), THREAD_FLAG) };
+}
@@ -112,6 +112,14 @@ impl core::fmt::Display for NumericError {
 
 impl core::error::Error for NumericError {}
 
+#[cfg(feature = "with_embedded_io")]
+impl embedded_io::Error for NumericError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        // FIXME there are some that do make sense here
+        embedded_io::ErrorKind::Other
+    }
+}
+
 impl<T> NegativeErrorExt for T
 where
     T: num_traits::Zero + core::cmp::PartialOrd + TryInto<isize>,
@@ -141,8 +149,10 @@ macro_rules! E {
 
 // See module level comment
 E!(EAGAIN);
+E!(EEXIST);
 E!(EINVAL);
 E!(ENODEV);
+E!(ENOENT);
 E!(ENOMEM);
 E!(ENOSPC);
 E!(ENOTSUP);
@@ -0,0 +1,200 @@
+//! A futures executor built on [RIOT's event queues](https://doc.riot-os.org/group__sys__event.html)
+//!
+//! Every [Task] carries an `event_t`; waking the task posts that event to the [Executor]'s queue,
+//! and handling the event polls the task. As the queue is a regular RIOT event queue, other events
+//! (eg. those of `sock_async` or of RIOT modules that take an event queue) can be posted to it as
+//! well, and are then handled in the same thread. That thread thus has a single blocking point,
+//! `event_loop()`.
+//!
+//! Wakers may be used from any thread or interrupt, as posting an event is.
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::event::Executor;
+//! static EXECUTOR: Executor = Executor::new();
+//!
+//! # fn f() -> ! {
+//! EXECUTOR.run(async {
+//!     // ... spawn further tasks, then do the main work ...
+//! })
+//! # }
+//! ```
+
+use core::cell::{Cell, UnsafeCell};
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::AtomicBool;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use riot_sys::{event_queue_t, event_t};
+
+/// Event queue that runs [tasks](Task)
+///
+/// The executor is placed in a static, and started in a thread using [`.run()`](Self::run).
+pub struct Executor {
+    queue: UnsafeCell<event_queue_t>,
+    running: AtomicBool,
+}
+
+// unsafe: The queue is only accessed through RIOT's event functions, which are thread and
+// interrupt safe.
+unsafe impl Sync for Executor {}
+
+impl Executor {
+    /// Create an executor whose queue is not yet bound to any thread
+    #[doc(alias = "EVENT_QUEUE_INIT_DETACHED")]
+    pub const fn new() -> Self {
+        Self {
+            // unsafe: All-zero is the detached state (no events, no waiter)
+            queue: UnsafeCell::new(unsafe { core::mem::zeroed() }),
+            running: AtomicBool::new(false),
+        }
+    }
+
+    /// Pointer to the underlying event queue
+    ///
+    /// This can be passed to RIOT APIs that post events to a queue, so that their events are
+    /// handled in the executor's thread.
+    pub fn queue(&'static self) -> *mut event_queue_t {
+        self.queue.get()
+    }
+
+    /// Make a task run on this executor
+    ///
+    /// The task is polled once the executor runs (or, if it already runs, once pending events have
+    /// been handled).
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the task was already spawned.
+    pub fn spawn<F: Future<Output = ()> + Send>(&'static self, task: &'static Task<F>) {
+        self.spawn_unchecked(task);
+    }
+
+    /// Spawn without requiring the future to be Send
+    ///
+    /// This is only used for the main task, which is created in the executor's thread.
+    fn spawn_unchecked<F: Future<Output = ()>>(&'static self, task: &'static Task<F>) {
+        let already_spawned = crate::interrupt::free(|_| {
+            let already_spawned = !task.header.queue.get().is_null();
+            if !already_spawned {
+                task.header.queue.set(self.queue());
+            }
+            already_spawned
+        });
+        assert!(!already_spawned, "Task was already spawned");
+        task.header.post();
+    }
+
+    /// Run the executor in the current thread, starting with the given main task
+    ///
+    /// This only ever returns when the thread is terminated.
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the executor is already running (in this or another thread).
+    #[doc(alias = "event_loop")]
+    pub fn run<F: Future<Output = ()>>(&'static self, main: F) -> ! {
+        assert!(
+            !crate::static_init::claim(&self.running),
+            "Executor is already running"
+        );
+        let main = Task::new(main);
+        // unsafe: This function never returns, so its stack frame (and thus `main`) lives on
+        // forever, and can be treated as static.
+        let main: &'static Task<F> = unsafe { &*(&main as *const _) };
+        self.spawn_unchecked(main);
+
+        let queue = crate::inline_cast_mut(self.queue());
+        // unsafe: C API as documented; the queue is only claimed once as the running flag is only
+        // ever set once.
+        unsafe { riot_sys::inline::event_queue_claim(queue) };
+        loop {
+            // unsafe: C API as documented
+            unsafe { riot_sys::inline::event_loop(queue) };
+        }
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Part of a task that is independent of its future, and thus accessible from the waker
+#[repr(C)]
+struct Header {
+    // Must be the first field: The event pointer is cast back to the task in the handler.
+    event: UnsafeCell<event_t>,
+    queue: Cell<*mut event_queue_t>,
+}
+
+impl Header {
+    fn post(&self) {
+        // unsafe: C API as documented; posting an event that is already queued is a no-op.
+        unsafe { riot_sys::event_post(self.queue.get(), self.event.get()) };
+    }
+
+    fn waker(&'static self) -> Waker {
+        // unsafe: The vtable functions uphold the RawWaker contract for a static header.
+        unsafe { Waker::from_raw(RawWaker::new(self as *const Self as *const (), &VTABLE)) }
+    }
+}
+
+/// A future that can be [spawned](Executor::spawn) on an [Executor]
+///
+/// Tasks are statically allocated, eg. using a `StaticCell`. Once the future completes, it is
+/// dropped, and the task stays inert.
+#[repr(C)]
+pub struct Task<F: Future<Output = ()>> {
+    header: Header,
+    future: UnsafeCell<Option<F>>,
+}
+
+// unsafe: The future is only accessed in the executor's thread (which it is sent to, hence the Send
+// bound); other threads and interrupts only post the event, and only set the queue in a critical
+// section.
+unsafe impl<F: Future<Output = ()> + Send> Sync for Task<F> {}
+
+impl<F: Future<Output = ()>> Task<F> {
+    pub fn new(future: F) -> Self {
+        // unsafe: All-zero is a valid unqueued event
+        let mut event: event_t = unsafe { core::mem::zeroed() };
+        event.handler = Some(Self::handle);
+        Self {
+            header: Header {
+                event: UnsafeCell::new(event),
+                queue: Cell::new(core::ptr::null_mut()),
+            },
+            future: UnsafeCell::new(Some(future)),
+        }
+    }
+
+    unsafe extern "C" fn handle(event: *mut event_t) {
+        // unsafe: The event is the first field of the header, which is the first field of the
+        // task, and the task is static.
+        let task: &'static Self = unsafe { &*(event as *const Self) };
+        // unsafe: Events are handled only in the executor's thread, and not reentrantly.
+        let future = unsafe { &mut *task.future.get() };
+        let Some(running) = future.as_mut() else {
+            // Woken after completion
+            return;
+        };
+        // unsafe: The future is in a static and not moved out until it is dropped in place.
+        let running = unsafe { Pin::new_unchecked(running) };
+        let waker = task.header.waker();
+        if let Poll::Ready(()) = running.poll(&mut Context::from_waker(&waker)) {
+            *future = None;
+        }
+    }
+}
+
+// unsafe (in the closures): The data pointers were created from a static Header in Header::waker.
+static VTABLE: RawWakerVTable = RawWakerVTable::new(
+    |data| RawWaker::new(data, &VTABLE),
+    |data| unsafe { (*(data as *const Header)).post() },
+    |data| unsafe { (*(data as *const Header)).post() },
+    |_| {},
+);
@@ -0,0 +1,177 @@
+//! Number formatting and printing through RIOT's [fmt] module
+//!
+//! These functions are a lightweight alternative to [core::fmt] for applications that are tight on
+//! flash memory: they format numbers into caller provided buffers, or print them directly to stdio
+//! without going through the C library's stdio.
+//!
+//! Formatting functions write into the start of the given buffer, and return the written part as
+//! a string. If the buffer is too short, nothing is written.
+//!
+//! [fmt]: https://doc.riot-os.org/group__sys__fmt.html
+
+use riot_sys::libc::c_char;
+
+/// Error returned when the buffer passed to a formatting function is too short
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BufferTooSmall;
+
+/// Run a formatting function that (like all of fmt's) returns the required length when passed a
+/// null pointer
+fn format_with(
+    buf: &mut [u8],
+    f: impl Fn(*mut c_char) -> riot_sys::size_t,
+) -> Result<&str, BufferTooSmall> {
+    let len = f(core::ptr::null_mut()) as usize;
+    if len > buf.len() {
+        return Err(BufferTooSmall);
+    }
+    f(buf.as_mut_ptr() as _);
+    // unsafe: The fmt functions only produce ASCII
+    Ok(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+}
+
+/// Format a number in decimal
+#[doc(alias = "fmt_u32_dec")]
+pub fn u32_dec(buf: &mut [u8], val: u32) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe { riot_sys::fmt_u32_dec(out, val) })
+}
+
+/// Format a number in decimal
+#[doc(alias = "fmt_s32_dec")]
+pub fn s32_dec(buf: &mut [u8], val: i32) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe { riot_sys::fmt_s32_dec(out, val) })
+}
+
+/// Format a number in decimal
+#[doc(alias = "fmt_u64_dec")]
+pub fn u64_dec(buf: &mut [u8], val: u64) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe { riot_sys::fmt_u64_dec(out, val) })
+}
+
+/// Format a number in decimal
+#[doc(alias = "fmt_s64_dec")]
+pub fn s64_dec(buf: &mut [u8], val: i64) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe { riot_sys::fmt_s64_dec(out, val) })
+}
+
+/// Format a number as 8 upper case hex digits
+#[doc(alias = "fmt_u32_hex")]
+pub fn u32_hex(buf: &mut [u8], val: u32) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe { riot_sys::fmt_u32_hex(out, val) })
+}
+
+/// Format a number as 16 upper case hex digits
+#[doc(alias = "fmt_u64_hex")]
+pub fn u64_hex(buf: &mut [u8], val: u64) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe { riot_sys::fmt_u64_hex(out, val) })
+}
+
+/// Format bytes as upper case hex digits (two per byte)
+#[doc(alias = "fmt_bytes_hex")]
+pub fn bytes_hex<'b>(buf: &'b mut [u8], data: &[u8]) -> Result<&'b str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe {
+        riot_sys::fmt_bytes_hex(out, data.as_ptr(), data.len() as _)
+    })
+}
+
+/// Format a fixed point number, given as an integer and the number of digits after the decimal
+/// point
+///
+/// For example, `s32_dfp(buf, -1234, 2)` produces "-12.34".
+#[doc(alias = "fmt_s32_dfp")]
+pub fn s32_dfp(buf: &mut [u8], val: i32, fp_digits: i32) -> Result<&str, BufferTooSmall> {
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe {
+        riot_sys::fmt_s32_dfp(out, val, fp_digits as _)
+    })
+}
+
+/// Format a floating point number with a given number of digits after the decimal point
+///
+/// The precision must be at most 7, and the number's magnitude must be below 2^32.
+#[doc(alias = "fmt_float")]
+pub fn float(buf: &mut [u8], val: f32, precision: u8) -> Result<&str, BufferTooSmall> {
+    assert!(
+        precision <= 7,
+        "fmt_float supports at most 7 digits precision"
+    );
+    // unsafe: C API as documented, buffer length is checked by format_with
+    format_with(buf, |out| unsafe {
+        riot_sys::fmt_float(out, val, precision as _)
+    })
+}
+
+/// Print a number in decimal to stdio
+#[doc(alias = "print_u32_dec")]
+pub fn print_u32_dec(val: u32) {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::print_u32_dec(val) }
+}
+
+/// Print a number in decimal to stdio
+#[doc(alias = "print_s32_dec")]
+pub fn print_s32_dec(val: i32) {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::print_s32_dec(val) }
+}
+
+/// Print a number in decimal to stdio
+#[doc(alias = "print_u64_dec")]
+pub fn print_u64_dec(val: u64) {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::print_u64_dec(val) }
+}
+
+/// Print a number as 8 upper case hex digits to stdio
+#[doc(alias = "print_u32_hex")]
+pub fn print_u32_hex(val: u32) {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::print_u32_hex(val) }
+}
+
+/// Print a number as 16 upper case hex digits to stdio
+#[doc(alias = "print_u64_hex")]
+pub fn print_u64_hex(val: u64) {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::print_u64_hex(val) }
+}
+
+/// Print a floating point number with a given number of digits after the decimal point to stdio
+///
+/// The same limitations as in [`float()`] apply.
+#[doc(alias = "print_float")]
+pub fn print_float(val: f32, precision: u8) {
+    assert!(
+        precision <= 7,
+        "fmt_float supports at most 7 digits precision"
+    );
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::print_float(val, precision as _) }
+}
+
+/// Print a string to stdio
+#[doc(alias = "print")]
+pub fn print_str(s: &str) {
+    // unsafe: C API as documented
+    unsafe { riot_sys::print(s.as_ptr() as _, s.len() as _) }
+}
+
+/// Writer that prints to stdio through fmt's [`print_str()`]
+///
+/// This is an alternative to [crate::stdio::Stdio] that does not depend on the stdio module's
+/// write function being available. Errors while printing are not reported.
+pub struct Print;
+
+impl core::fmt::Write for Print {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        print_str(s);
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! Access to [RIOT's frequency meter peripherals](https://doc.riot-os.org/group__drivers__periph__freqm.html)
+//!
+//! A frequency meter counts the edges of an external (or internal) signal over a gate period, and
+//! reports the signal's frequency.
+
+use riot_sys::freqm_t;
+use riot_sys::libc::c_void;
+
+/// Error indicating that the counter overflowed during the measurement, so the measured signal is
+/// too fast for the chosen gate period
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Overflow;
+
+/// An initialized frequency meter
+pub struct FrequencyMeter(freqm_t);
+
+impl FrequencyMeter {
+    /// Initialize the frequency meter with the given index
+    ///
+    /// This is declared as unsafe as it may only be called once per device, and the index needs to
+    /// be below `FREQM_NUMOF`; see [`ADCLine::init`](crate::adc::ADCLine::init) for the general
+    /// considerations.
+    #[doc(alias = "freqm_init")]
+    pub unsafe fn init(index: freqm_t) -> Self {
+        riot_sys::freqm_init(index);
+        Self(index)
+    }
+
+    /// Measure the frequency (in Hz) over a gate period of `period_us` microseconds, blocking until
+    /// the measurement is complete
+    #[doc(alias = "freqm_frequency_get")]
+    pub fn measure(&mut self, period_us: u32) -> Result<u32, Overflow> {
+        let mut result = 0;
+        // unsafe: C API as documented
+        let overflow = unsafe { riot_sys::freqm_frequency_get(self.0, &mut result, period_us) };
+        match overflow {
+            false => Ok(result),
+            true => Err(Overflow),
+        }
+    }
+
+    /// Start measuring the frequency (in Hz) over a gate period of `period_us` microseconds, and
+    /// return immediately
+    ///
+    /// The callback is called in an interrupt once the measurement is complete. It needs to be
+    /// static as a running measurement can not be aborted.
+    #[doc(alias = "freqm_frequency_get_async")]
+    pub fn measure_with_static_cb<F>(&mut self, period_us: u32, callback: &'static mut F)
+    where
+        F: FnMut(Result<u32, Overflow>) + Send + 'static,
+    {
+        unsafe extern "C" fn run<F: FnMut(Result<u32, Overflow>)>(
+            result: u32,
+            overflow: bool,
+            context: *mut c_void,
+        ) {
+            let callback = &mut *(context as *mut F);
+            callback(match overflow {
+                false => Ok(result),
+                true => Err(Overflow),
+            });
+        }
+
+        // unsafe: C API as documented; the callback is static.
+        unsafe {
+            riot_sys::freqm_frequency_get_async(
+                self.0,
+                Some(run::<F>),
+                callback as *mut F as *mut c_void,
+                period_us,
+            )
+        };
+    }
+}
@@ -453,6 +453,12 @@ impl<'b> PacketBuffer<'b> {
         .map(|_| ())
     }
 
+    /// Pointer to the underlying packet, for use by other wrappers that process it further
+    #[cfg(any(riot_module_liboscore, riot_module_nanocoap_cache))]
+    pub(crate) fn pkt_ptr(&mut self) -> *mut coap_pkt_t {
+        self.pkt
+    }
+
     pub fn opt_iter<'a>(&'a self) -> PacketBufferOptIter<'a, 'b> {
         PacketBufferOptIter {
             buffer: self,
@@ -570,3 +576,53 @@ impl<'a, 'b> Iterator for PacketBufferOptIterMut<'a, 'b> {
         }
     }
 }
+
+/// The UDP port on which gcoap listens for unsecured requests
+///
+/// gcoap offers no way to select the port at runtime; it is set at build time through
+/// `CONFIG_GCOAP_PORT` (eg. `CFLAGS += -DCONFIG_GCOAP_PORT=5685`).
+#[doc(alias = "CONFIG_GCOAP_PORT")]
+pub const PORT: u16 = riot_sys::CONFIG_GCOAP_PORT as _;
+
+/// The UDP port on which gcoap listens for DTLS secured requests
+///
+/// Like [PORT], this is set at build time only, through `CONFIG_GCOAPS_PORT`.
+#[cfg(riot_module_gcoap_dtls)]
+#[doc(alias = "CONFIG_GCOAPS_PORT")]
+pub const DTLS_PORT: u16 = riot_sys::CONFIG_GCOAPS_PORT as _;
+
+/// Make gcoap's DTLS socket use the [credman](crate::credman) credentials with the given tag
+///
+/// This is used both for the server side and for requests sent through gcoap.
+#[cfg(riot_module_gcoap_dtls)]
+#[doc(alias = "sock_dtls_add_credential")]
+pub fn dtls_add_credential(tag: crate::credman::Tag) -> Result<(), crate::error::NumericError> {
+    // unsafe: C API as documented; gcoap's socket is initialized along with gcoap.
+    unsafe { riot_sys::sock_dtls_add_credential(riot_sys::gcoap_get_sock_dtls(), tag) }
+        .negative_to_error()?;
+    Ok(())
+}
+
+/// Stop using the credentials with the given tag on gcoap's DTLS socket
+#[cfg(riot_module_gcoap_dtls)]
+#[doc(alias = "sock_dtls_remove_credential")]
+pub fn dtls_remove_credential(tag: crate::credman::Tag) {
+    // unsafe: C API as documented; gcoap's socket is initialized along with gcoap.
+    unsafe { riot_sys::sock_dtls_remove_credential(riot_sys::gcoap_get_sock_dtls(), tag) };
+}
+
+/// Number of DTLS sessions that can still be established
+#[cfg(all(riot_module_gcoap_dtls, riot_module_dsm))]
+#[doc(alias = "dsm_get_num_available_slots")]
+pub fn dtls_sessions_available() -> usize {
+    // unsafe: C API as documented
+    unsafe { riot_sys::dsm_get_num_available_slots() as _ }
+}
+
+/// Maximum number of simultaneous DTLS sessions
+#[cfg(all(riot_module_gcoap_dtls, riot_module_dsm))]
+#[doc(alias = "dsm_get_num_maximum_slots")]
+pub fn dtls_sessions_max() -> usize {
+    // unsafe: C API as documented
+    unsafe { riot_sys::dsm_get_num_maximum_slots() as _ }
+}
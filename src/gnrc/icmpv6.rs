@@ -45,3 +45,114 @@ impl<'a> Pktsnip<Writable> {
         }
     }
 }
+
+/// Send an ICMPv6 echo request ("ping") to `addr`
+///
+/// The request carries a `payload_len` bytes long payload that starts with a timestamp (if at
+/// least 4 bytes long), which allows calculating the round trip time when the reply is processed
+/// through [`Pktsnip::icmpv6_echo_reply()`]. Replies are delivered through GNRC's netreg for
+/// [`EchoType::Reply`], see [FullDemuxContext::new_icmpv6_echo].
+///
+/// If no `netif` is given, the interface is selected by the IPv6 layer (which fails for
+/// link-local addresses if there are multiple interfaces). A `hop_limit` of 0 selects the
+/// interface's default hop limit.
+///
+/// [FullDemuxContext::new_icmpv6_echo]: crate::gnrc::netreg::FullDemuxContext::new_icmpv6_echo
+#[cfg(riot_module_gnrc_icmpv6_echo)]
+#[doc(alias = "gnrc_icmpv6_echo_send")]
+pub fn echo_send(
+    netif: Option<&super::Netif>,
+    addr: &super::ipv6::Address,
+    id: u16,
+    seq: u16,
+    hop_limit: u8,
+    payload_len: usize,
+) -> Result<(), crate::error::NumericError> {
+    use crate::error::NegativeErrorExt;
+
+    // unsafe: C API as documented; the netif pointer is either NULL or a registered interface
+    unsafe {
+        riot_sys::gnrc_icmpv6_echo_send(
+            netif.map(|n| n.0).unwrap_or(core::ptr::null()),
+            addr.as_ptr(),
+            id,
+            seq,
+            hop_limit,
+            payload_len as _,
+        )
+    }
+    .negative_to_error()?;
+    Ok(())
+}
+
+/// Information extracted from a received ICMPv6 echo reply through
+/// [`Pktsnip::icmpv6_echo_reply()`]
+#[cfg(riot_module_gnrc_icmpv6_echo)]
+#[derive(Debug, Copy, Clone)]
+pub struct EchoReply {
+    /// Address of the responding host
+    pub source: super::ipv6::Address,
+    /// Identifier of the request this is a reply to
+    pub id: u16,
+    /// Sequence number of the request this is a reply to
+    pub seq: u16,
+    /// Hop limit of the received reply
+    pub hop_limit: u8,
+    /// Round trip time in microseconds, as derived from the timestamp in the payload
+    pub rtt_us: u32,
+    /// True if the payload did not contain the pattern it was sent with
+    pub corrupt: bool,
+}
+
+#[cfg(riot_module_gnrc_icmpv6_echo)]
+impl<M: crate::gnrc_pktbuf::Mode> Pktsnip<M> {
+    /// Evaluate a received ICMPv6 echo reply
+    ///
+    /// This expects a packet as received through a netreg registration for [`EchoType::Reply`]
+    /// (ie. with an ICMPv6 snip first and an IPv6 header snip later), and `payload_len` being the
+    /// length requested in [`echo_send()`].
+    ///
+    /// Returns None if the packet is not a well-formed echo reply.
+    #[doc(alias = "gnrc_icmpv6_echo_rsp_handle")]
+    pub fn icmpv6_echo_reply(&self, payload_len: usize) -> Option<EchoReply> {
+        unsafe extern "C" fn cb(
+            _pkt: *mut riot_sys::gnrc_pktsnip_t,
+            corrupt: core::ffi::c_int,
+            rtt_us: u32,
+            ctx: *mut riot_sys::libc::c_void,
+        ) -> core::ffi::c_int {
+            // unsafe: Cast from the same type below, and only used while the function runs
+            let ctx = unsafe { &mut *(ctx as *mut Option<(bool, u32)>) };
+            *ctx = Some((corrupt != 0, rtt_us));
+            0
+        }
+
+        let header = self.ipv6_get_header()?;
+        let icmpv6 = self.data();
+        // type, code, checksum, identifier, sequence number
+        if icmpv6.len() < 8 || icmpv6[0] != riot_sys::ICMPV6_ECHO_REP as u8 {
+            return None;
+        }
+
+        let mut result: Option<(bool, u32)> = None;
+        // unsafe: C API as documented; the function does not take ownership of the packet
+        unsafe {
+            riot_sys::gnrc_icmpv6_echo_rsp_handle(
+                self.ptr,
+                payload_len as _,
+                Some(cb),
+                &mut result as *mut _ as *mut _,
+            )
+        };
+        let (corrupt, rtt_us) = result?;
+
+        Some(EchoReply {
+            source: *header.src(),
+            id: u16::from_be_bytes([icmpv6[4], icmpv6[5]]),
+            seq: u16::from_be_bytes([icmpv6[6], icmpv6[7]]),
+            hop_limit: header.hop_limit(),
+            rtt_us,
+            corrupt,
+        })
+    }
+}
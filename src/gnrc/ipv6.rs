@@ -26,10 +26,62 @@ impl super::Netif {
         addrs.len = (result.negative_to_error()? as usize) / core::mem::size_of::<Address>();
         Ok(addrs)
     }
+
+    /// Multicast groups the interface has joined
+    #[doc(alias = "gnrc_netif_ipv6_groups_get")]
+    pub fn ipv6_groups(
+        &self,
+    ) -> Result<AddrList<{ riot_sys::GNRC_NETIF_IPV6_GROUPS_NUMOF as _ }>, NumericError> {
+        let mut groups = AddrList {
+            // unsafe: as per "Initializing an array element-by-element" documentation
+            addresses: unsafe { MaybeUninit::uninit().assume_init() },
+            len: 0,
+        };
+        let result = unsafe {
+            riot_sys::gnrc_netif_ipv6_groups_get(
+                crate::inline_cast(self.0),
+                groups.addresses.as_mut() as *mut _ as _, /* justified by array guarantees and repr(Transparent) */
+                core::mem::size_of_val(&groups.addresses) as _,
+            )
+        };
+        groups.len = (result.negative_to_error()? as usize) / core::mem::size_of::<Address>();
+        Ok(groups)
+    }
+
+    /// Join a multicast group on the interface
+    ///
+    /// Joining a group that was already joined is not an error.
+    #[doc(alias = "gnrc_netif_ipv6_group_join")]
+    pub fn ipv6_group_join(&self, group: &Address) -> Result<(), NumericError> {
+        // unsafe: C API as documented; the group is only read despite the mutable pointer
+        unsafe {
+            riot_sys::gnrc_netif_ipv6_group_join(
+                crate::inline_cast(self.0),
+                crate::inline_cast_mut(group.as_ptr() as *mut ipv6_addr_t),
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Leave a multicast group on the interface
+    #[doc(alias = "gnrc_netif_ipv6_group_leave")]
+    pub fn ipv6_group_leave(&self, group: &Address) -> Result<(), NumericError> {
+        // unsafe: C API as documented; the group is only read despite the mutable pointer
+        unsafe {
+            riot_sys::gnrc_netif_ipv6_group_leave(
+                crate::inline_cast(self.0),
+                crate::inline_cast_mut(group.as_ptr() as *mut ipv6_addr_t),
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
 }
 
-/// Helper for [super::Netif::ipv6_addrs]: As the [riot_sys::gnrc_netif_ipv6_addrs_get] function requires
-/// a multiple-address buffer to write in, this carries a suitable buffer.
+/// Helper for [super::Netif::ipv6_addrs] and [super::Netif::ipv6_groups]: As the
+/// [riot_sys::gnrc_netif_ipv6_addrs_get] function requires a multiple-address buffer to write in,
+/// this carries a suitable buffer.
 pub struct AddrList<const MAX: usize> {
     addresses: [MaybeUninit<Address>; MAX],
     len: usize,
@@ -3,6 +3,8 @@ pub mod icmpv6;
 #[cfg(riot_module_ipv6)]
 pub mod ipv6;
 pub mod netif;
+#[cfg(all(riot_module_gnrc_netif_bus, riot_module_gnrc_ipv6, riot_module_ipv6))]
+pub mod netif_bus;
 
 pub mod netapi;
 pub mod netreg;
@@ -110,4 +112,23 @@ impl Netif {
     pub fn l2addr(&self) -> &[u8] {
         unsafe { &(*self.0).l2addr[..(*self.0).l2addr_len as usize] }
     }
+
+    /// Whether the interface's link is up
+    ///
+    /// This is None if the interface does not report its link state.
+    #[doc(alias = "NETOPT_LINK")]
+    pub fn is_link_up(&self) -> Option<bool> {
+        let mut state: riot_sys::netopt_enable_t = 0;
+        // unsafe: C API as documented
+        let result = unsafe {
+            riot_sys::gnrc_netapi_get(
+                (*self.0).pid,
+                riot_sys::netopt_t_NETOPT_LINK,
+                0,
+                &mut state as *mut _ as *mut _,
+                core::mem::size_of_val(&state) as _,
+            )
+        };
+        (result >= 0).then(|| state == riot_sys::netopt_enable_t_NETOPT_ENABLE)
+    }
 }
@@ -0,0 +1,205 @@
+//! Notifications about changes on a network interface through [GNRC's netif message
+//! bus](https://doc.riot-os.org/group__net__gnrc__netif__bus.html)
+//!
+//! This allows applications to start network activity once the interface is configured, rather
+//! than to poll its addresses or to sleep and retry:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::gnrc::{Netif, netif_bus::Event};
+//! # fn f(netif: Netif) {
+//! netif.with_ipv6_events(|events| loop {
+//!     match events.receive() {
+//!         Ok(Event::AddressValid(addr)) if !addr.is_link_local() => break,
+//!         Ok(_) => continue,
+//!         Err(_other_message) => {
+//!             // ... process messages the thread receives for other purposes ...
+//!         }
+//!     }
+//! });
+//! # }
+//! ```
+//!
+//! ## Link state
+//!
+//! The GNRC netif bus only carries IPv6 events; RIOT does not publish link up or down events on
+//! it. The [Event::LinkUp] and [Event::LinkDown] events are thus produced by polling
+//! [`Netif::is_link_up()`] in [`Subscription::receive()`]: every [LINK_POLL_INTERVAL] if the
+//! `ztimer_msec` module is available, and otherwise only whenever a message arrives. Interfaces
+//! that do not report their link state produce no such events.
+
+use core::cell::Cell;
+
+use riot_sys::{msg_bus_entry_t, msg_bus_t};
+
+use super::ipv6::Address;
+use super::Netif;
+use crate::msg::{OpaqueMsg, WrapsMsgT};
+
+/// Interval in which [`Subscription::receive()`] checks the link state while waiting for messages
+#[cfg(riot_module_ztimer_msec)]
+pub const LINK_POLL_INTERVAL: crate::ztimer::Ticks<1000> = crate::ztimer::Ticks(500);
+
+/// An event received from a netif message bus, or a change of the link state
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Event {
+    /// An address on the interface has become valid (ie. its duplicate address detection has
+    /// completed)
+    #[doc(alias = "GNRC_IPV6_EVENT_ADDR_VALID")]
+    AddressValid(Address),
+    /// The interface's link came up
+    ///
+    /// This is detected by polling, see the [module level documentation](self#link-state).
+    #[doc(alias = "NETDEV_EVENT_LINK_UP")]
+    LinkUp,
+    /// The interface's link went down
+    ///
+    /// This is detected by polling, see the [module level documentation](self#link-state).
+    #[doc(alias = "NETDEV_EVENT_LINK_DOWN")]
+    LinkDown,
+    /// An event on the bus that is not known to this wrapper, indicated by its type number
+    Other(u16),
+}
+
+/// A thread's subscription to a netif's message bus
+///
+/// This is only available inside [`Netif::with_ipv6_events()`].
+pub struct Subscription<'a> {
+    bus: *mut msg_bus_t,
+    netif: &'a Netif,
+    // Link state as last reported
+    link_up: Cell<Option<bool>>,
+}
+
+impl Netif {
+    /// Subscribe the current thread to the interface's IPv6 events while `f` runs
+    ///
+    /// The events arrive as messages in the thread's message queue; they can be received through
+    /// the [Subscription].
+    ///
+    /// Messages for events that were not received before `f` returns stay in the message queue.
+    #[doc(alias = "gnrc_netif_get_bus")]
+    #[doc(alias = "msg_bus_attach")]
+    pub fn with_ipv6_events<R>(&self, f: impl FnOnce(&Subscription<'_>) -> R) -> R {
+        // unsafe: C API as documented; the netif is registered and thus has a bus.
+        let bus = unsafe {
+            riot_sys::gnrc_netif_get_bus(
+                self.0 as *mut _,
+                riot_sys::gnrc_netif_bus_t_GNRC_NETIF_BUS_IPV6,
+            )
+        };
+
+        // The entry needs to stay in place while attached, which the stack frame of this function
+        // ensures.
+        let mut entry = msg_bus_entry_t::default();
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::msg_bus_attach(bus, &mut entry);
+            riot_sys::inline::msg_bus_subscribe(
+                crate::inline_cast_mut(&mut entry as *mut _),
+                riot_sys::gnrc_ipv6_event_t_GNRC_IPV6_EVENT_ADDR_VALID as _,
+            );
+        }
+
+        let result = f(&Subscription {
+            bus,
+            netif: self,
+            link_up: Cell::new(self.is_link_up()),
+        });
+
+        // unsafe: C API as documented; the entry was attached above.
+        unsafe { riot_sys::msg_bus_detach(bus, &mut entry) };
+
+        result
+    }
+
+    /// Wait until the interface has a valid global IPv6 address, or until the timeout has
+    /// expired
+    ///
+    /// Returns true if a global address is available.
+    #[cfg(riot_module_ztimer)]
+    #[doc(alias = "gnrc_netif_ipv6_wait_for_global_address")]
+    pub fn wait_for_global_address(&self, timeout: impl Into<crate::ztimer::Ticks<1000>>) -> bool {
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::gnrc_netif_ipv6_wait_for_global_address(self.0 as *mut _, timeout.into().0)
+        }
+    }
+}
+
+/// Wait until any interface has a valid global IPv6 address, or until the timeout has expired
+///
+/// Returns true if a global address is available.
+#[cfg(riot_module_ztimer)]
+#[doc(alias = "gnrc_netif_ipv6_wait_for_global_address")]
+pub fn wait_for_global_address(timeout: impl Into<crate::ztimer::Ticks<1000>>) -> bool {
+    // unsafe: C API as documented; NULL stands for any interface.
+    unsafe {
+        riot_sys::gnrc_netif_ipv6_wait_for_global_address(core::ptr::null_mut(), timeout.into().0)
+    }
+}
+
+impl Subscription<'_> {
+    /// Interpret a message received by the thread, or return None if it did not come from this
+    /// subscription's bus
+    #[doc(alias = "msg_is_from_bus")]
+    pub fn decode(&self, msg: &OpaqueMsg) -> Option<Event> {
+        let msg = msg.view();
+        // unsafe: C API as documented
+        if !unsafe {
+            riot_sys::inline::msg_is_from_bus(
+                crate::inline_cast(self.bus as *const _),
+                crate::inline_cast(msg as *const _),
+            )
+        } {
+            return None;
+        }
+        // unsafe: C API as documented
+        let type_ =
+            unsafe { riot_sys::inline::msg_bus_get_type(crate::inline_cast(msg as *const _)) };
+        Some(match type_ as riot_sys::gnrc_ipv6_event_t {
+            riot_sys::gnrc_ipv6_event_t_GNRC_IPV6_EVENT_ADDR_VALID => {
+                // unsafe: The bus's documentation says the content points to the address, which
+                // is copied out immediately.
+                Event::AddressValid(Address::clone_from_ptr(unsafe { msg.content.ptr } as _))
+            }
+            _ => Event::Other(type_ as _),
+        })
+    }
+
+    /// Report a change of the link state since the last call, if there was any
+    fn link_change(&self) -> Option<Event> {
+        let current = self.netif.is_link_up()?;
+        if self.link_up.replace(Some(current)) == Some(current) {
+            return None;
+        }
+        Some(match current {
+            true => Event::LinkUp,
+            false => Event::LinkDown,
+        })
+    }
+
+    /// Block until an event arrives or the link state changes, or until the thread receives any
+    /// other message
+    ///
+    /// Messages that did not come from this subscription's bus are returned as errors, so that
+    /// threads can process them as they would outside of the subscription.
+    pub fn receive(&self) -> Result<Event, OpaqueMsg> {
+        loop {
+            if let Some(event) = self.link_change() {
+                return Ok(event);
+            }
+            #[cfg(riot_module_ztimer_msec)]
+            let Some(msg) =
+                OpaqueMsg::receive_timeout(crate::ztimer::Clock::msec(), LINK_POLL_INTERVAL)
+            else {
+                continue;
+            };
+            #[cfg(not(riot_module_ztimer_msec))]
+            let msg = OpaqueMsg::receive();
+            return self.decode(&msg).ok_or(msg);
+        }
+    }
+}
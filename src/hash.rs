@@ -0,0 +1,292 @@
+//! Cryptographic hash functions from RIOT's [hashes] module
+//!
+//! Each hash function is exposed as a type with `new()` / `update()` / `finalize()` methods. The
+//! types also implement the traits of the [digest] crate (and thus `digest::Digest`), so they can be
+//! used with crates from the RustCrypto ecosystem.
+//!
+//! Depending on the board, RIOT may provide these through a hardware accelerator (eg. with the
+//! `periph_hash_sha256` module); that is transparent to users of this module.
+//!
+//! On top of SHA-256, [HmacSha256] provides message authentication, and the
+//! [`hkdf_sha256_extract()`] / [`hkdf_sha256_expand()`] functions provide key derivation.
+//!
+//! MD5 and SHA-1 are provided for compatibility with existing protocols; they should not be used
+//! where collision resistance matters.
+//!
+//! Simple hashes for non-cryptographic purposes are available in [non_crypto].
+//!
+//! [hashes]: https://doc.riot-os.org/group__sys__hashes.html
+
+use digest::generic_array::GenericArray;
+
+pub mod non_crypto;
+
+macro_rules! hash {
+    (
+        $(#[$meta:meta])*
+        $name:ident,
+        $ctx:ty,
+        $len:expr,
+        $outsize:ty,
+        $init:path,
+        $update:path,
+        |$ctx_arg:ident, $out_arg:ident| $final:expr,
+    ) => {
+        $(#[$meta])*
+        pub struct $name($ctx);
+
+        impl $name {
+            /// Length of the produced digest in bytes
+            pub const OUTPUT_LENGTH: usize = $len as _;
+
+            /// Start a new hash computation
+            pub fn new() -> Self {
+                let mut ctx = core::mem::MaybeUninit::uninit();
+                // unsafe: C API as documented, initializes the context
+                unsafe { $init(ctx.as_mut_ptr()) };
+                // unsafe: initialized in the init function
+                Self(unsafe { ctx.assume_init() })
+            }
+
+            /// Feed data into the hash computation
+            pub fn update(&mut self, data: &[u8]) {
+                // unsafe: C API as documented
+                unsafe { $update(&mut self.0, data.as_ptr() as _, data.len() as _) };
+            }
+
+            /// Finish the computation and return the digest
+            pub fn finalize(mut self) -> [u8; $len as usize] {
+                let mut result = [0; $len as usize];
+                self.finalize_to(&mut result);
+                result
+            }
+
+            fn finalize_to(&mut self, out: &mut [u8]) {
+                // Not just a debug assertion: The C function relies on it.
+                assert!(out.len() == Self::OUTPUT_LENGTH);
+                let $ctx_arg = &mut self.0;
+                let $out_arg = out.as_mut_ptr() as *mut core::ffi::c_void;
+                // unsafe: C API as documented, the output has the right size as checked
+                unsafe { $final };
+            }
+
+            /// Compute the digest of some data in a single call
+            pub fn digest(data: &[u8]) -> [u8; $len as usize] {
+                let mut hash = Self::new();
+                hash.update(data);
+                hash.finalize()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl digest::HashMarker for $name {}
+
+        impl digest::OutputSizeUser for $name {
+            type OutputSize = $outsize;
+        }
+
+        impl digest::Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                $name::update(self, data)
+            }
+        }
+
+        impl digest::FixedOutput for $name {
+            fn finalize_into(mut self, out: &mut GenericArray<u8, $outsize>) {
+                self.finalize_to(out.as_mut_slice())
+            }
+        }
+
+        impl digest::Reset for $name {
+            fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl digest::FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut GenericArray<u8, $outsize>) {
+                self.finalize_to(out.as_mut_slice());
+                digest::Reset::reset(self);
+            }
+        }
+    };
+}
+
+hash!(
+    /// SHA-256 hash computation
+    #[doc(alias = "sha256_context_t")]
+    Sha256,
+    riot_sys::sha256_context_t,
+    riot_sys::SHA256_DIGEST_LENGTH,
+    digest::consts::U32,
+    riot_sys::sha256_init,
+    // sha256_update is an inline wrapper around this
+    riot_sys::sha2xx_update,
+    |ctx, out| riot_sys::sha2xx_final(ctx, out as _, riot_sys::SHA256_DIGEST_LENGTH as _),
+);
+
+hash!(
+    /// SHA-224 hash computation
+    #[doc(alias = "sha224_context_t")]
+    Sha224,
+    riot_sys::sha224_context_t,
+    riot_sys::SHA224_DIGEST_LENGTH,
+    digest::consts::U28,
+    riot_sys::sha224_init,
+    // sha224_update is an inline wrapper around this
+    riot_sys::sha2xx_update,
+    |ctx, out| riot_sys::sha2xx_final(ctx, out as _, riot_sys::SHA224_DIGEST_LENGTH as _),
+);
+
+hash!(
+    /// SHA-1 hash computation
+    ///
+    /// SHA-1 is not collision resistant; only use it where required by existing protocols.
+    #[doc(alias = "sha1_context")]
+    Sha1,
+    riot_sys::sha1_context,
+    riot_sys::SHA1_DIGEST_LENGTH,
+    digest::consts::U20,
+    riot_sys::sha1_init,
+    riot_sys::sha1_update,
+    |ctx, out| riot_sys::sha1_final(ctx, out as _),
+);
+
+hash!(
+    /// MD5 hash computation
+    ///
+    /// MD5 is not collision resistant; only use it where required by existing protocols.
+    #[doc(alias = "md5_ctx_t")]
+    Md5,
+    riot_sys::md5_ctx_t,
+    riot_sys::MD5_DIGEST_LENGTH,
+    digest::consts::U16,
+    riot_sys::md5_init,
+    riot_sys::md5_update,
+    |ctx, out| riot_sys::md5_final(ctx, out as _),
+);
+
+/// HMAC with SHA-256
+///
+/// This implements the [digest::Mac] trait (through its components).
+#[doc(alias = "hmac_context_t")]
+pub struct HmacSha256(riot_sys::hmac_context_t);
+
+impl HmacSha256 {
+    /// Start a new HMAC computation with the given key
+    ///
+    /// Keys of any length are accepted; long keys are hashed as described in RFC 2104.
+    #[doc(alias = "hmac_sha256_init")]
+    pub fn new(key: &[u8]) -> Self {
+        let mut ctx = core::mem::MaybeUninit::uninit();
+        // unsafe: C API as documented, initializes the context
+        unsafe { riot_sys::hmac_sha256_init(ctx.as_mut_ptr(), key.as_ptr() as _, key.len() as _) };
+        // unsafe: initialized in the init function
+        Self(unsafe { ctx.assume_init() })
+    }
+
+    /// Feed data into the HMAC computation
+    #[doc(alias = "hmac_sha256_update")]
+    pub fn update(&mut self, data: &[u8]) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::hmac_sha256_update(&mut self.0, data.as_ptr() as _, data.len() as _) };
+    }
+
+    /// Finish the computation and return the authentication code
+    ///
+    /// Beware that comparing the result to a received code with `==` is not constant-time; use
+    /// [digest::Mac::verify_slice] for that.
+    #[doc(alias = "hmac_sha256_final")]
+    pub fn finalize(mut self) -> [u8; Sha256::OUTPUT_LENGTH] {
+        let mut result = [0; Sha256::OUTPUT_LENGTH];
+        // unsafe: C API as documented, the output has the right size
+        unsafe { riot_sys::hmac_sha256_final(&mut self.0, result.as_mut_ptr() as _) };
+        result
+    }
+
+    /// Compute the HMAC of some data in a single call
+    #[doc(alias = "hmac_sha256")]
+    pub fn mac(key: &[u8], data: &[u8]) -> [u8; Sha256::OUTPUT_LENGTH] {
+        let mut hmac = Self::new(key);
+        hmac.update(data);
+        hmac.finalize()
+    }
+}
+
+impl digest::MacMarker for HmacSha256 {}
+
+impl digest::OutputSizeUser for HmacSha256 {
+    type OutputSize = digest::consts::U32;
+}
+
+impl digest::crypto_common::KeySizeUser for HmacSha256 {
+    // Following the RustCrypto hmac crate, this is the block size, but any length is accepted
+    // through new_from_slice.
+    type KeySize = digest::consts::U64;
+}
+
+impl digest::KeyInit for HmacSha256 {
+    fn new(key: &digest::Key<Self>) -> Self {
+        HmacSha256::new(key.as_slice())
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, digest::InvalidLength> {
+        Ok(HmacSha256::new(key))
+    }
+}
+
+impl digest::Update for HmacSha256 {
+    fn update(&mut self, data: &[u8]) {
+        HmacSha256::update(self, data)
+    }
+}
+
+impl digest::FixedOutput for HmacSha256 {
+    fn finalize_into(self, out: &mut GenericArray<u8, digest::consts::U32>) {
+        out.copy_from_slice(&self.finalize());
+    }
+}
+
+/// Error returned by [`hkdf_sha256_expand()`] if more output is requested than HKDF can produce
+/// (255 times the hash length)
+#[derive(Debug, Copy, Clone)]
+pub struct OutputTooLong;
+
+/// HKDF-Extract step of [RFC 5869](https://www.rfc-editor.org/rfc/rfc5869) key derivation with
+/// SHA-256
+///
+/// This derives a pseudorandom key from some input keying material and an optional salt (an empty
+/// salt is equivalent to a zero-filled one).
+pub fn hkdf_sha256_extract(salt: &[u8], ikm: &[u8]) -> [u8; Sha256::OUTPUT_LENGTH] {
+    HmacSha256::mac(salt, ikm)
+}
+
+/// HKDF-Expand step of [RFC 5869](https://www.rfc-editor.org/rfc/rfc5869) key derivation with
+/// SHA-256
+///
+/// This fills `okm` with output keying material derived from a pseudorandom key (usually
+/// obtained from [`hkdf_sha256_extract()`]) and some context information.
+pub fn hkdf_sha256_expand(prk: &[u8], info: &[u8], okm: &mut [u8]) -> Result<(), OutputTooLong> {
+    if okm.len() > 255 * Sha256::OUTPUT_LENGTH {
+        return Err(OutputTooLong);
+    }
+    let mut previous: Option<[u8; Sha256::OUTPUT_LENGTH]> = None;
+    for (counter, chunk) in okm.chunks_mut(Sha256::OUTPUT_LENGTH).enumerate() {
+        let mut hmac = HmacSha256::new(prk);
+        if let Some(previous) = &previous {
+            hmac.update(previous);
+        }
+        hmac.update(info);
+        // Can not exceed 255 as checked above
+        hmac.update(&[counter as u8 + 1]);
+        let block = hmac.finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        previous = Some(block);
+    }
+    Ok(())
+}
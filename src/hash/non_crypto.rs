@@ -0,0 +1,55 @@
+//! Non-cryptographic hash functions
+//!
+//! These are fast 32-bit hashes for use in hash tables or [Bloom filters](crate::bloom). They are
+//! not suitable where an attacker may choose the input.
+
+macro_rules! non_crypto_hash {
+    ($(#[$meta:meta])* $name:ident, $cname:ident) => {
+        $(#[$meta])*
+        pub fn $name(data: &[u8]) -> u32 {
+            // unsafe: C API as documented
+            unsafe { riot_sys::$cname(data.as_ptr(), data.len() as _) }
+        }
+    };
+}
+
+non_crypto_hash!(
+    /// Bernstein's djb2 hash
+    djb2,
+    djb2_hash
+);
+non_crypto_hash!(
+    /// The sdbm hash (as used in the sdbm database library)
+    sdbm,
+    sdbm_hash
+);
+non_crypto_hash!(
+    /// The hash from Kernighan and Ritchie's "The C Programming Language"
+    kr,
+    kr_hash
+);
+non_crypto_hash!(
+    /// Shift-add-xor hash
+    sax,
+    sax_hash
+);
+non_crypto_hash!(
+    /// Donald E. Knuth's hash from "The Art Of Computer Programming"
+    dek,
+    dek_hash
+);
+non_crypto_hash!(
+    /// Fowler–Noll–Vo hash
+    fnv,
+    fnv_hash
+);
+non_crypto_hash!(
+    /// Rotating hash
+    rotating,
+    rotating_hash
+);
+non_crypto_hash!(
+    /// Bob Jenkins' one-at-a-time hash
+    one_at_a_time,
+    one_at_a_time_hash
+);
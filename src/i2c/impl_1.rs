@@ -141,3 +141,17 @@ impl i2c::I2c<i2c::SevenBitAddress> for I2CDevice {
         })
     }
 }
+
+// RIOT's I2C API has no non-blocking transfers, so the transaction is performed synchronously
+// inside the first poll. This still allows using async driver crates; other tasks are just not
+// run while the bus is busy.
+#[cfg(feature = "with_embedded_hal_async")]
+impl embedded_hal_async::i2c::I2c<i2c::SevenBitAddress> for I2CDevice {
+    async fn transaction(
+        &mut self,
+        address: i2c::SevenBitAddress,
+        operations: &mut [i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        i2c::I2c::transaction(self, address, operations)
+    }
+}
@@ -36,6 +36,7 @@ pub use cstr;
 
 pub mod error;
 
+pub mod c_str;
 mod helpers;
 mod never;
 
@@ -112,11 +113,30 @@ unsafe fn inline_cast_ref_mut<A, B>(input: &mut A) -> &mut B {
     core::mem::transmute(input)
 }
 
+#[cfg(riot_module_dbgpin)]
+pub mod dbgpin;
+pub mod debug;
+#[cfg(feature = "with_defmt")]
+mod defmt;
+#[cfg(riot_module_fmt)]
+pub mod fmt;
+#[cfg(feature = "with_log")]
+pub mod log;
 #[cfg(riot_module_saul)]
 pub mod saul;
+#[cfg(riot_module_sched_cb)]
+pub mod sched_cb;
 #[cfg(riot_module_shell)]
 pub mod shell;
+pub mod static_init;
+#[cfg(any(riot_module_malloc_monitor, riot_module_schedstatistics))]
+pub mod stats;
 pub mod stdio;
+#[cfg(any(
+    riot_module_test_utils_interactive_sync,
+    riot_module_test_utils_result_output
+))]
+pub mod test_utils;
 pub mod thread;
 // internally cfg-gated as it has a no-op implementation
 #[cfg(riot_module_gcoap)]
@@ -134,6 +154,35 @@ pub mod msg;
 #[cfg(riot_module_random)]
 pub mod random;
 
+#[cfg(riot_module_hashes)]
+pub mod hash;
+
+#[cfg(riot_module_crypto)]
+pub mod crypto;
+
+#[cfg(riot_module_psa_crypto)]
+pub mod psa_crypto;
+
+#[cfg(riot_module_c25519)]
+pub mod c25519;
+
+#[cfg(riot_module_checksum)]
+pub mod checksum;
+
+#[cfg(riot_module_color)]
+pub mod color;
+
+#[cfg(riot_module_base64)]
+pub mod base64;
+
+#[cfg(riot_module_bloom)]
+pub mod bloom;
+
+#[cfg(riot_module_nanocbor)]
+pub mod nanocbor;
+#[cfg(riot_module_senml)]
+pub mod senml;
+
 #[cfg(riot_module_periph_spi)]
 pub mod spi;
 
@@ -146,6 +195,12 @@ pub mod dac;
 #[cfg(riot_module_ztimer)]
 pub mod ztimer;
 
+#[cfg(all(feature = "with_embassy", riot_module_core_thread_flags))]
+pub mod embassy;
+
+#[cfg(riot_module_event)]
+pub mod event;
+
 pub mod mutex;
 #[cfg(riot_module_pthread)]
 pub mod rwlock;
@@ -157,6 +212,13 @@ mod panic;
 pub mod coap_handler;
 #[cfg(riot_module_gcoap)]
 pub mod coap_message;
+#[cfg(riot_module_nanocoap_cache)]
+pub mod nanocoap_cache;
+#[cfg(all(riot_module_gcoap, riot_module_liboscore))]
+pub mod oscore;
+
+#[cfg(riot_module_credman)]
+pub mod credman;
 
 #[cfg(riot_module_sock)]
 pub mod socket;
@@ -171,6 +233,9 @@ pub mod socket_embedded_nal_async_udp;
 #[cfg(all(riot_module_sock_tcp, feature = "with_embedded_nal"))]
 pub mod socket_embedded_nal_tcp;
 
+#[cfg(riot_module_usbus)]
+pub mod usbus;
+
 #[cfg(riot_module_periph_gpio)]
 pub mod gpio;
 
@@ -182,15 +247,33 @@ pub mod nimble {
     pub mod uuid;
 }
 
+#[cfg(all(riot_module_apa102, riot_module_color))]
+pub mod apa102;
 #[cfg(riot_module_ws281x)]
 pub mod ws281x;
 
 #[cfg(riot_module_microbit)]
 pub mod microbit;
 
+#[cfg(riot_module_disp_dev)]
+pub mod disp_dev;
+#[cfg(riot_module_touch_dev)]
+pub mod touch_dev;
+
+#[cfg(riot_module_motor_driver)]
+pub mod motor_driver;
+#[cfg(riot_module_servo)]
+pub mod servo;
+
 #[cfg(riot_module_vfs)]
 pub mod vfs;
 
+#[cfg(riot_module_mtd)]
+pub mod mtd;
+
+#[cfg(accessible_riot_sys_CPU_HAS_BACKUP_RAM)]
+pub mod backup_ram;
+
 mod impl_critical_section;
 pub mod interrupt;
 #[path = "main_module.rs"]
@@ -209,5 +292,18 @@ pub mod auto_init;
 ))]
 mod async_helpers;
 
+#[cfg(riot_module_periph_freqm)]
+pub mod freqm;
+
+#[cfg(riot_module_periph_timer)]
+pub mod timer;
+
 #[cfg(riot_module_periph_uart)]
 pub mod uart;
+
+#[cfg(riot_module_uuid)]
+pub mod uuid;
+
+// internally cfg-gated for the flashwrite parts
+#[cfg(riot_module_riotboot_slot)]
+pub mod riotboot;
@@ -0,0 +1,68 @@
+//! Backend for the [log] crate that outputs like RIOT's [logging](https://doc.riot-os.org/group__core__util.html)
+//!
+//! Once [`init()`] was called, messages from `log::error!()` to `log::debug!()` are shown if RIOT's
+//! `LOG_ERROR` to `LOG_DEBUG` would show them at the configured `LOG_LEVEL` (set, eg., through
+//! `CFLAGS += -DLOG_LEVEL=LOG_DEBUG`). `log::trace!()` messages are shown only when the level is
+//! `LOG_ALL`.
+//!
+//! Messages are printed to stdio. Log backend modules (such as `log_color`) only affect messages
+//! from C code.
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # fn f() {
+//! riot_wrappers::log::init().unwrap();
+//! log::info!("Rust code logs like RIOT");
+//! # }
+//! ```
+
+use ::log::{LevelFilter, Log, Metadata, Record};
+
+struct RiotLogger;
+
+static LOGGER: RiotLogger = RiotLogger;
+
+/// The [log] crate level filter that corresponds to RIOT's configured `LOG_LEVEL`
+pub const MAX_LEVEL: LevelFilter = if cfg!(riot_log_level = "none") {
+    LevelFilter::Off
+} else if cfg!(riot_log_level = "error") {
+    LevelFilter::Error
+} else if cfg!(riot_log_level = "warning") {
+    LevelFilter::Warn
+} else if cfg!(riot_log_level = "debug") {
+    LevelFilter::Debug
+} else if cfg!(riot_log_level = "all") {
+    LevelFilter::Trace
+} else {
+    LevelFilter::Info
+};
+
+/// Install the RIOT backend as the [log] crate's logger, showing messages up to [MAX_LEVEL]
+///
+/// The level can be lowered later through [`log::set_max_level()`](::log::set_max_level).
+///
+/// This fails if a logger was already installed.
+pub fn init() -> Result<(), ::log::SetLoggerError> {
+    ::log::set_logger(&LOGGER)?;
+    ::log::set_max_level(MAX_LEVEL);
+    Ok(())
+}
+
+impl Log for RiotLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= ::log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        use core::fmt::Write;
+
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        // Like printf in RIOT's default log_write, this does not report errors.
+        let _ = writeln!(crate::stdio::Stdio {}, "{}", record.args());
+    }
+
+    fn flush(&self) {}
+}
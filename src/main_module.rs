@@ -27,7 +27,12 @@ use crate::thread::{EndToken, StartToken};
 //     0
 // }
 
+use core::cell::UnsafeCell;
+use core::ffi::CStr;
 use core::fmt;
+use core::sync::atomic::AtomicBool;
+
+use crate::thread::TrackedThread;
 
 mod sealed {
     pub trait Sealed<Variant> {}
@@ -112,11 +117,46 @@ impl<F: Fn(StartToken) -> ((), EndToken)> UsableAsMain<[u8; 3]> for F {
 ///   above.
 /// * `fn main(tokens: StartToken) -> !` -- a frequently useful variation thereof for main loops
 ///   that are loops anyway.
+///
+/// Threads declared as [StaticThread]s can be started right before the main function is run:
+///
+/// ```
+/// # #![no_std]
+/// # #![no_main]
+/// use riot_wrappers::main::StaticThread;
+/// use riot_wrappers::riot_main;
+///
+/// static WORKER: StaticThread<2048> = StaticThread::new(c"worker", worker, 6);
+///
+/// riot_main!(main, threads = [WORKER]);
+///
+/// fn main() -> Result<(), &'static str> {
+///     unimplemented!()
+/// }
+///
+/// fn worker() {
+///     unimplemented!()
+/// }
+/// ```
+///
+/// Instead of a main function, `shell` can be given: The main thread then runs RIOT's shell with
+/// all commands registered through XFA (eg. using [`shell_command!`](crate::shell_command!)).
+/// This can be combined with `threads = [...]` as well.
 #[macro_export]
 macro_rules! riot_main {
-    ($main:ident) => {
+    (shell $(, threads = [$($thread:path),* $(,)?])?) => {
+        fn __riot_wrappers_shell_main() -> ! {
+            use $crate::shell::CommandList;
+            $crate::shell::new().run_forever()
+        }
+        $crate::riot_main!(__riot_wrappers_shell_main $(, threads = [$($thread),*])?);
+    };
+    ($main:ident $(, threads = [$($thread:path),* $(,)?])?) => {
         #[export_name = "main"]
         pub extern "C" fn c_main() -> i32 {
+            $($(
+                $thread.start().expect("Failed to start thread");
+            )*)?
             unsafe { <_ as $crate::main::UsableAsMain<_>>::call_main(&$main) }
         }
     };
@@ -174,3 +214,57 @@ impl<E: fmt::Debug> Termination for Result<crate::never::Never, E> {
         }
     }
 }
+
+/// A thread whose stack and entry function are statically allocated
+///
+/// Such threads are typically started by [`riot_main!`](crate::riot_main!) before the main
+/// function runs, but can be started manually as well.
+pub struct StaticThread<const STACKSIZE: usize> {
+    stack: UnsafeCell<[u8; STACKSIZE]>,
+    function: UnsafeCell<fn()>,
+    name: &'static CStr,
+    priority: u8,
+    started: AtomicBool,
+}
+
+// unsafe: The cells are only accessed once, when the thread is started, which is guarded by
+// `started`.
+unsafe impl<const STACKSIZE: usize> Sync for StaticThread<STACKSIZE> {}
+
+impl<const STACKSIZE: usize> StaticThread<STACKSIZE> {
+    /// Declare a thread that will run `function` at the given priority
+    ///
+    /// Note that in RIOT, lower numbers indicate higher priorities; the main thread runs at
+    /// `THREAD_PRIORITY_MAIN`.
+    pub const fn new(name: &'static CStr, function: fn(), priority: u8) -> Self {
+        Self {
+            stack: UnsafeCell::new([0; STACKSIZE]),
+            function: UnsafeCell::new(function),
+            name,
+            priority,
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Start the thread
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the thread was already started.
+    #[doc(alias = "thread_create")]
+    pub fn start(&'static self) -> Result<TrackedThread, riot_sys::kernel_pid_t> {
+        assert!(
+            !crate::static_init::claim(&self.started),
+            "Static thread started twice"
+        );
+        // unsafe: The started flag ensures that these are only ever handed out once.
+        let (stack, function) = unsafe { (&mut *self.stack.get(), &mut *self.function.get()) };
+        crate::thread::spawn(
+            stack,
+            function,
+            self.name,
+            self.priority,
+            riot_sys::THREAD_CREATE_STACKTEST as _,
+        )
+    }
+}
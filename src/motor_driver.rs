@@ -0,0 +1,118 @@
+//! Control of DC motors through [RIOT's DC motor
+//! driver](https://doc.riot-os.org/group__drivers__motor__driver.html)
+//!
+//! A driver controls several motors through a shared PWM device. Speeds are given in steps of the
+//! PWM's configured resolution, and limited to it:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::motor_driver::{MotorDriver, Direction};
+//! # fn f(params: &'static riot_sys::motor_driver_params_t) {
+//! let mut driver = MotorDriver::init(params).unwrap();
+//! let half = driver.max_speed() / 2;
+//! let mut left = driver.motor(0).unwrap();
+//! left.set_speed(Direction::Clockwise, half).unwrap();
+//! # }
+//! ```
+
+use riot_sys::{motor_driver_params_t, motor_driver_t};
+
+use crate::error::{NegativeErrorExt, NumericError};
+
+/// Rotation direction of a motor
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[doc(alias = "motor_direction_t")]
+pub enum Direction {
+    #[doc(alias = "MOTOR_CW")]
+    Clockwise,
+    #[doc(alias = "MOTOR_CCW")]
+    CounterClockwise,
+}
+
+/// An initialized motor driver
+pub struct MotorDriver {
+    dev: motor_driver_t,
+    params: &'static motor_driver_params_t,
+}
+
+impl MotorDriver {
+    /// Initialize the driver's PWM device and motor pins
+    #[doc(alias = "motor_driver_init")]
+    pub fn init(params: &'static motor_driver_params_t) -> Result<Self, NumericError> {
+        // unsafe: All-zero is a valid uninitialized device, which is initialized right away
+        let mut dev: motor_driver_t = unsafe { core::mem::zeroed() };
+        // unsafe: C API as documented; the parameters are static.
+        unsafe { riot_sys::motor_driver_init(&mut dev, params) }.negative_to_error()?;
+        Ok(Self { dev, params })
+    }
+
+    /// Number of motors configured on this driver
+    pub fn motor_count(&self) -> u8 {
+        self.params.nb_motors
+    }
+
+    /// Highest speed value, which corresponds to a PWM duty cycle of 100%
+    pub fn max_speed(&self) -> u32 {
+        self.params.pwm_resolution as _
+    }
+
+    /// Access a single motor, or None if there is no such motor
+    pub fn motor(&mut self, id: u8) -> Option<Motor<'_>> {
+        (id < self.motor_count()).then_some(Motor { driver: self, id })
+    }
+}
+
+/// A single motor on a [MotorDriver]
+pub struct Motor<'a> {
+    driver: &'a mut MotorDriver,
+    id: u8,
+}
+
+impl Motor<'_> {
+    /// Run the motor in the given direction
+    ///
+    /// Speeds above the driver's [maximum](MotorDriver::max_speed) are limited to it.
+    #[doc(alias = "motor_set")]
+    pub fn set_speed(&mut self, direction: Direction, speed: u32) -> Result<(), NumericError> {
+        let speed = speed.min(self.driver.max_speed()).min(i32::MAX as u32) as i32;
+        self.set_signed(match direction {
+            Direction::Clockwise => speed,
+            Direction::CounterClockwise => -speed,
+        })
+    }
+
+    /// Run the motor at a speed whose sign indicates the direction (positive is clockwise)
+    ///
+    /// Speeds whose magnitude exceeds the driver's [maximum](MotorDriver::max_speed) are limited
+    /// to it.
+    #[doc(alias = "motor_set")]
+    pub fn set_signed(&mut self, speed: i32) -> Result<(), NumericError> {
+        let max = self.driver.max_speed().min(i32::MAX as u32) as i32;
+        let speed = speed.clamp(-max, max);
+        // unsafe: C API as documented; the motor ID was checked at construction.
+        unsafe { riot_sys::motor_set(&self.driver.dev, self.id, speed) }.negative_to_error()?;
+        Ok(())
+    }
+
+    /// Stop the motor actively
+    #[doc(alias = "motor_brake")]
+    pub fn brake(&mut self) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::motor_brake(&self.driver.dev, self.id) };
+    }
+
+    /// Power the motor's H-bridge
+    #[doc(alias = "motor_enable")]
+    pub fn enable(&mut self) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::motor_enable(&self.driver.dev, self.id) };
+    }
+
+    /// Stop powering the motor's H-bridge, letting the motor run freely
+    #[doc(alias = "motor_disable")]
+    pub fn disable(&mut self) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::motor_disable(&self.driver.dev, self.id) };
+    }
+}
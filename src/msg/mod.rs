@@ -106,6 +106,67 @@ impl OpaqueMsg {
         let _ = unsafe { msg_receive(m.as_mut_ptr()) };
         OpaqueMsg(unsafe { m.assume_init() })
     }
+
+    /// Receive a message if one is available in the queue (or another thread is blocking to send
+    /// a message, if no queue is used), without blocking
+    #[doc(alias = "msg_try_receive")]
+    pub fn try_receive() -> Option<OpaqueMsg> {
+        let mut m: MaybeUninit<msg_t> = MaybeUninit::uninit();
+        // unsafe: C API as documented
+        match unsafe { riot_sys::msg_try_receive(m.as_mut_ptr()) } {
+            // unsafe: Message was written
+            1 => Some(OpaqueMsg(unsafe { m.assume_init() })),
+            _ => None,
+        }
+    }
+
+    /// Block to receive a message, but give up after the timeout has passed on the given clock
+    #[cfg(riot_module_ztimer)]
+    #[doc(alias = "ztimer_msg_receive_timeout")]
+    pub fn receive_timeout<const HZ: u32>(
+        clock: crate::thread::ValueInThread<crate::ztimer::Clock<HZ>>,
+        timeout: impl Into<crate::ztimer::Ticks<HZ>>,
+    ) -> Option<OpaqueMsg> {
+        let mut m: MaybeUninit<msg_t> = MaybeUninit::uninit();
+        // unsafe: C API as documented
+        let result = unsafe {
+            riot_sys::ztimer_msg_receive_timeout(
+                clock.into_inner().as_ptr(),
+                m.as_mut_ptr(),
+                timeout.into().0,
+            )
+        };
+        match result {
+            // unsafe: Message was written
+            r if r >= 0 => Some(OpaqueMsg(unsafe { m.assume_init() })),
+            _ => None,
+        }
+    }
+}
+
+/// Number of messages waiting in the current thread's message queue
+///
+/// This returns None if the thread has no message queue.
+#[doc(alias = "msg_avail")]
+pub fn available() -> Option<usize> {
+    // unsafe: C API as documented
+    let count = unsafe { riot_sys::msg_avail() };
+    count.try_into().ok()
+}
+
+/// Number of messages waiting in the message queue of the given thread
+///
+/// This returns None if the thread does not exist, or has no message queue.
+#[doc(alias = "msg_avail_thread")]
+pub fn available_in(thread: KernelPID) -> Option<usize> {
+    // Checking and querying without interruption, so that the thread can not exit in between
+    let count = crate::interrupt::free(|_| {
+        // msg_avail_thread would dereference the NULL thread of a PID that is not in use
+        thread.status().ok()?;
+        // unsafe: C API as documented; the thread exists.
+        Some(unsafe { riot_sys::msg_avail_thread(thread.into()) })
+    })?;
+    count.try_into().ok()
 }
 
 impl ::core::fmt::Debug for OpaqueMsg {
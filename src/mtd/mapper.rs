@@ -0,0 +1,110 @@
+//! Partitioning of an MTD device using [mtd_mapper]
+//!
+//! A [Parent] takes ownership of an MTD device, and any number of [Region]s can be created from it,
+//! each covering a range of sectors. A region acts as an MTD device of its own (through
+//! [`Region::as_mtd()`]), with addresses starting at zero at the region's start. This allows a
+//! single flash chip to host, for example, a file system, a key-value store and a firmware update
+//! slot side by side.
+//!
+//! Regions are not checked for overlap: Creating overlapping regions is not unsafe (all accesses go
+//! through the parent's lock), but will lead to the users trampling over each other's data.
+//!
+//! [mtd_mapper]: https://doc.riot-os.org/group__drivers__mtd__mapper.html
+
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+
+use super::Mtd;
+use crate::error::{NumericError, EINVAL};
+
+/// An MTD device that is shared among [Region]s
+pub struct Parent<'a> {
+    parent: UnsafeCell<riot_sys::mtd_mapper_parent_t>,
+    _phantom: PhantomData<Mtd<'a>>,
+}
+
+// unsafe: All access to the parent from the regions goes through the mtd_mapper, which serializes
+// access using the parent's mutex.
+unsafe impl Sync for Parent<'_> {}
+unsafe impl Send for Parent<'_> {}
+
+impl<'a> Parent<'a> {
+    /// Prepare an MTD device for sharing among regions
+    ///
+    /// The device should be initialized (using [`Mtd::init()`]) before, as its geometry is used to
+    /// set up the regions.
+    #[doc(alias = "MTD_PARENT_INIT")]
+    pub fn new(mut dev: Mtd<'a>) -> Self {
+        // unsafe: All zero is the initializer for the mutex, and for the other fields as
+        // populated by MTD_PARENT_INIT.
+        let mut parent: riot_sys::mtd_mapper_parent_t = unsafe { core::mem::zeroed() };
+        parent.mtd = dev.as_ptr();
+        Self {
+            parent: UnsafeCell::new(parent),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn parent_dev(&self) -> &riot_sys::mtd_dev_t {
+        // unsafe: The pointer was valid at construction and stays so through the lifetime; the
+        // geometry fields we read are not changed by mtd_mapper.
+        unsafe { &*(*self.parent.get()).mtd }
+    }
+
+    /// Create a region that starts at sector `first_sector` and spans `sector_count` sectors
+    ///
+    /// This returns `EINVAL` if the region exceeds the parent device.
+    pub fn region(&self, first_sector: u32, sector_count: u32) -> Result<Region<'_>, NumericError> {
+        let parent_dev = self.parent_dev();
+        if first_sector
+            .checked_add(sector_count)
+            .map_or(true, |end| end > parent_dev.sector_count)
+        {
+            return Err(EINVAL);
+        }
+
+        // unsafe: Zero is a valid value for all the fields we don't set explicitly (they are
+        // populated when the region is initialized).
+        let mut region: riot_sys::mtd_mapper_region_t = unsafe { core::mem::zeroed() };
+        region.mtd.driver = core::ptr::addr_of!(riot_sys::mtd_mapper_driver);
+        region.mtd.sector_count = sector_count;
+        region.mtd.pages_per_sector = parent_dev.pages_per_sector;
+        region.mtd.page_size = parent_dev.page_size;
+        region.mtd.write_size = parent_dev.write_size;
+        region.parent = self.parent.get();
+        region.sector = first_sector;
+
+        Ok(Region {
+            region,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// A range of sectors on a [Parent] device
+///
+/// Access the region through [`.as_mtd()`](Region::as_mtd); as with any MTD, it needs to be
+/// initialized before use.
+pub struct Region<'p> {
+    region: riot_sys::mtd_mapper_region_t,
+    _phantom: PhantomData<&'p Parent<'p>>,
+}
+
+// unsafe: The only pointer in there is to the parent, which is Sync.
+unsafe impl Send for Region<'_> {}
+
+impl<'p> Region<'p> {
+    /// Number of the parent's sector at which the region starts
+    pub fn first_sector(&self) -> u32 {
+        self.region.sector
+    }
+
+    /// Access the region as an MTD device
+    ///
+    /// The region may be moved between uses of the returned device, as mtd_mapper does not retain
+    /// any pointers to it.
+    pub fn as_mtd(&mut self) -> Mtd<'_> {
+        // unsafe: The device is exclusively borrowed from us for the returned lifetime
+        unsafe { Mtd::from_ptr(&mut self.region.mtd) }
+    }
+}
@@ -0,0 +1,210 @@
+//! Access to [Memory Technology Devices (MTD)](https://doc.riot-os.org/group__drivers__mtd.html)
+//!
+//! An MTD is a flash (or flash-like) storage device such as an external SPI NOR flash chip; the
+//! [Mtd] type wraps a device for reading, writing and erasing. With the `with_embedded_storage`
+//! feature, it implements the [embedded_storage] traits, so it can be passed to storage crates
+//! that are agnostic of RIOT.
+//!
+//! A single device can be split into several independent regions using the [mapper] submodule.
+
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::error::{NegativeErrorExt, NumericError};
+
+#[cfg(riot_module_mtd_mapper)]
+pub mod mapper;
+
+/// An MTD device that is exclusively accessible through this reference
+///
+/// The lifetime indicates how long the underlying `mtd_dev_t` is borrowed for; it is `'static`
+/// for devices that are defined by the board.
+pub struct Mtd<'a> {
+    dev: NonNull<riot_sys::mtd_dev_t>,
+    _phantom: PhantomData<&'a mut riot_sys::mtd_dev_t>,
+}
+
+impl Mtd<'static> {
+    /// Access one of the board's MTD devices by its index
+    ///
+    /// The device is not initialized by this; call [`.init()`](Mtd::init) before use.
+    ///
+    /// ## Safety
+    ///
+    /// The index must be smaller than the number of devices (`MTD_NUMOF`), and no other
+    /// references to the same device may be used while this is in use.
+    #[doc(alias = "mtd_dev_get")]
+    pub unsafe fn from_index(index: usize) -> Self {
+        Self::from_ptr(crate::inline_cast_mut(riot_sys::inline::mtd_dev_get(
+            index as _,
+        )))
+    }
+}
+
+impl<'a> Mtd<'a> {
+    /// Wrap a raw MTD device pointer
+    ///
+    /// ## Safety
+    ///
+    /// The pointer must be valid and exclusively usable through the result for the lifetime `'a`.
+    pub unsafe fn from_ptr(dev: *mut riot_sys::mtd_dev_t) -> Self {
+        Self {
+            dev: NonNull::new(dev).expect("MTD device pointer is null"),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Raw device pointer, eg. to pass the device on to C APIs
+    pub fn as_ptr(&mut self) -> *mut riot_sys::mtd_dev_t {
+        self.dev.as_ptr()
+    }
+
+    /// Initialize the device
+    ///
+    /// This is necessary before any access, and also populates the geometry information for
+    /// devices that obtain it from the hardware.
+    #[doc(alias = "mtd_init")]
+    pub fn init(&mut self) -> Result<(), NumericError> {
+        // unsafe: Pointer validity ensured by construction
+        unsafe { riot_sys::mtd_init(self.dev.as_ptr()) }.negative_to_error()?;
+        Ok(())
+    }
+
+    fn dev(&self) -> &riot_sys::mtd_dev_t {
+        // unsafe: Pointer validity ensured by construction, and we don't hand out mutable
+        // references.
+        unsafe { self.dev.as_ref() }
+    }
+
+    /// Number of sectors on the device
+    pub fn sector_count(&self) -> u32 {
+        self.dev().sector_count
+    }
+
+    /// Number of pages in each sector
+    pub fn pages_per_sector(&self) -> u32 {
+        self.dev().pages_per_sector
+    }
+
+    /// Size of a page in bytes
+    pub fn page_size(&self) -> u32 {
+        self.dev().page_size
+    }
+
+    /// Minimal number of bytes that can be written at once
+    pub fn write_size(&self) -> u32 {
+        self.dev().write_size
+    }
+
+    /// Size of a sector (the erase unit) in bytes
+    pub fn sector_size(&self) -> u32 {
+        self.pages_per_sector() * self.page_size()
+    }
+
+    /// Total size of the device in bytes
+    pub fn size(&self) -> u64 {
+        u64::from(self.sector_count()) * u64::from(self.sector_size())
+    }
+
+    /// Read `buf.len()` bytes from the device, starting at the byte address `addr`
+    #[doc(alias = "mtd_read")]
+    pub fn read(&mut self, addr: u32, buf: &mut [u8]) -> Result<(), NumericError> {
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::mtd_read(
+                self.dev.as_ptr(),
+                buf.as_mut_ptr() as _,
+                addr,
+                buf.len() as _,
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Write data to already erased memory, starting at the byte address `addr`
+    ///
+    /// The written area may span several pages, but needs to be erased before (otherwise, the
+    /// result depends on the flash technology).
+    #[doc(alias = "mtd_write_page_raw")]
+    pub fn write_raw(&mut self, addr: u32, data: &[u8]) -> Result<(), NumericError> {
+        let page_size = self.page_size();
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::mtd_write_page_raw(
+                self.dev.as_ptr(),
+                data.as_ptr() as _,
+                addr / page_size,
+                addr % page_size,
+                data.len() as _,
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Write data starting at the byte address `addr`, erasing and re-writing the affected
+    /// sectors as needed
+    #[cfg(riot_module_mtd_write_page)]
+    #[doc(alias = "mtd_write_page")]
+    pub fn write(&mut self, addr: u32, data: &[u8]) -> Result<(), NumericError> {
+        let page_size = self.page_size();
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::mtd_write_page(
+                self.dev.as_ptr(),
+                data.as_ptr() as _,
+                addr / page_size,
+                addr % page_size,
+                data.len() as _,
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Erase `count` sectors starting at sector number `first`
+    #[doc(alias = "mtd_erase_sector")]
+    pub fn erase_sectors(&mut self, first: u32, count: u32) -> Result<(), NumericError> {
+        // unsafe: C API as documented
+        unsafe { riot_sys::mtd_erase_sector(self.dev.as_ptr(), first, count) }
+            .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Erase a sector aligned area of `len` bytes starting at the byte address `addr`
+    #[doc(alias = "mtd_erase")]
+    pub fn erase(&mut self, addr: u32, len: u32) -> Result<(), NumericError> {
+        // unsafe: C API as documented; alignment is checked there
+        unsafe { riot_sys::mtd_erase(self.dev.as_ptr(), addr, len) }.negative_to_error()?;
+        Ok(())
+    }
+}
+
+// unsafe: An MTD device is usable from any thread, and we have exclusive access
+unsafe impl Send for Mtd<'_> {}
+
+#[cfg(feature = "with_embedded_storage")]
+mod impl_embedded_storage {
+    use super::*;
+
+    impl embedded_storage::ReadStorage for Mtd<'_> {
+        type Error = NumericError;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), NumericError> {
+            Mtd::read(self, offset, bytes)
+        }
+
+        fn capacity(&self) -> usize {
+            self.size() as _
+        }
+    }
+
+    // Storage promises to take care of erasing, which only mtd_write_page does.
+    #[cfg(riot_module_mtd_write_page)]
+    impl embedded_storage::Storage for Mtd<'_> {
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), NumericError> {
+            Mtd::write(self, offset, bytes)
+        }
+    }
+}
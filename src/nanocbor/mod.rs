@@ -0,0 +1,517 @@
+//! CBOR encoding and decoding through the [nanocbor] package
+//!
+//! An [Encoder] writes CBOR items into a user provided buffer; a [Decoder] reads items from a
+//! buffer through typed getters, and enters arrays and maps through closures.
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::nanocbor::*;
+//! # fn f() -> Result<(), Error> {
+//! let mut buf = [0; 32];
+//! let mut encoder = Encoder::new(&mut buf);
+//! encoder.array(2).uint(42).tstr("hello");
+//! let encoded = encoder.finish()?;
+//!
+//! let mut decoder = Decoder::new(encoded);
+//! let (number, text) = decoder.array(|items| Ok((items.u32()?, items.tstr()?)))?;
+//! assert!(number == 42 && text == "hello");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `with_serde` feature, encoders and decoders also work as serde serializers and
+//! deserializers (see [`Encoder::serialize()`] and [`Decoder::deserialize()`]).
+//!
+//! [nanocbor]: https://doc.riot-os.org/group__pkg__nanocbor.html
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "with_serde")]
+mod serde_impl;
+
+/// Error type of CBOR encoding and decoding
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A value does not fit the requested type
+    Overflow,
+    /// The next item is of a different type than requested
+    InvalidType,
+    /// The end of the buffer was reached (when decoding), or the buffer was too small (when
+    /// encoding)
+    End,
+    /// Containers are nested too deeply
+    Recursion,
+    /// A searched item was not found
+    NotFound,
+    /// A text string was not valid UTF-8
+    InvalidUtf8,
+    /// An error occurred in a serde implementation
+    #[cfg(feature = "with_serde")]
+    Custom,
+}
+
+impl Error {
+    /// Convert a nanocbor return value into a result
+    ///
+    /// The numeric values are those of `nanocbor_error_t`.
+    fn check(code: i32) -> Result<i32, Self> {
+        match code {
+            -1 => Err(Error::Overflow),
+            -2 => Err(Error::InvalidType),
+            -3 => Err(Error::End),
+            -4 => Err(Error::Recursion),
+            -5 => Err(Error::NotFound),
+            c if c < 0 => Err(Error::InvalidType),
+            c => Ok(c),
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Writer of CBOR items into a buffer
+///
+/// Encoding methods return the encoder, so they can be chained. Encoding continues (counting the
+/// required length) when the buffer is exhausted; whether everything fit is reported by
+/// [`.finish()`](Encoder::finish).
+pub struct Encoder<'a> {
+    encoder: riot_sys::nanocbor_encoder_t,
+    buf: *mut u8,
+    buf_len: usize,
+    _phantom: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Encoder<'a> {
+    /// Start encoding into a buffer
+    #[doc(alias = "nanocbor_encoder_init")]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        let mut encoder = core::mem::MaybeUninit::uninit();
+        // unsafe: C API as documented; the buffer outlives the encoder as it is borrowed for 'a
+        unsafe {
+            riot_sys::nanocbor_encoder_init(encoder.as_mut_ptr(), buf.as_mut_ptr(), buf.len() as _)
+        };
+        Self {
+            // unsafe: Initialized by the init function
+            encoder: unsafe { encoder.assume_init() },
+            buf: buf.as_mut_ptr(),
+            buf_len: buf.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Pointer to the underlying encoder, for use with C APIs that encode into it
+    ///
+    /// Like the encoding methods, such functions do not need to be checked for errors.
+    pub fn as_mut_ptr(&mut self) -> *mut riot_sys::nanocbor_encoder_t {
+        &mut self.encoder
+    }
+
+    /// Number of bytes the items encoded so far take
+    ///
+    /// This may exceed the buffer's length if the items did not fit.
+    #[doc(alias = "nanocbor_encoded_len")]
+    pub fn encoded_len(&mut self) -> usize {
+        // unsafe: C API as documented
+        (unsafe { riot_sys::nanocbor_encoded_len(&mut self.encoder) }) as _
+    }
+
+    /// Finish encoding, and return the encoded data
+    ///
+    /// This fails with [`Error::End`] if the buffer was too small; the required size is available
+    /// through [`.encoded_len()`](Encoder::encoded_len) before finishing.
+    pub fn finish(mut self) -> Result<&'a [u8], Error> {
+        let len = self.encoded_len();
+        if len > self.buf_len {
+            return Err(Error::End);
+        }
+        // unsafe: This is the buffer the encoder was created from, and its borrow ends with the
+        // encoder.
+        Ok(unsafe { core::slice::from_raw_parts(self.buf, len) })
+    }
+
+    /// Encode a boolean
+    #[doc(alias = "nanocbor_fmt_bool")]
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_bool(&mut self.encoder, value) };
+        self
+    }
+
+    /// Encode a null item
+    #[doc(alias = "nanocbor_fmt_null")]
+    pub fn null(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_null(&mut self.encoder) };
+        self
+    }
+
+    /// Encode an unsigned integer
+    #[doc(alias = "nanocbor_fmt_uint")]
+    pub fn uint(&mut self, value: u64) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_uint(&mut self.encoder, value) };
+        self
+    }
+
+    /// Encode a signed integer
+    #[doc(alias = "nanocbor_fmt_int")]
+    pub fn int(&mut self, value: i64) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_int(&mut self.encoder, value) };
+        self
+    }
+
+    /// Encode a single precision float
+    #[doc(alias = "nanocbor_fmt_float")]
+    pub fn float(&mut self, value: f32) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_float(&mut self.encoder, value) };
+        self
+    }
+
+    /// Encode a double precision float
+    #[doc(alias = "nanocbor_fmt_double")]
+    pub fn double(&mut self, value: f64) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_double(&mut self.encoder, value) };
+        self
+    }
+
+    /// Encode a tag; it applies to the next item
+    #[doc(alias = "nanocbor_fmt_tag")]
+    pub fn tag(&mut self, tag: u64) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_tag(&mut self.encoder, tag as _) };
+        self
+    }
+
+    /// Encode a byte string
+    #[doc(alias = "nanocbor_put_bstr")]
+    pub fn bstr(&mut self, value: &[u8]) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_put_bstr(&mut self.encoder, value.as_ptr(), value.len() as _) };
+        self
+    }
+
+    /// Encode a text string
+    #[doc(alias = "nanocbor_put_tstrn")]
+    pub fn tstr(&mut self, value: &str) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::nanocbor_put_tstrn(&mut self.encoder, value.as_ptr() as _, value.len() as _)
+        };
+        self
+    }
+
+    /// Start an array of `len` items; the items are encoded next
+    #[doc(alias = "nanocbor_fmt_array")]
+    pub fn array(&mut self, len: usize) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_array(&mut self.encoder, len as _) };
+        self
+    }
+
+    /// Start a map of `len` key-value pairs; keys and values are encoded next, alternating
+    #[doc(alias = "nanocbor_fmt_map")]
+    pub fn map(&mut self, len: usize) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_map(&mut self.encoder, len as _) };
+        self
+    }
+
+    /// Start an array of unknown length, which is terminated by
+    /// [`.end_indefinite()`](Encoder::end_indefinite)
+    #[doc(alias = "nanocbor_fmt_array_indefinite")]
+    pub fn array_indefinite(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_array_indefinite(&mut self.encoder) };
+        self
+    }
+
+    /// Start a map of unknown length, which is terminated by
+    /// [`.end_indefinite()`](Encoder::end_indefinite)
+    #[doc(alias = "nanocbor_fmt_map_indefinite")]
+    pub fn map_indefinite(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_map_indefinite(&mut self.encoder) };
+        self
+    }
+
+    /// Terminate an array or map of unknown length
+    #[doc(alias = "nanocbor_fmt_end_indefinite")]
+    pub fn end_indefinite(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_fmt_end_indefinite(&mut self.encoder) };
+        self
+    }
+}
+
+/// Major types of CBOR items
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MajorType {
+    Uint,
+    Nint,
+    Bstr,
+    Tstr,
+    Array,
+    Map,
+    Tag,
+    /// Floats and simple values (booleans, null, undefined)
+    FloatOrSimple,
+}
+
+/// Reader of CBOR items from a buffer
+///
+/// Getters consume the next item if it has the requested type, and leave the decoder unchanged
+/// otherwise. Cloning the decoder allows looking ahead.
+#[derive(Clone)]
+pub struct Decoder<'a> {
+    value: riot_sys::nanocbor_value_t,
+    _phantom: PhantomData<&'a [u8]>,
+}
+
+macro_rules! getter {
+    ($(#[$meta:meta])* $name:ident, $t:ty, $cfn:ident) => {
+        $(#[$meta])*
+        pub fn $name(&mut self) -> Result<$t, Error> {
+            let mut result = Default::default();
+            // unsafe: C API as documented
+            Error::check(unsafe { riot_sys::$cfn(&mut self.value, &mut result) })?;
+            Ok(result)
+        }
+    };
+}
+
+impl<'a> Decoder<'a> {
+    /// Start decoding a buffer
+    #[doc(alias = "nanocbor_decoder_init")]
+    pub fn new(data: &'a [u8]) -> Self {
+        let mut value = core::mem::MaybeUninit::uninit();
+        // unsafe: C API as documented; the data outlives the decoder as it is borrowed for 'a
+        unsafe {
+            riot_sys::nanocbor_decoder_init(value.as_mut_ptr(), data.as_ptr(), data.len() as _)
+        };
+        Self {
+            // unsafe: Initialized by the init function
+            value: unsafe { value.assume_init() },
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Whether all items of the buffer (or of the entered container) have been read
+    #[doc(alias = "nanocbor_at_end")]
+    pub fn at_end(&self) -> bool {
+        // unsafe: C API as documented
+        unsafe { riot_sys::nanocbor_at_end(&self.value) }
+    }
+
+    /// Major type of the next item
+    #[doc(alias = "nanocbor_get_type")]
+    pub fn major_type(&self) -> Result<MajorType, Error> {
+        // unsafe: C API as documented
+        Ok(
+            match Error::check(unsafe { riot_sys::nanocbor_get_type(&self.value) })? {
+                0 => MajorType::Uint,
+                1 => MajorType::Nint,
+                2 => MajorType::Bstr,
+                3 => MajorType::Tstr,
+                4 => MajorType::Array,
+                5 => MajorType::Map,
+                6 => MajorType::Tag,
+                _ => MajorType::FloatOrSimple,
+            },
+        )
+    }
+
+    getter!(
+        /// Read an unsigned integer
+        u8,
+        u8,
+        nanocbor_get_uint8
+    );
+    getter!(
+        /// Read an unsigned integer
+        u16,
+        u16,
+        nanocbor_get_uint16
+    );
+    getter!(
+        /// Read an unsigned integer
+        u32,
+        u32,
+        nanocbor_get_uint32
+    );
+    getter!(
+        /// Read an unsigned integer
+        u64,
+        u64,
+        nanocbor_get_uint64
+    );
+    getter!(
+        /// Read an integer, which may be encoded as an unsigned or negative integer
+        i8,
+        i8,
+        nanocbor_get_int8
+    );
+    getter!(
+        /// Read an integer, which may be encoded as an unsigned or negative integer
+        i16,
+        i16,
+        nanocbor_get_int16
+    );
+    getter!(
+        /// Read an integer, which may be encoded as an unsigned or negative integer
+        i32,
+        i32,
+        nanocbor_get_int32
+    );
+    getter!(
+        /// Read an integer, which may be encoded as an unsigned or negative integer
+        i64,
+        i64,
+        nanocbor_get_int64
+    );
+    getter!(
+        /// Read a boolean
+        bool,
+        bool,
+        nanocbor_get_bool
+    );
+    getter!(
+        /// Read a float (which may be encoded in half or single precision)
+        f32,
+        f32,
+        nanocbor_get_float
+    );
+    getter!(
+        /// Read a float (which may be encoded in half, single or double precision)
+        f64,
+        f64,
+        nanocbor_get_double
+    );
+    getter!(
+        /// Read a tag; the tagged item is read next
+        tag,
+        u32,
+        nanocbor_get_tag
+    );
+
+    /// Read a null item
+    #[doc(alias = "nanocbor_get_null")]
+    pub fn null(&mut self) -> Result<(), Error> {
+        // unsafe: C API as documented
+        Error::check(unsafe { riot_sys::nanocbor_get_null(&mut self.value) })?;
+        Ok(())
+    }
+
+    /// Read a byte string
+    #[doc(alias = "nanocbor_get_bstr")]
+    pub fn bstr(&mut self) -> Result<&'a [u8], Error> {
+        let mut ptr = core::ptr::null();
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::check(unsafe { riot_sys::nanocbor_get_bstr(&mut self.value, &mut ptr, &mut len) })?;
+        // unsafe: The C function points into the buffer, which is borrowed for 'a
+        Ok(unsafe { core::slice::from_raw_parts(ptr, len as _) })
+    }
+
+    /// Read a text string
+    #[doc(alias = "nanocbor_get_tstr")]
+    pub fn tstr(&mut self) -> Result<&'a str, Error> {
+        let mut ptr = core::ptr::null();
+        let mut len = 0;
+        let mut lookahead = self.clone();
+        // unsafe: C API as documented
+        Error::check(unsafe {
+            riot_sys::nanocbor_get_tstr(&mut lookahead.value, &mut ptr, &mut len)
+        })?;
+        // unsafe: The C function points into the buffer, which is borrowed for 'a
+        let bytes = unsafe { core::slice::from_raw_parts(ptr, len as _) };
+        let result = core::str::from_utf8(bytes).map_err(|_| Error::InvalidUtf8)?;
+        *self = lookahead;
+        Ok(result)
+    }
+
+    /// Skip over the next item (including all items in it if it is a container)
+    #[doc(alias = "nanocbor_skip")]
+    pub fn skip(&mut self) -> Result<(), Error> {
+        // unsafe: C API as documented
+        Error::check(unsafe { riot_sys::nanocbor_skip(&mut self.value) })?;
+        Ok(())
+    }
+
+    /// Read the next item without decoding it, returning its encoded form
+    #[doc(alias = "nanocbor_get_subcbor")]
+    pub fn raw(&mut self) -> Result<&'a [u8], Error> {
+        let mut ptr = core::ptr::null();
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::check(unsafe {
+            riot_sys::nanocbor_get_subcbor(&mut self.value, &mut ptr, &mut len)
+        })?;
+        // unsafe: The C function points into the buffer, which is borrowed for 'a
+        Ok(unsafe { core::slice::from_raw_parts(ptr, len as _) })
+    }
+
+    fn enter(&mut self, map: bool) -> Result<Decoder<'a>, Error> {
+        let mut inner = core::mem::MaybeUninit::uninit();
+        // unsafe: C API as documented
+        Error::check(unsafe {
+            if map {
+                riot_sys::nanocbor_enter_map(&self.value, inner.as_mut_ptr())
+            } else {
+                riot_sys::nanocbor_enter_array(&self.value, inner.as_mut_ptr())
+            }
+        })?;
+        Ok(Decoder {
+            // unsafe: Initialized as the call was successful
+            value: unsafe { inner.assume_init() },
+            _phantom: PhantomData,
+        })
+    }
+
+    fn leave(&mut self, mut inner: Decoder<'a>) -> Result<(), Error> {
+        while !inner.at_end() {
+            inner.skip()?;
+        }
+        // unsafe: C API as documented; the inner decoder was entered from self and is at its end.
+        unsafe { riot_sys::nanocbor_leave_container(&mut self.value, &mut inner.value) };
+        Ok(())
+    }
+
+    /// Read an array, whose items are read from the decoder passed to the closure
+    ///
+    /// Items the closure does not read are skipped. Iterating over the items typically looks like
+    /// `while !items.at_end() { ... }`.
+    #[doc(alias = "nanocbor_enter_array")]
+    pub fn array<R>(
+        &mut self,
+        f: impl FnOnce(&mut Decoder<'a>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let mut inner = self.enter(false)?;
+        let result = f(&mut inner)?;
+        self.leave(inner)?;
+        Ok(result)
+    }
+
+    /// Read a map, whose keys and values are read (alternatingly) from the decoder passed to the
+    /// closure
+    ///
+    /// Items the closure does not read are skipped.
+    #[doc(alias = "nanocbor_enter_map")]
+    pub fn map<R>(
+        &mut self,
+        f: impl FnOnce(&mut Decoder<'a>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let mut inner = self.enter(true)?;
+        let result = f(&mut inner)?;
+        self.leave(inner)?;
+        Ok(result)
+    }
+}
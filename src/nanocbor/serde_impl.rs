@@ -0,0 +1,522 @@
+//! serde integration for the [Encoder] and [Decoder]
+//!
+//! The data model mapping follows the conventions of the serde_cbor / ciborium crates: structs are
+//! maps keyed by field names, unit variants are text strings, and other enum variants are
+//! single-entry maps from the variant name to the content.
+
+use serde::de::{self, DeserializeSeed, Visitor};
+use serde::ser::{self, Serialize};
+
+use super::{Decoder, Encoder, Error, MajorType};
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: core::fmt::Display>(_msg: T) -> Self {
+        Error::Custom
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: core::fmt::Display>(_msg: T) -> Self {
+        Error::Custom
+    }
+}
+
+impl<'a> Encoder<'a> {
+    /// Encode any serializable value
+    pub fn serialize<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<&mut Self, Error> {
+        value.serialize(&mut *self)?;
+        Ok(self)
+    }
+}
+
+impl<'de> Decoder<'de> {
+    /// Decode the next item into any deserializable type
+    ///
+    /// Strings and byte strings can be borrowed from the decoded buffer.
+    pub fn deserialize<T: de::Deserialize<'de>>(&mut self) -> Result<T, Error> {
+        T::deserialize(self)
+    }
+}
+
+/// Serializer state for arrays and maps
+pub struct Compound<'e, 'a> {
+    encoder: &'e mut Encoder<'a>,
+    indefinite: bool,
+}
+
+impl Compound<'_, '_> {
+    fn finish(self) -> Result<(), Error> {
+        if self.indefinite {
+            self.encoder.end_indefinite();
+        }
+        Ok(())
+    }
+
+    fn item<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.encoder)
+    }
+}
+
+impl<'e, 'a> ser::Serializer for &'e mut Encoder<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'e, 'a>;
+    type SerializeTuple = Compound<'e, 'a>;
+    type SerializeTupleStruct = Compound<'e, 'a>;
+    type SerializeTupleVariant = Compound<'e, 'a>;
+    type SerializeMap = Compound<'e, 'a>;
+    type SerializeStruct = Compound<'e, 'a>;
+    type SerializeStructVariant = Compound<'e, 'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.bool(v);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.int(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.uint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.float(v);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.double(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.tstr(v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.tstr(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.bstr(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.null();
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.null();
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.tstr(variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.map(1).tstr(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'e, 'a>, Error> {
+        match len {
+            Some(len) => self.array(len),
+            None => self.array_indefinite(),
+        };
+        Ok(Compound {
+            encoder: self,
+            indefinite: len.is_none(),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'e, 'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'e, 'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'e, 'a>, Error> {
+        self.map(1).tstr(variant);
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'e, 'a>, Error> {
+        match len {
+            Some(len) => self.map(len),
+            None => self.map_indefinite(),
+        };
+        Ok(Compound {
+            encoder: self,
+            indefinite: len.is_none(),
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'e, 'a>, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'e, 'a>, Error> {
+        self.map(1).tstr(variant);
+        self.serialize_map(Some(len))
+    }
+
+    fn collect_str<T: core::fmt::Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        // Without an allocator, the text needs to be formatted into a buffer first to know its
+        // length.
+        let mut buffer = heapless::String::<64>::new();
+        core::fmt::write(&mut buffer, format_args!("{}", value)).map_err(|_| Error::Custom)?;
+        self.tstr(&buffer);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.item(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.encoder.tstr(key);
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.encoder.tstr(key);
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// Deserializer access to the items of an entered array or map
+struct ContainerAccess<'c, 'de> {
+    inner: &'c mut Decoder<'de>,
+}
+
+impl<'de> de::SeqAccess<'de> for ContainerAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.inner.at_end() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.inner).map(Some)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for ContainerAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.inner.at_end() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.inner).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.inner)
+    }
+}
+
+/// Deserializer access to an enum, which is either a bare text string (for unit variants), or
+/// the inside of a single-entry map
+struct EnumAccess<'c, 'de> {
+    decoder: &'c mut Decoder<'de>,
+    unit: bool,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let variant = seed.deserialize(&mut *self.decoder)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for EnumAccess<'_, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        if self.unit {
+            Ok(())
+        } else {
+            // Accept explicit nulls as content
+            self.decoder.null()
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        if self.unit {
+            return Err(Error::InvalidType);
+        }
+        seed.deserialize(&mut *self.decoder)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        if self.unit {
+            return Err(Error::InvalidType);
+        }
+        de::Deserializer::deserialize_seq(&mut *self.decoder, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if self.unit {
+            return Err(Error::InvalidType);
+        }
+        de::Deserializer::deserialize_map(&mut *self.decoder, visitor)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Decoder<'de> {
+    type Error = Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.major_type()? {
+            MajorType::Uint => visitor.visit_u64(self.u64()?),
+            MajorType::Nint => visitor.visit_i64(self.i64()?),
+            MajorType::Bstr => visitor.visit_borrowed_bytes(self.bstr()?),
+            MajorType::Tstr => visitor.visit_borrowed_str(self.tstr()?),
+            MajorType::Array => self.array(|inner| visitor.visit_seq(ContainerAccess { inner })),
+            MajorType::Map => self.map(|inner| visitor.visit_map(ContainerAccess { inner })),
+            MajorType::Tag => {
+                // Tags carry no meaning in serde's data model
+                self.tag()?;
+                self.deserialize_any(visitor)
+            }
+            MajorType::FloatOrSimple => {
+                if let Ok(v) = self.bool() {
+                    visitor.visit_bool(v)
+                } else if self.null().is_ok() {
+                    visitor.visit_unit()
+                } else {
+                    visitor.visit_f64(self.f64()?)
+                }
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.null().is_ok() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.major_type()? {
+            MajorType::Tstr => visitor.visit_enum(EnumAccess {
+                decoder: self,
+                unit: true,
+            }),
+            MajorType::Map => self.map(|decoder| {
+                visitor.visit_enum(EnumAccess {
+                    decoder,
+                    unit: false,
+                })
+            }),
+            _ => Err(Error::InvalidType),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
@@ -0,0 +1,94 @@
+//! Inspection and invalidation of the [nanocoap response
+//! cache](https://doc.riot-os.org/group__net__nanocoap__cache.html)
+//!
+//! The cache is populated by gcoap's client and by its forward proxy. This module allows
+//! applications to monitor the cache's use, and to drop responses that are known to be outdated
+//! (eg. after the resource changed on the origin server).
+//!
+//! ## Forward proxy control
+//!
+//! This module only covers the cache. Controlling the forward proxy itself (enabling or disabling
+//! it at runtime, or configuring an upstream) is not supported yet: it first needs an API in RIOT's
+//! `gcoap_forward_proxy` module, which so far is active whenever it is built in, and forwards every
+//! request to the origin named in its Proxy-Uri (or Proxy-Scheme and Uri-Host) option.
+//!
+//! ## Safety
+//!
+//! The cache is not synchronized: gcoap modifies its lists without any locking. All functions
+//! that access the cache are thus unsafe, and may only be called from the gcoap thread (eg. from
+//! inside a request handler), or while gcoap is otherwise known not to access the cache.
+
+use riot_sys::nanocoap_cache_entry_t;
+
+/// Key identifying a cached response, derived from the request it answers
+pub type Key = [u8; riot_sys::CONFIG_NANOCOAP_CACHE_KEY_LENGTH as usize];
+
+/// Number of cache entries in use
+///
+/// See the [module level safety requirements](self#safety).
+#[doc(alias = "nanocoap_cache_used_count")]
+pub unsafe fn used_count() -> usize {
+    // unsafe: C API as documented; synchronization is up to the caller.
+    unsafe { riot_sys::nanocoap_cache_used_count() as _ }
+}
+
+/// Number of cache entries still available
+///
+/// See the [module level safety requirements](self#safety).
+#[doc(alias = "nanocoap_cache_free_count")]
+pub unsafe fn free_count() -> usize {
+    // unsafe: C API as documented; synchronization is up to the caller.
+    unsafe { riot_sys::nanocoap_cache_free_count() as _ }
+}
+
+/// Build the cache key for a request
+#[cfg(riot_module_gcoap)]
+#[doc(alias = "nanocoap_cache_key_generate")]
+pub fn key_for(request: &mut crate::gcoap::PacketBuffer<'_>) -> Key {
+    let mut key = Key::default();
+    // unsafe: C API as documented; the key has the required length.
+    unsafe { riot_sys::nanocoap_cache_key_generate(request.pkt_ptr(), key.as_mut_ptr()) };
+    key
+}
+
+unsafe fn lookup(key: &Key) -> *mut nanocoap_cache_entry_t {
+    // unsafe: C API as documented; the key has the required length, and synchronization is up to
+    // the caller.
+    unsafe { riot_sys::nanocoap_cache_key_lookup(key.as_ptr()) }
+}
+
+/// Whether a response for the given key is cached
+///
+/// See the [module level safety requirements](self#safety).
+#[doc(alias = "nanocoap_cache_key_lookup")]
+pub unsafe fn contains(key: &Key) -> bool {
+    // unsafe: Synchronization is up to the caller.
+    !unsafe { lookup(key) }.is_null()
+}
+
+/// Remove the response for the given key from the cache
+///
+/// Returns true if there was a cached response.
+///
+/// See the [module level safety requirements](self#safety).
+#[doc(alias = "nanocoap_cache_del")]
+pub unsafe fn invalidate(key: &Key) -> bool {
+    // unsafe: Synchronization is up to the caller.
+    let entry = unsafe { lookup(key) };
+    if entry.is_null() {
+        return false;
+    }
+    // unsafe: C API as documented; the entry was just found in the cache, and synchronization is
+    // up to the caller.
+    unsafe { riot_sys::nanocoap_cache_del(entry) == 0 }
+}
+
+/// Remove all responses from the cache
+///
+/// See the [module level safety requirements](self#safety).
+#[doc(alias = "nanocoap_cache_init")]
+pub unsafe fn clear() {
+    // unsafe: C API as documented; re-initializing puts all entries back into the free list.
+    // Synchronization is up to the caller.
+    unsafe { riot_sys::nanocoap_cache_init() };
+}
@@ -0,0 +1,567 @@
+//! End-to-end protection of CoAP messages with OSCORE (RFC 8613) through the [liboscore
+//! package](https://doc.riot-os.org/group__pkg__liboscore.html)
+//!
+//! A security context is derived once from its input parameters (the master secret and salt, and
+//! the sender and recipient IDs), and is then used to unprotect incoming requests and protect the
+//! matching responses inside a gcoap handler:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::oscore::*;
+//! # use riot_wrappers::gcoap::PacketBuffer;
+//! # use coap_numbers;
+//! # fn f(mut pkt: PacketBuffer, context: &mut SecurityContext) -> Result<(), Error> {
+//! let (code, mut request_id) = {
+//!     let request = unprotect_request(&mut pkt, context)?;
+//!     (request.code(), request.request_id())
+//! };
+//! // ... evaluate the decrypted request based on its code ...
+//! pkt.resp_init(coap_numbers::code::CHANGED).map_err(|_| Error::Protect)?;
+//! protect_response(&mut pkt, context, &mut request_id, |response| {
+//!     response.set_code(coap_numbers::code::CONTENT);
+//!     response.set_payload(b"Hello")
+//! })??;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! On the client side, a request is protected with [protect_request] into a packet set up through
+//! `gcoap_req_init`, and its response is processed with [unprotect_response] in the gcoap response
+//! handler. As this crate has no wrappers for gcoap's client side, these work on the `coap_pkt_t`
+//! directly.
+//!
+//! Only gcoap's messages are supported; messages handled through plain nanocoap (eg. its server's
+//! request handlers) can not be processed by this module.
+//!
+//! ## Replay protection
+//!
+//! Requests that were seen before are rejected with [Error::Duplicate]. As a fresh
+//! [SecurityContext] does not know which requests were seen before the last reboot, this also
+//! happens to all requests until the replay window is initialized. The Echo based recovery of RFC
+//! 8613 Appendix B.1.2, which does that, is not implemented; the application can only reject such
+//! requests (eg. with 4.01 Unauthorized), and a client needs to establish a new context.
+//!
+//! ## Persistence
+//!
+//! Contexts are kept in memory only. A context that outlives a reboot (ie. is derived from the same
+//! master secret and IDs again after a reboot) **must not** be started with
+//! [`SecurityContext::new()`], as reusing sequence numbers reuses AEAD nonces, which breaks the
+//! encryption. Instead, the application stores the [sender sequence
+//! number](SecurityContext::sender_sequence_number) in non-volatile memory, and resumes with
+//! [`SecurityContext::with_sequence_number()`], following RFC 8613 Appendix B.1.1.
+
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+use riot_sys::{
+    coap_pkt_t, oscore_context_primitive, oscore_context_primitive_immutables, oscore_context_t,
+    oscore_msg_native_t, oscore_msg_protected_t, oscore_oscoreoption_t, oscore_requestid_t,
+};
+
+use crate::gcoap::PacketBuffer;
+
+/// CoAP option number of the OSCORE option
+const OPTION_OSCORE: u16 = 9;
+
+/// Error in any of the OSCORE operations
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The algorithm number is not supported by the cryptographic backend
+    UnsupportedAlgorithm,
+    /// Key derivation failed, or an ID does not fit the algorithm's nonce
+    Derivation,
+    /// The message carries no (or an unparsable) OSCORE option
+    NotOscore,
+    /// The request was already processed before, or the replay window is not initialized (see
+    /// [replay protection](self#replay-protection))
+    Duplicate,
+    /// The message could not be decrypted
+    Unprotect,
+    /// The message could not be prepared or encrypted, or did not fit the buffer
+    Protect,
+}
+
+#[doc(alias = "oscore_cryptoerr_is_error")]
+fn is_crypto_error(err: riot_sys::oscore_cryptoerr_t) -> bool {
+    // unsafe: C API as documented
+    unsafe { riot_sys::oscore_cryptoerr_is_error(err) }
+}
+
+#[doc(alias = "oscore_msgerr_protected_is_error")]
+fn msg_result(err: riot_sys::oscore_msgerr_protected_t) -> Result<(), Error> {
+    // unsafe: C API as documented
+    match unsafe { riot_sys::oscore_msgerr_protected_is_error(err) } {
+        false => Ok(()),
+        true => Err(Error::Protect),
+    }
+}
+
+/// Input parameters of a security context, with the keys derived from them
+///
+/// This is the immutable part of a security context, which is typically placed in a static.
+#[doc(alias = "oscore_context_primitive_immutables")]
+pub struct ContextParameters(oscore_context_primitive_immutables);
+
+impl ContextParameters {
+    /// Derive the context's keys and common IV as described in RFC 8613 Section 3.2
+    ///
+    /// The algorithms are given by their COSE numbers, eg. 10 for AES-CCM-16-64-128 and 5 for
+    /// HKDF SHA-256.
+    #[doc(alias = "oscore_context_primitive_derive")]
+    pub fn derive(
+        aead_algorithm: i32,
+        hkdf_algorithm: i32,
+        master_secret: &[u8],
+        master_salt: &[u8],
+        id_context: Option<&[u8]>,
+        sender_id: &[u8],
+        recipient_id: &[u8],
+    ) -> Result<Self, Error> {
+        let mut immutables = oscore_context_primitive_immutables::default();
+
+        // unsafe: C API as documented
+        let err = unsafe {
+            riot_sys::oscore_crypto_aead_from_number(&mut immutables.aeadalg, aead_algorithm)
+        };
+        if is_crypto_error(err) {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+        let mut hkdf = MaybeUninit::uninit();
+        // unsafe: C API as documented
+        let err =
+            unsafe { riot_sys::oscore_crypto_hkdf_from_number(hkdf.as_mut_ptr(), hkdf_algorithm) };
+        if is_crypto_error(err) {
+            return Err(Error::UnsupportedAlgorithm);
+        }
+        // unsafe: Initialized by the successful call
+        let hkdf = unsafe { hkdf.assume_init() };
+
+        if sender_id.len() > immutables.sender_id.len()
+            || recipient_id.len() > immutables.recipient_id.len()
+        {
+            return Err(Error::Derivation);
+        }
+        immutables.sender_id[..sender_id.len()].copy_from_slice(sender_id);
+        immutables.sender_id_len = sender_id.len() as _;
+        immutables.recipient_id[..recipient_id.len()].copy_from_slice(recipient_id);
+        immutables.recipient_id_len = recipient_id.len() as _;
+
+        let id_context = id_context.unwrap_or(&[]);
+        // unsafe: C API as documented; the algorithm and IDs are set as required.
+        let result = unsafe {
+            riot_sys::oscore_context_primitive_derive(
+                &mut immutables,
+                hkdf,
+                master_secret.as_ptr(),
+                master_secret.len() as _,
+                master_salt.as_ptr(),
+                master_salt.len() as _,
+                id_context.as_ptr(),
+                id_context.len() as _,
+            )
+        };
+        if is_crypto_error(result) {
+            return Err(Error::Derivation);
+        }
+        Ok(Self(immutables))
+    }
+}
+
+/// The mutable part of a security context (sender sequence number and replay window)
+///
+/// This is created with an uninitialized replay window, which requires the peer to go through the
+/// Echo recovery of RFC 8613 Appendix B.1.2 (see [the module level
+/// documentation](self#replay-protection)).
+#[doc(alias = "oscore_context_primitive")]
+pub struct SecurityContext<'a> {
+    primitive: oscore_context_primitive,
+    // The context as it is handed to liboscore, which keeps pointers to it in messages while they
+    // are processed. Its data pointer is refreshed on every use, as the security context may have
+    // been moved in between.
+    context: oscore_context_t,
+    _parameters: PhantomData<&'a ContextParameters>,
+}
+
+impl<'a> SecurityContext<'a> {
+    /// Create a context that starts at sender sequence number 0
+    ///
+    /// This may only be used for contexts whose parameters were never used before (eg. because
+    /// their master secret or salt was freshly established); see the [module level
+    /// documentation](self#persistence) for the alternative.
+    pub fn new(parameters: &'a ContextParameters) -> Self {
+        Self::with_sequence_number(parameters, 0)
+    }
+
+    /// Create a context that resumes after a reboot at a stored sender sequence number
+    ///
+    /// Following RFC 8613 Appendix B.1.1, the stored number needs to be at least as large as any
+    /// sequence number that was used before the reboot. A typical scheme is to store the current
+    /// [sender sequence number](Self::sender_sequence_number) plus some margin K in non-volatile
+    /// memory, and to store again (adding K) whenever the context's sequence number reaches the
+    /// stored value.
+    pub fn with_sequence_number(parameters: &'a ContextParameters, sequence_number: u64) -> Self {
+        let mut primitive = oscore_context_primitive::default();
+        primitive.immutables = &parameters.0;
+        primitive.sender_sequence_number = sequence_number as _;
+        Self {
+            primitive,
+            context: oscore_context_t {
+                type_: riot_sys::oscore_context_type_OSCORE_CONTEXT_PRIMITIVE,
+                data: core::ptr::null_mut(),
+            },
+            _parameters: PhantomData,
+        }
+    }
+
+    /// The sender sequence number that will be used next
+    pub fn sender_sequence_number(&self) -> u64 {
+        self.primitive.sender_sequence_number as _
+    }
+
+    /// The context to pass to liboscore
+    ///
+    /// The pointer stays valid for as long as the security context is borrowed exclusively.
+    fn as_context(&mut self) -> *mut oscore_context_t {
+        self.context.data = &mut self.primitive as *mut _ as *mut _;
+        &mut self.context
+    }
+}
+
+/// Information from a request that is needed to protect its response (on the server side) or to
+/// unprotect its response (on the client side)
+#[doc(alias = "oscore_requestid_t")]
+pub struct RequestId(oscore_requestid_t);
+
+/// A decrypted message, accessed through liboscore's message API
+///
+/// This is the inner message of an OSCORE protected CoAP message: Its code, (encrypted) options
+/// and payload are those that the application sees.
+#[doc(alias = "oscore_msg_protected_t")]
+pub struct Message<'a> {
+    msg: oscore_msg_protected_t,
+    // Borrows both the underlying packet and the security context
+    _buffer: PhantomData<&'a mut ()>,
+}
+
+impl Message<'_> {
+    #[doc(alias = "oscore_msg_protected_get_code")]
+    pub fn code(&self) -> u8 {
+        // unsafe: C API as documented; the message is not modified despite the mutable pointer.
+        unsafe { riot_sys::oscore_msg_protected_get_code(&self.msg as *const _ as *mut _) }
+    }
+
+    #[doc(alias = "oscore_msg_protected_set_code")]
+    pub fn set_code(&mut self, code: u8) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::oscore_msg_protected_set_code(&mut self.msg, code) }
+    }
+
+    /// Add an option; options need to be added in ascending order, and before the payload is set
+    #[doc(alias = "oscore_msg_protected_append_option")]
+    pub fn append_option(&mut self, number: u16, value: &[u8]) -> Result<(), Error> {
+        // unsafe: C API as documented
+        let err = unsafe {
+            riot_sys::oscore_msg_protected_append_option(
+                &mut self.msg,
+                number,
+                value.as_ptr(),
+                value.len() as _,
+            )
+        };
+        msg_result(err)
+    }
+
+    fn map_payload(&mut self) -> Result<&mut [u8], Error> {
+        let mut data = core::ptr::null_mut();
+        let mut len = 0;
+        // unsafe: C API as documented
+        let err = unsafe {
+            riot_sys::oscore_msg_protected_map_payload(&mut self.msg, &mut data, &mut len)
+        };
+        msg_result(err)?;
+        // unsafe: Valid as per the C API for as long as the message is not modified otherwise,
+        // which the exclusive borrow ensures
+        Ok(unsafe { core::slice::from_raw_parts_mut(data, len as _) })
+    }
+
+    /// The message's payload
+    #[doc(alias = "oscore_msg_protected_map_payload")]
+    pub fn payload(&mut self) -> &[u8] {
+        self.map_payload().unwrap_or(&mut [])
+    }
+
+    /// Set the message's payload
+    ///
+    /// This fails if the payload does not fit the remaining space of the message.
+    #[doc(alias = "oscore_msg_protected_trim_payload")]
+    pub fn set_payload(&mut self, payload: &[u8]) -> Result<(), Error> {
+        let buffer = self.map_payload()?;
+        buffer
+            .get_mut(..payload.len())
+            .ok_or(Error::Protect)?
+            .copy_from_slice(payload);
+        // unsafe: C API as documented
+        let err = unsafe {
+            riot_sys::oscore_msg_protected_trim_payload(&mut self.msg, payload.len() as _)
+        };
+        msg_result(err)
+    }
+}
+
+/// A message that was decrypted in place, and is released when dropped
+struct Unprotected<'a>(Message<'a>);
+
+impl Drop for Unprotected<'_> {
+    #[doc(alias = "oscore_release_unprotected")]
+    fn drop(&mut self) {
+        // unsafe: C API as documented; the message was successfully unprotected
+        unsafe { riot_sys::oscore_release_unprotected(&mut self.0.msg) };
+    }
+}
+
+/// An incoming request that was decrypted in place
+///
+/// When this is dropped, the request's buffer is released to be used for the response.
+pub struct UnprotectedRequest<'a> {
+    message: Unprotected<'a>,
+    request_id: oscore_requestid_t,
+}
+
+impl<'a> UnprotectedRequest<'a> {
+    /// Data to pass on into [protect_response]
+    pub fn request_id(&self) -> RequestId {
+        RequestId(self.request_id)
+    }
+}
+
+impl<'a> core::ops::Deref for UnprotectedRequest<'a> {
+    type Target = Message<'a>;
+    fn deref(&self) -> &Message<'a> {
+        &self.message.0
+    }
+}
+
+impl<'a> core::ops::DerefMut for UnprotectedRequest<'a> {
+    fn deref_mut(&mut self) -> &mut Message<'a> {
+        &mut self.message.0
+    }
+}
+
+/// An incoming response that was decrypted in place
+pub struct UnprotectedResponse<'a>(Unprotected<'a>);
+
+impl<'a> core::ops::Deref for UnprotectedResponse<'a> {
+    type Target = Message<'a>;
+    fn deref(&self) -> &Message<'a> {
+        &self.0 .0
+    }
+}
+
+impl<'a> core::ops::DerefMut for UnprotectedResponse<'a> {
+    fn deref_mut(&mut self) -> &mut Message<'a> {
+        &mut self.0 .0
+    }
+}
+
+fn native(pkt: *mut coap_pkt_t) -> oscore_msg_native_t {
+    oscore_msg_native_t {
+        pkt: crate::inline_cast_mut(pkt),
+        ..Default::default()
+    }
+}
+
+fn parse_option(option: &[u8]) -> Result<oscore_oscoreoption_t, Error> {
+    let mut header = MaybeUninit::uninit();
+    // unsafe: C API as documented; the header refers into the packet, which is only accessed
+    // through liboscore from here on.
+    if !unsafe {
+        riot_sys::oscore_oscoreoption_parse(header.as_mut_ptr(), option.as_ptr(), option.len() as _)
+    } {
+        return Err(Error::NotOscore);
+    }
+    // unsafe: Initialized by the successful parse
+    Ok(unsafe { header.assume_init() })
+}
+
+/// Encrypt a message that was prepared (and populated by `build`) into its packet
+#[doc(alias = "oscore_encrypt_message")]
+fn encrypt<R>(
+    pkt: *mut coap_pkt_t,
+    msg: MaybeUninit<oscore_msg_protected_t>,
+    build: impl FnOnce(&mut Message<'_>) -> R,
+) -> Result<R, Error> {
+    let mut message = Message {
+        // unsafe: Initialized by the successful prepare_request or prepare_response of the caller
+        msg: unsafe { msg.assume_init() },
+        _buffer: PhantomData,
+    };
+    let output = build(&mut message);
+
+    let mut native = native(pkt);
+    // unsafe: C API as documented
+    let result = unsafe { riot_sys::oscore_encrypt_message(&mut message.msg, &mut native) };
+    if result != riot_sys::oscore_finish_result_OSCORE_FINISH_OK {
+        return Err(Error::Protect);
+    }
+    Ok(output)
+}
+
+/// Verify and decrypt an incoming request in place
+///
+/// Fails with [Error::NotOscore] if the request carries no OSCORE option; applications that serve
+/// both protected and unprotected resources can then continue processing it as a plain request.
+#[doc(alias = "oscore_unprotect_request")]
+pub fn unprotect_request<'a>(
+    request: &'a mut PacketBuffer<'_>,
+    context: &'a mut SecurityContext<'_>,
+) -> Result<UnprotectedRequest<'a>, Error> {
+    let option = request
+        .opt_iter()
+        .find(|(number, _)| *number == OPTION_OSCORE)
+        .ok_or(Error::NotOscore)?
+        .1;
+    let header = parse_option(option)?;
+
+    let mut msg = MaybeUninit::uninit();
+    let mut request_id = MaybeUninit::uninit();
+    // unsafe: C API as documented. The message keeps a pointer to the context, which stays valid
+    // as the context stays borrowed by the returned request.
+    let result = unsafe {
+        riot_sys::oscore_unprotect_request(
+            native(request.pkt_ptr()),
+            msg.as_mut_ptr(),
+            &header,
+            context.as_context(),
+            request_id.as_mut_ptr(),
+        )
+    };
+    match result {
+        riot_sys::oscore_unprotect_request_result_OSCORE_UNPROTECT_REQUEST_OK => (),
+        riot_sys::oscore_unprotect_request_result_OSCORE_UNPROTECT_REQUEST_DUPLICATE => {
+            // The message was still decrypted and needs to be released, but is not to be acted on
+            // (see the module level documentation on replay protection).
+            // unsafe: Initialized by unprotect_request
+            unsafe { riot_sys::oscore_release_unprotected(msg.as_mut_ptr()) };
+            return Err(Error::Duplicate);
+        }
+        _ => return Err(Error::Unprotect),
+    }
+
+    Ok(UnprotectedRequest {
+        message: Unprotected(Message {
+            // unsafe: Initialized by the successful unprotect_request
+            msg: unsafe { msg.assume_init() },
+            _buffer: PhantomData,
+        }),
+        // unsafe: Initialized by the successful unprotect_request
+        request_id: unsafe { request_id.assume_init() },
+    })
+}
+
+/// Build a protected response in the packet buffer
+///
+/// The response (which needs to be initialized using [PacketBuffer::resp_init] before) is
+/// populated through the [Message] passed to `build`; that message's code and payload are what
+/// the client sees after decryption. When `build` returns, the message is encrypted into the
+/// packet buffer.
+#[doc(alias = "oscore_prepare_response")]
+#[doc(alias = "oscore_encrypt_message")]
+pub fn protect_response<R>(
+    response: &mut PacketBuffer<'_>,
+    context: &mut SecurityContext<'_>,
+    request_id: &mut RequestId,
+    build: impl FnOnce(&mut Message<'_>) -> R,
+) -> Result<R, Error> {
+    let mut msg = MaybeUninit::uninit();
+    // unsafe: C API as documented
+    let result = unsafe {
+        riot_sys::oscore_prepare_response(
+            native(response.pkt_ptr()),
+            msg.as_mut_ptr(),
+            context.as_context(),
+            &mut request_id.0,
+        )
+    };
+    if result != riot_sys::oscore_prepare_result_OSCORE_PREPARE_OK {
+        return Err(Error::Protect);
+    }
+    encrypt(response.pkt_ptr(), msg, build)
+}
+
+/// Build a protected request in a packet
+///
+/// The packet needs to be initialized through `gcoap_req_init` before, without a path: the
+/// request's Uri-Path is added as an option through the [Message] passed to `build`, along with
+/// the request's code and payload. When `build` returns, the message is encrypted into the packet,
+/// which can then be sent through gcoap.
+///
+/// The returned request ID is needed to [unprotect the response](unprotect_response).
+#[doc(alias = "oscore_prepare_request")]
+#[doc(alias = "oscore_encrypt_message")]
+pub fn protect_request<R>(
+    request: &mut coap_pkt_t,
+    context: &mut SecurityContext<'_>,
+    build: impl FnOnce(&mut Message<'_>) -> R,
+) -> Result<(R, RequestId), Error> {
+    let mut msg = MaybeUninit::uninit();
+    let mut request_id = MaybeUninit::uninit();
+    // unsafe: C API as documented
+    let result = unsafe {
+        riot_sys::oscore_prepare_request(
+            native(request),
+            msg.as_mut_ptr(),
+            context.as_context(),
+            request_id.as_mut_ptr(),
+        )
+    };
+    if result != riot_sys::oscore_prepare_result_OSCORE_PREPARE_OK {
+        return Err(Error::Protect);
+    }
+    let output = encrypt(request, msg, build)?;
+    // unsafe: Initialized by the successful prepare_request
+    Ok((output, RequestId(unsafe { request_id.assume_init() })))
+}
+
+/// Verify and decrypt a response (as received in a gcoap response handler) in place
+///
+/// The request ID is the one returned when [protecting the request](protect_request).
+#[doc(alias = "oscore_unprotect_response")]
+pub fn unprotect_response<'a>(
+    response: &'a mut coap_pkt_t,
+    context: &'a mut SecurityContext<'_>,
+    request_id: &mut RequestId,
+) -> Result<UnprotectedResponse<'a>, Error> {
+    let mut value = core::ptr::null_mut();
+    // unsafe: C API as documented
+    let len = unsafe { riot_sys::coap_opt_get_opaque(response, OPTION_OSCORE as _, &mut value) };
+    if len < 0 {
+        return Err(Error::NotOscore);
+    }
+    // unsafe: Points into the packet, which is exclusively borrowed
+    let option = unsafe { core::slice::from_raw_parts(value, len as _) };
+    let mut header = parse_option(option)?;
+
+    let mut msg = MaybeUninit::uninit();
+    // unsafe: C API as documented. The message keeps a pointer to the context, which stays valid
+    // as the context stays borrowed by the returned response.
+    let result = unsafe {
+        riot_sys::oscore_unprotect_response(
+            native(response),
+            msg.as_mut_ptr(),
+            &mut header,
+            context.as_context(),
+            &mut request_id.0,
+        )
+    };
+    if result != riot_sys::oscore_unprotect_response_result_OSCORE_UNPROTECT_RESPONSE_OK {
+        return Err(Error::Unprotect);
+    }
+
+    Ok(UnprotectedResponse(Unprotected(Message {
+        // unsafe: Initialized by the successful unprotect_response
+        msg: unsafe { msg.assume_init() },
+        _buffer: PhantomData,
+    })))
+}
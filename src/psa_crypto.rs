@@ -0,0 +1,629 @@
+//! Cryptographic operations through RIOT's implementation of the [PSA Crypto API]
+//!
+//! Keys are created through [`Key::import()`] or [`Key::generate()`] from a set of
+//! [KeyAttributes], and are then used through methods of the [Key] handle. Depending on the key's
+//! location, the key material may reside in a secure element and never be accessible to the
+//! application.
+//!
+//! Algorithms are selected through typed enums ([HashAlgorithm], [AeadAlgorithm],
+//! [SignatureAlgorithm]); whether a particular algorithm is available depends on the `psa_*`
+//! modules enabled in RIOT -- unavailable ones produce [`Error::NotSupported`].
+//!
+//! [PSA Crypto API]: https://doc.riot-os.org/group__sys__psa__crypto.html
+
+use riot_sys::{psa_algorithm_t, psa_key_id_t, psa_status_t};
+
+/// Error type of PSA operations
+///
+/// The variants correspond to the `PSA_ERROR_*` status codes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    GenericError,
+    NotSupported,
+    NotPermitted,
+    BufferTooSmall,
+    AlreadyExists,
+    DoesNotExist,
+    BadState,
+    InvalidArgument,
+    InsufficientMemory,
+    InsufficientStorage,
+    CommunicationFailure,
+    StorageFailure,
+    HardwareFailure,
+    InsufficientEntropy,
+    InvalidSignature,
+    InvalidPadding,
+    InsufficientData,
+    InvalidHandle,
+    CorruptionDetected,
+    /// A status code not covered by the other variants
+    Other(psa_status_t),
+}
+
+impl Error {
+    /// Convert a PSA status code into a result
+    ///
+    /// The numeric values are those of the PSA specification; they can not be taken from riot_sys
+    /// because they are defined as casting macros.
+    fn from_status(status: psa_status_t) -> Result<(), Self> {
+        Err(match status {
+            0 => return Ok(()),
+            -132 => Error::GenericError,
+            -133 => Error::NotPermitted,
+            -134 => Error::NotSupported,
+            -135 => Error::InvalidArgument,
+            -136 => Error::InvalidHandle,
+            -137 => Error::BadState,
+            -138 => Error::BufferTooSmall,
+            -139 => Error::AlreadyExists,
+            -140 => Error::DoesNotExist,
+            -141 => Error::InsufficientMemory,
+            -142 => Error::InsufficientStorage,
+            -143 => Error::InsufficientData,
+            -145 => Error::CommunicationFailure,
+            -146 => Error::StorageFailure,
+            -147 => Error::HardwareFailure,
+            -148 => Error::InsufficientEntropy,
+            -149 => Error::InvalidSignature,
+            -150 => Error::InvalidPadding,
+            -151 => Error::CorruptionDetected,
+            s => Error::Other(s),
+        })
+    }
+}
+
+/// Initialize the PSA Crypto implementation
+///
+/// This needs to be called before any other operation; calling it again is harmless.
+#[doc(alias = "psa_crypto_init")]
+pub fn init() -> Result<(), Error> {
+    // unsafe: C API makes no requirements
+    Error::from_status(unsafe { riot_sys::psa_crypto_init() })
+}
+
+/// Hash algorithms, usable for [hash computation](hash_compute) and inside signature algorithms
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn to_raw(self) -> psa_algorithm_t {
+        match self {
+            HashAlgorithm::Sha1 => 0x02000005,
+            HashAlgorithm::Sha224 => 0x02000008,
+            HashAlgorithm::Sha256 => 0x02000009,
+            HashAlgorithm::Sha384 => 0x0200000a,
+            HashAlgorithm::Sha512 => 0x0200000b,
+        }
+    }
+}
+
+/// Authenticated encryption algorithms (with their default tag lengths)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AeadAlgorithm {
+    Ccm,
+    Gcm,
+    ChaCha20Poly1305,
+}
+
+impl AeadAlgorithm {
+    fn to_raw(self) -> psa_algorithm_t {
+        match self {
+            AeadAlgorithm::Ccm => 0x05500100,
+            AeadAlgorithm::Gcm => 0x05500200,
+            AeadAlgorithm::ChaCha20Poly1305 => 0x05100500,
+        }
+    }
+}
+
+/// Signature algorithms
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    /// ECDSA with the given hash algorithm (randomized)
+    Ecdsa(HashAlgorithm),
+    /// EdDSA without prehashing (eg. Ed25519)
+    PureEdDsa,
+}
+
+impl SignatureAlgorithm {
+    fn to_raw(self) -> psa_algorithm_t {
+        match self {
+            SignatureAlgorithm::Ecdsa(hash) => 0x06000600 | (hash.to_raw() & 0xff),
+            SignatureAlgorithm::PureEdDsa => 0x06000800,
+        }
+    }
+}
+
+/// Any algorithm a key can be restricted to through [`KeyAttributes::algorithm()`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Algorithm {
+    Hash(HashAlgorithm),
+    Aead(AeadAlgorithm),
+    Signature(SignatureAlgorithm),
+}
+
+impl Algorithm {
+    fn to_raw(self) -> psa_algorithm_t {
+        match self {
+            Algorithm::Hash(a) => a.to_raw(),
+            Algorithm::Aead(a) => a.to_raw(),
+            Algorithm::Signature(a) => a.to_raw(),
+        }
+    }
+}
+
+impl From<HashAlgorithm> for Algorithm {
+    fn from(a: HashAlgorithm) -> Self {
+        Algorithm::Hash(a)
+    }
+}
+
+impl From<AeadAlgorithm> for Algorithm {
+    fn from(a: AeadAlgorithm) -> Self {
+        Algorithm::Aead(a)
+    }
+}
+
+impl From<SignatureAlgorithm> for Algorithm {
+    fn from(a: SignatureAlgorithm) -> Self {
+        Algorithm::Signature(a)
+    }
+}
+
+/// Curve families for elliptic curve keys
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EccFamily {
+    /// NIST curves such as P-256
+    SecpR1,
+    /// Edwards curves such as Ed25519
+    TwistedEdwards,
+}
+
+impl EccFamily {
+    fn to_raw(self) -> u16 {
+        match self {
+            EccFamily::SecpR1 => 0x12,
+            EccFamily::TwistedEdwards => 0x42,
+        }
+    }
+}
+
+/// Types of keys
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyType {
+    RawData,
+    Hmac,
+    Aes,
+    ChaCha20,
+    EccKeyPair(EccFamily),
+    EccPublicKey(EccFamily),
+}
+
+impl KeyType {
+    fn to_raw(self) -> riot_sys::psa_key_type_t {
+        (match self {
+            KeyType::RawData => 0x1001,
+            KeyType::Hmac => 0x1100,
+            KeyType::Aes => 0x2400,
+            KeyType::ChaCha20 => 0x2004,
+            KeyType::EccKeyPair(family) => 0x7100 | family.to_raw(),
+            KeyType::EccPublicKey(family) => 0x4100 | family.to_raw(),
+        }) as _
+    }
+}
+
+/// Set of operations a key may be used for
+///
+/// Flags are combined using the `|` operator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Usage(riot_sys::psa_key_usage_t);
+
+impl Usage {
+    pub const EXPORT: Self = Self(0x0001);
+    pub const COPY: Self = Self(0x0002);
+    pub const ENCRYPT: Self = Self(0x0100);
+    pub const DECRYPT: Self = Self(0x0200);
+    pub const SIGN_MESSAGE: Self = Self(0x0400);
+    pub const VERIFY_MESSAGE: Self = Self(0x0800);
+    pub const SIGN_HASH: Self = Self(0x1000);
+    pub const VERIFY_HASH: Self = Self(0x2000);
+    pub const DERIVE: Self = Self(0x4000);
+}
+
+impl core::ops::BitOr for Usage {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// Properties of a key that is about to be created
+///
+/// This is a builder: it is created with the key's type and size, and all other properties
+/// default to a volatile key without permitted usage that is stored in the local key store.
+#[derive(Debug, Clone)]
+pub struct KeyAttributes {
+    key_type: KeyType,
+    bits: usize,
+    usage: Usage,
+    algorithm: Option<Algorithm>,
+    id: Option<psa_key_id_t>,
+    location: u32,
+}
+
+impl KeyAttributes {
+    /// Start describing a key of the given type and size
+    ///
+    /// When importing keys, the size can be given as 0 to take it from the imported data.
+    pub fn new(key_type: KeyType, bits: usize) -> Self {
+        Self {
+            key_type,
+            bits,
+            usage: Usage(0),
+            algorithm: None,
+            id: None,
+            location: 0,
+        }
+    }
+
+    /// Set the operations the key may be used for
+    pub fn usage(self, usage: Usage) -> Self {
+        Self { usage, ..self }
+    }
+
+    /// Set the algorithm the key may be used with
+    pub fn algorithm(self, algorithm: impl Into<Algorithm>) -> Self {
+        Self {
+            algorithm: Some(algorithm.into()),
+            ..self
+        }
+    }
+
+    /// Make the key persistent under the given ID
+    pub fn persistent(self, id: psa_key_id_t) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    /// Set the location where the key is stored
+    ///
+    /// The default (0) is the local key store; other values select secure elements as configured
+    /// in RIOT.
+    pub fn location(self, location: u32) -> Self {
+        Self { location, ..self }
+    }
+
+    fn to_c(&self) -> riot_sys::psa_key_attributes_t {
+        // unsafe: All-zero is PSA_KEY_ATTRIBUTES_INIT
+        let mut attributes: riot_sys::psa_key_attributes_t = unsafe { core::mem::zeroed() };
+        let persistence = if self.id.is_some() { 1 } else { 0 };
+        let a = crate::inline_cast_mut(&mut attributes);
+        // unsafe: C API as documented; the attributes are initialized
+        unsafe {
+            riot_sys::inline::psa_set_key_type(a, self.key_type.to_raw() as _);
+            riot_sys::inline::psa_set_key_bits(a, self.bits as _);
+            riot_sys::inline::psa_set_key_usage_flags(a, self.usage.0 as _);
+            if let Some(algorithm) = self.algorithm {
+                riot_sys::inline::psa_set_key_algorithm(a, algorithm.to_raw() as _);
+            }
+            if let Some(id) = self.id {
+                riot_sys::inline::psa_set_key_id(a, id as _);
+            }
+            // Set last, as setting the ID implicitly changes the lifetime
+            riot_sys::inline::psa_set_key_lifetime(a, ((self.location << 8) | persistence) as _);
+        }
+        attributes
+    }
+}
+
+/// Handle to a key in the PSA key store
+///
+/// Volatile keys are destroyed when the handle is dropped; persistent keys stay in the key store
+/// until they are explicitly [destroyed](Key::destroy).
+#[derive(Debug)]
+pub struct Key {
+    id: psa_key_id_t,
+    volatile: bool,
+}
+
+impl Key {
+    /// Import a key from its exported representation
+    #[doc(alias = "psa_import_key")]
+    pub fn import(attributes: &KeyAttributes, data: &[u8]) -> Result<Self, Error> {
+        let attributes_c = attributes.to_c();
+        let mut id = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_import_key(&attributes_c, data.as_ptr(), data.len() as _, &mut id)
+        })?;
+        Ok(Self {
+            id,
+            volatile: attributes.id.is_none(),
+        })
+    }
+
+    /// Generate a random key
+    #[doc(alias = "psa_generate_key")]
+    pub fn generate(attributes: &KeyAttributes) -> Result<Self, Error> {
+        let attributes_c = attributes.to_c();
+        let mut id = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe { riot_sys::psa_generate_key(&attributes_c, &mut id) })?;
+        Ok(Self {
+            id,
+            volatile: attributes.id.is_none(),
+        })
+    }
+
+    /// Access a persistent key that was created earlier
+    ///
+    /// No check is performed on whether the key exists; operations on a nonexisting key fail
+    /// with [`Error::InvalidHandle`].
+    pub fn from_persistent_id(id: psa_key_id_t) -> Self {
+        Self {
+            id,
+            volatile: false,
+        }
+    }
+
+    /// The key's identifier
+    pub fn id(&self) -> psa_key_id_t {
+        self.id
+    }
+
+    /// Remove the key from the key store
+    #[doc(alias = "psa_destroy_key")]
+    pub fn destroy(self) -> Result<(), Error> {
+        let id = self.id;
+        core::mem::forget(self);
+        // unsafe: C API as documented
+        Error::from_status(unsafe { riot_sys::psa_destroy_key(id) })
+    }
+
+    /// Write the key's material into a buffer, returning the written part
+    ///
+    /// This requires the key to have the [`Usage::EXPORT`] flag.
+    #[doc(alias = "psa_export_key")]
+    pub fn export<'b>(&self, buffer: &'b mut [u8]) -> Result<&'b [u8], Error> {
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_export_key(self.id, buffer.as_mut_ptr(), buffer.len() as _, &mut len)
+        })?;
+        Ok(&buffer[..len as usize])
+    }
+
+    /// Write the public part of an asymmetric key into a buffer, returning the written part
+    #[doc(alias = "psa_export_public_key")]
+    pub fn export_public<'b>(&self, buffer: &'b mut [u8]) -> Result<&'b [u8], Error> {
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_export_public_key(
+                self.id,
+                buffer.as_mut_ptr(),
+                buffer.len() as _,
+                &mut len,
+            )
+        })?;
+        Ok(&buffer[..len as usize])
+    }
+
+    /// Sign a message, writing the signature into a buffer and returning the written part
+    #[doc(alias = "psa_sign_message")]
+    pub fn sign_message<'b>(
+        &self,
+        algorithm: SignatureAlgorithm,
+        message: &[u8],
+        signature: &'b mut [u8],
+    ) -> Result<&'b [u8], Error> {
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_sign_message(
+                self.id,
+                algorithm.to_raw(),
+                message.as_ptr(),
+                message.len() as _,
+                signature.as_mut_ptr(),
+                signature.len() as _,
+                &mut len,
+            )
+        })?;
+        Ok(&signature[..len as usize])
+    }
+
+    /// Verify the signature of a message
+    ///
+    /// A signature that does not match produces [`Error::InvalidSignature`].
+    #[doc(alias = "psa_verify_message")]
+    pub fn verify_message(
+        &self,
+        algorithm: SignatureAlgorithm,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_verify_message(
+                self.id,
+                algorithm.to_raw(),
+                message.as_ptr(),
+                message.len() as _,
+                signature.as_ptr(),
+                signature.len() as _,
+            )
+        })
+    }
+
+    /// Sign a precomputed hash, writing the signature into a buffer and returning the written part
+    #[doc(alias = "psa_sign_hash")]
+    pub fn sign_hash<'b>(
+        &self,
+        algorithm: SignatureAlgorithm,
+        hash: &[u8],
+        signature: &'b mut [u8],
+    ) -> Result<&'b [u8], Error> {
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_sign_hash(
+                self.id,
+                algorithm.to_raw(),
+                hash.as_ptr(),
+                hash.len() as _,
+                signature.as_mut_ptr(),
+                signature.len() as _,
+                &mut len,
+            )
+        })?;
+        Ok(&signature[..len as usize])
+    }
+
+    /// Verify the signature of a precomputed hash
+    ///
+    /// A signature that does not match produces [`Error::InvalidSignature`].
+    #[doc(alias = "psa_verify_hash")]
+    pub fn verify_hash(
+        &self,
+        algorithm: SignatureAlgorithm,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_verify_hash(
+                self.id,
+                algorithm.to_raw(),
+                hash.as_ptr(),
+                hash.len() as _,
+                signature.as_ptr(),
+                signature.len() as _,
+            )
+        })
+    }
+
+    /// Encrypt and authenticate a message, writing ciphertext and tag into a buffer and returning
+    /// the written part
+    #[doc(alias = "psa_aead_encrypt")]
+    pub fn aead_encrypt<'b>(
+        &self,
+        algorithm: AeadAlgorithm,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+        ciphertext: &'b mut [u8],
+    ) -> Result<&'b [u8], Error> {
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_aead_encrypt(
+                self.id,
+                algorithm.to_raw(),
+                nonce.as_ptr(),
+                nonce.len() as _,
+                associated_data.as_ptr(),
+                associated_data.len() as _,
+                plaintext.as_ptr(),
+                plaintext.len() as _,
+                ciphertext.as_mut_ptr(),
+                ciphertext.len() as _,
+                &mut len,
+            )
+        })?;
+        Ok(&ciphertext[..len as usize])
+    }
+
+    /// Check and decrypt a message (ciphertext followed by the tag), writing the plaintext into a
+    /// buffer and returning the written part
+    ///
+    /// A message that is not authentic produces [`Error::InvalidSignature`].
+    #[doc(alias = "psa_aead_decrypt")]
+    pub fn aead_decrypt<'b>(
+        &self,
+        algorithm: AeadAlgorithm,
+        nonce: &[u8],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        plaintext: &'b mut [u8],
+    ) -> Result<&'b [u8], Error> {
+        let mut len = 0;
+        // unsafe: C API as documented
+        Error::from_status(unsafe {
+            riot_sys::psa_aead_decrypt(
+                self.id,
+                algorithm.to_raw(),
+                nonce.as_ptr(),
+                nonce.len() as _,
+                associated_data.as_ptr(),
+                associated_data.len() as _,
+                ciphertext.as_ptr(),
+                ciphertext.len() as _,
+                plaintext.as_mut_ptr(),
+                plaintext.len() as _,
+                &mut len,
+            )
+        })?;
+        Ok(&plaintext[..len as usize])
+    }
+}
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        if self.volatile {
+            // unsafe: C API as documented. Errors can not be reported, and leave nothing to clean
+            // up.
+            unsafe { riot_sys::psa_destroy_key(self.id) };
+        }
+    }
+}
+
+/// Compute the hash of some data, writing it into a buffer and returning the written part
+#[doc(alias = "psa_hash_compute")]
+pub fn hash_compute<'b>(
+    algorithm: HashAlgorithm,
+    input: &[u8],
+    hash: &'b mut [u8],
+) -> Result<&'b [u8], Error> {
+    let mut len = 0;
+    // unsafe: C API as documented
+    Error::from_status(unsafe {
+        riot_sys::psa_hash_compute(
+            algorithm.to_raw(),
+            input.as_ptr(),
+            input.len() as _,
+            hash.as_mut_ptr(),
+            hash.len() as _,
+            &mut len,
+        )
+    })?;
+    Ok(&hash[..len as usize])
+}
+
+/// Check that some data has the given hash
+///
+/// The comparison is performed in constant time; a mismatch produces
+/// [`Error::InvalidSignature`].
+#[doc(alias = "psa_hash_compare")]
+pub fn hash_compare(algorithm: HashAlgorithm, input: &[u8], hash: &[u8]) -> Result<(), Error> {
+    // unsafe: C API as documented
+    Error::from_status(unsafe {
+        riot_sys::psa_hash_compare(
+            algorithm.to_raw(),
+            input.as_ptr(),
+            input.len() as _,
+            hash.as_ptr(),
+            hash.len() as _,
+        )
+    })
+}
@@ -0,0 +1,163 @@
+//! Access to the [riotboot] slots, and writing firmware images into them
+//!
+//! The main component of this module is [FlashWrite], which wraps [riotboot_flashwrite]: It allows
+//! an application to stream a firmware image it receives over any transport into the currently
+//! inactive slot. Only when the image is complete, [`FlashWrite::finish()`] writes the leading
+//! magic number that makes the bootloader consider the slot at all -- so an interrupted transfer
+//! never leaves a half-written image that is eligible for booting.
+//!
+//! [riotboot]: https://doc.riot-os.org/group__sys__riotboot__slot.html
+//! [riotboot_flashwrite]: https://doc.riot-os.org/group__sys__riotboot__flashwrite.html
+
+use crate::error::{NegativeErrorExt, NumericError};
+
+/// Number of the slot the running firmware was booted from
+#[doc(alias = "riotboot_slot_current")]
+pub fn current_slot() -> u32 {
+    // unsafe: C API makes no requirements
+    (unsafe { riot_sys::riotboot_slot_current() }) as _
+}
+
+/// Number of the slot that is not running, and thus the typical target of an update
+#[doc(alias = "riotboot_slot_other")]
+pub fn other_slot() -> u32 {
+    // unsafe: C API makes no requirements
+    (unsafe { riot_sys::inline::riotboot_slot_other() }) as _
+}
+
+/// Maximum size of an image (including its header) in the given slot
+#[doc(alias = "riotboot_slot_size")]
+pub fn slot_size(slot: u32) -> usize {
+    // unsafe: C API makes no requirements on the argument (out-of-range slots produce garbage,
+    // but do not access memory)
+    (unsafe { riot_sys::inline::riotboot_slot_size(slot as _) }) as _
+}
+
+/// Mark the given slot as not bootable by clearing its magic number
+///
+/// This must not be called on the running slot; doing that would (on the next boot) leave the
+/// device without a bootable image if the other slot is not valid either.
+///
+/// Note that riotboot_flashwrite only reports failure as a generic `-1`, which is why the error
+/// is not more specific.
+#[cfg(riot_module_riotboot_flashwrite)]
+#[doc(alias = "riotboot_flashwrite_invalidate")]
+pub fn invalidate(slot: u32) -> Result<(), NumericError> {
+    // unsafe: C API checks the slot number
+    unsafe { riot_sys::riotboot_flashwrite_invalidate(slot as _) }.negative_to_error()?;
+    Ok(())
+}
+
+/// A firmware update in progress
+///
+/// Data is written to the target slot in the sequence in which it is passed to
+/// [`.put_bytes()`](FlashWrite::put_bytes), and the slot becomes bootable once
+/// [`.finish()`](FlashWrite::finish) is called. Dropping this without finishing leaves the slot
+/// in an invalid (and thus harmless) state.
+///
+/// Note that this contains a buffer of a full flash page; on some platforms, that is large enough
+/// that it is prudent to keep the writer in a static rather than on a thread's stack.
+#[cfg(riot_module_riotboot_flashwrite)]
+pub struct FlashWrite {
+    state: riot_sys::riotboot_flashwrite_t,
+}
+
+#[cfg(riot_module_riotboot_flashwrite)]
+impl FlashWrite {
+    /// Start writing an image into the slot that is not currently running
+    ///
+    /// This erases the first flash page of the slot, invalidating any image that was there.
+    #[doc(alias = "riotboot_flashwrite_init")]
+    pub fn new() -> Result<Self, NumericError> {
+        Self::new_for_slot(other_slot())
+    }
+
+    /// Start writing an image into a particular slot
+    ///
+    /// Like [`.new()`](Self::new), but with an explicit slot number. Users must take care not to
+    /// pass in the [`current_slot()`].
+    pub fn new_for_slot(slot: u32) -> Result<Self, NumericError> {
+        // unsafe: The struct is plain data and fully initialized by the init function before it
+        // is used.
+        let mut state: riot_sys::riotboot_flashwrite_t = unsafe { core::mem::zeroed() };
+        // unsafe: C API as documented. The inline init function is not used because it would
+        // require casting the large state, and all it does is pass in the length of the magic
+        // number.
+        unsafe {
+            riot_sys::riotboot_flashwrite_init_raw(
+                &mut state,
+                slot as _,
+                riot_sys::RIOTBOOT_FLASHWRITE_SKIPLEN as _,
+            )
+        }
+        .negative_to_error()?;
+        Ok(Self { state })
+    }
+
+    /// Append data to the image being written
+    ///
+    /// The first bytes of the image (which would contain the magic number) are not written yet, as
+    /// that only happens in [`.finish()`](Self::finish).
+    ///
+    /// `more` indicates whether more data is to be expected; when false, any buffered data is
+    /// flushed to flash immediately.
+    #[doc(alias = "riotboot_flashwrite_putbytes")]
+    pub fn put_bytes(&mut self, data: &[u8], more: bool) -> Result<(), NumericError> {
+        // unsafe: C API as documented; the state was initialized in the constructor
+        unsafe {
+            riot_sys::riotboot_flashwrite_putbytes(
+                &mut self.state,
+                data.as_ptr(),
+                data.len() as _,
+                more,
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Write out any data that is buffered in the writer but not yet flashed
+    #[doc(alias = "riotboot_flashwrite_flush")]
+    pub fn flush(&mut self) -> Result<(), NumericError> {
+        // unsafe: C API as documented; the state was initialized in the constructor
+        unsafe { riot_sys::riotboot_flashwrite_flush(&mut self.state) }.negative_to_error()?;
+        Ok(())
+    }
+
+    /// Number of bytes of the image that were received so far
+    ///
+    /// This includes the magic number that is only written in [`.finish()`](Self::finish), and
+    /// can thus directly be compared to the length of the image that is being transferred.
+    pub fn offset(&self) -> usize {
+        self.state.offset as _
+    }
+
+    /// Size of the slot that is being written to
+    #[doc(alias = "riotboot_flashwrite_slotsize")]
+    pub fn slot_size(&self) -> usize {
+        // unsafe: C API as documented; the state was initialized in the constructor
+        (unsafe { riot_sys::riotboot_flashwrite_slotsize(&self.state) }) as _
+    }
+
+    /// Flush all remaining data, and make the slot bootable by writing the image's magic number.
+    ///
+    /// Whether the new image is then actually used depends on the version number in its header,
+    /// and is decided by the bootloader at the next reboot.
+    #[doc(alias = "riotboot_flashwrite_finish")]
+    pub fn finish(mut self) -> Result<(), NumericError> {
+        const MAGIC: &[u8] = b"RIOT";
+        const {
+            assert!(MAGIC.len() == riot_sys::RIOTBOOT_FLASHWRITE_SKIPLEN as usize);
+        }
+        // unsafe: C API as documented; the state was initialized in the constructor
+        unsafe {
+            riot_sys::riotboot_flashwrite_finish_raw(
+                &mut self.state,
+                MAGIC.as_ptr(),
+                MAGIC.len() as _,
+            )
+        }
+        .negative_to_error()?;
+        Ok(())
+    }
+}
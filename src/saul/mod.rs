@@ -7,6 +7,8 @@
 //! * [`RegistryEntry`] with its various constructors finds sensors or actuators in SAUL,
 //!   and allows interacting with them.
 //!
+//! * Devices that support it can notify about changes; see the [observer] submodule.
+//!
 //! In mapping SAUL semantics to Rust, some parts are not aligned in full:
 //!
 //! * The [`Phydat`] type used here *always* has a length -- as opposed to `phydat_t` which contains
@@ -21,6 +23,8 @@ use crate::error;
 use crate::helpers::PointerToCStr;
 use error::NegativeErrorExt;
 
+#[cfg(riot_module_saul_observer)]
+pub mod observer;
 pub mod registration;
 
 /// A discovered SAUL registry entry
@@ -37,6 +41,12 @@ pub type AllRegistryEntries = core::iter::MapWhile<
 >;
 
 impl RegistryEntry {
+    /// Pointer to the underlying registration, for use with other C APIs that process SAUL
+    /// devices
+    pub(crate) fn as_ptr(&self) -> *mut riot_sys::saul_reg {
+        self.0
+    }
+
     /// Find a registry entry by its index
     ///
     /// Wrapper around `saul_reg_find_nth`.
@@ -58,12 +68,41 @@ impl RegistryEntry {
         (0..).map(Self::nth as _).map_while(|p| p)
     }
 
+    /// Find the first registry entry of the given class
+    ///
+    /// Wildcard classes ([`Class::Sensor(None)`](Class::Sensor) or
+    /// [`Class::Actuator(None)`](Class::Actuator)) are passed to SAUL as `SAUL_SENSE_ANY` /
+    /// `SAUL_ACT_ANY`, which only match devices that registered as such; use
+    /// [`.all_of_class()`](Self::all_of_class) to find any device of a category.
+    #[doc(alias = "saul_reg_find_type")]
+    pub fn find_type(class: Class) -> Option<Self> {
+        // unsafe: C API makes no requirements, and as in nth(), a non-null result is static.
+        (unsafe { riot_sys::saul_reg_find_type(class.to_c()).as_mut() }).map(|r| RegistryEntry(r))
+    }
+
+    /// Find the first registry entry with the given name
+    #[doc(alias = "saul_reg_find_name")]
+    pub fn find_name(name: &core::ffi::CStr) -> Option<Self> {
+        // unsafe: C API only reads the string, and as in nth(), a non-null result is static.
+        (unsafe { riot_sys::saul_reg_find_name(name.as_ptr() as _).as_mut() })
+            .map(|r| RegistryEntry(r))
+    }
+
+    /// All registered entries whose class matches the given class
+    ///
+    /// See [`Class::matches()`] for how wildcards are applied.
+    pub fn all_of_class(class: Class) -> impl Iterator<Item = Self> {
+        Self::all().filter(move |e| e.type_().is_some_and(|t| class.matches(t)))
+    }
+
+    /// Class of the device, or None if the device's class is not known to the wrappers
     pub fn type_(&self) -> Option<Class> {
         // unsafe: Registrations are stable
         let type_ = unsafe { (*(*self.0).driver).type_ };
         Class::from_c(type_)
     }
 
+    /// Name of the device, if it has one and that is valid UTF-8
     pub fn name(&self) -> Option<&'static str> {
         // unsafe: Registrations are stable, and point to null-terminated strings or are NULL.
         unsafe { Some((*self.0).name.to_lifetimed_cstr()?.to_str().ok()?) }
@@ -99,6 +138,15 @@ impl RegistryEntry {
     }
 }
 
+impl core::fmt::Debug for RegistryEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.debug_struct("RegistryEntry")
+            .field("name", &self.name())
+            .field("class", &self.type_())
+            .finish()
+    }
+}
+
 /// A wrapper around phydat_t that keeps the values and the number of valid values in one place.
 #[derive(Default, Copy, Clone)]
 pub struct Phydat {
@@ -161,6 +209,186 @@ impl Phydat {
     pub fn scale(&self) -> i8 {
         self.values.scale
     }
+
+    /// Create a phydat value from floating point numbers
+    ///
+    /// The values are rounded to the given scale (eg. with a scale of -2, 21.456 becomes 2146
+    /// ×10^-2), and then fit into phydat's value range as in [`fit()`](Self::fit), which may
+    /// reduce precision further.
+    ///
+    /// # Panics
+    ///
+    /// like `new()`
+    pub fn from_f32(data: &[f32], unit: Option<Unit>, scale: i8) -> Self {
+        let factor = pow10_f32(-i32::from(scale));
+        let mut scaled = [0i32; riot_sys::PHYDAT_DIM as _];
+        for (s, d) in scaled.iter_mut().zip(data.iter()) {
+            let v = d * factor;
+            // `as` saturates, and core has no round()
+            *s = if v >= 0.0 { v + 0.5 } else { v - 0.5 } as i32;
+        }
+        Self::fit(&scaled[..data.len()], unit, scale)
+    }
+
+    /// The values, converted to floating point numbers with the scale applied
+    pub fn value_f32(&self) -> impl Iterator<Item = f32> + '_ {
+        let factor = pow10_f32(self.scale().into());
+        self.value().iter().map(move |v| f32::from(*v) * factor)
+    }
+
+    /// The values, expressed as integer multiples of 10^`scale`
+    ///
+    /// This is useful to obtain fixed-point values, eg. temperatures in milli-degrees through
+    /// `.value_scaled(-3)`. Values are truncated towards zero if the requested scale is coarser
+    /// than the data's scale; a value is None if it can not be expressed in an i32.
+    pub fn value_scaled(&self, scale: i8) -> impl Iterator<Item = Option<i32>> + '_ {
+        let diff = i32::from(self.scale()) - i32::from(scale);
+        self.value().iter().map(move |v| {
+            let v = i32::from(*v);
+            if diff >= 0 {
+                10i32
+                    .checked_pow(diff as u32)
+                    .and_then(|f| v.checked_mul(f))
+                    // A 0 value can be scaled arbitrarily
+                    .or((v == 0).then_some(0))
+            } else {
+                // Small values become 0 when dividing by a factor that exceeds the i32 range
+                Some(10i32.checked_pow(diff.unsigned_abs()).map_or(0, |f| v / f))
+            }
+        })
+    }
+
+    /// Start building a phydat value step by step
+    ///
+    /// Unlike [`new()`](Self::new), building through this does not panic on excessive data, but
+    /// reports errors when [`.build()`](PhydatBuilder::build) is called. This is convenient when
+    /// writing to actuators:
+    ///
+    /// ```
+    /// # #![no_std]
+    /// # #![feature(start)]
+    /// # #[start]
+    /// # fn main(_argc: isize, _argv: *const *const u8) -> isize {
+    /// # use riot_wrappers::saul::*;
+    /// let led = RegistryEntry::find_type(Class::Actuator(Some(ActuatorClass::LedRgb))).unwrap();
+    /// let purple = Phydat::builder()
+    ///     .value(255)
+    ///     .value(0)
+    ///     .value(255)
+    ///     .unit(Unit::None)
+    ///     .build()
+    ///     .unwrap();
+    /// led.write(purple).unwrap();
+    /// # 0
+    /// # }
+    /// ```
+    pub fn builder() -> PhydatBuilder {
+        PhydatBuilder {
+            values: Default::default(),
+            length: 0,
+            unit: None,
+            scale: 0,
+            error: None,
+        }
+    }
+}
+
+/// 10 to the power of `exp`, as core does not provide powi
+fn pow10_f32(exp: i32) -> f32 {
+    let mut result = 1.0f32;
+    let factor = if exp >= 0 { 10.0 } else { 0.1 };
+    for _ in 0..exp.unsigned_abs() {
+        result *= factor;
+    }
+    result
+}
+
+/// Error produced by [`PhydatBuilder::build()`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PhydatBuildError {
+    /// No values were given; SAUL devices always take at least one.
+    Empty,
+    /// More than PHYDAT_DIM (3) values were given.
+    TooManyValues,
+    /// A scale was given that exceeds phydat's exponent range (10^-128 to 10^127).
+    ScaleOutOfRange,
+}
+
+/// Builder for a [Phydat], created through [`Phydat::builder()`]
+///
+/// Errors are not reported on the individual steps, but only when building.
+#[derive(Debug, Copy, Clone)]
+pub struct PhydatBuilder {
+    values: [i16; riot_sys::PHYDAT_DIM as _],
+    length: u8,
+    unit: Option<Unit>,
+    scale: i8,
+    error: Option<PhydatBuildError>,
+}
+
+impl PhydatBuilder {
+    fn set_error(&mut self, error: PhydatBuildError) {
+        self.error.get_or_insert(error);
+    }
+
+    /// Append a value
+    pub fn value(mut self, value: i16) -> Self {
+        match self.values.get_mut(self.length as usize) {
+            Some(slot) => {
+                *slot = value;
+                self.length += 1;
+            }
+            None => self.set_error(PhydatBuildError::TooManyValues),
+        }
+        self
+    }
+
+    /// Append values from a slice
+    pub fn values(self, values: &[i16]) -> Self {
+        values.iter().fold(self, |s, v| s.value(*v))
+    }
+
+    /// Set the unit of all values
+    ///
+    /// If not set, the built value will have an undefined unit.
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Set the decimal exponent with which all values are to be multiplied
+    pub fn scale(mut self, scale: i8) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the decimal exponent, given as a wider integer
+    ///
+    /// This is a convenience for scales that are computed; values outside the range of phydat's
+    /// scale produce a [`PhydatBuildError::ScaleOutOfRange`] error at build time.
+    pub fn scale_checked(mut self, scale: i32) -> Self {
+        match scale.try_into() {
+            Ok(scale) => self.scale = scale,
+            Err(_) => self.set_error(PhydatBuildError::ScaleOutOfRange),
+        }
+        self
+    }
+
+    /// Build the value
+    pub fn build(self) -> Result<Phydat, PhydatBuildError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        if self.length == 0 {
+            return Err(PhydatBuildError::Empty);
+        }
+        Ok(Phydat::new(
+            &self.values[..self.length as usize],
+            self.unit,
+            self.scale,
+        ))
+    }
 }
 
 impl core::fmt::Debug for Phydat {
@@ -199,7 +427,7 @@ impl core::fmt::Display for Phydat {
 /// Both for the class in general and for its details, Option is used to represent undefined /
 /// unknown values, which are used as a wildcard in queries and as an indicator of unknown /
 /// unsupported types in introspection.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Class {
     Actuator(Option<ActuatorClass>),
     Sensor(Option<SensorClass>),
@@ -297,6 +525,18 @@ impl Class {
         }) as _
     }
 
+    /// Check whether `other` falls into this class
+    ///
+    /// A class without details (eg. `Class::Sensor(None)`) matches any class of the same category,
+    /// whereas a detailed class only matches itself.
+    pub fn matches(self, other: Class) -> bool {
+        match (self, other) {
+            (Class::Actuator(None), Class::Actuator(_)) => true,
+            (Class::Sensor(None), Class::Sensor(_)) => true,
+            (a, b) => a == b,
+        }
+    }
+
     /// Human-readable name of the class
     pub fn name(self) -> Option<&'static str> {
         unsafe { riot_sys::saul_class_to_str(self.to_c()).to_lifetimed_cstr()? }
@@ -305,7 +545,7 @@ impl Class {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 /// Classes of actuators; typically used as details on a [Class]
 pub enum ActuatorClass {
@@ -316,7 +556,7 @@ pub enum ActuatorClass {
     Dimmer,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 /// Classes of sensors; typically used as details on a [Class]
 pub enum SensorClass {
@@ -350,7 +590,7 @@ pub enum SensorClass {
     Size,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 /// Unit of measurement required to interpret numeric values in a [Phydat] exchanged with a SAUL
 /// device
 #[non_exhaustive]
@@ -393,6 +633,17 @@ pub enum Unit {
     Cpm3,
 }
 
+impl core::fmt::Display for Unit {
+    /// Writes the unit's name as produced by [`.name_owned()`](Unit::name_owned), or its Debug
+    /// representation if RIOT has no name for it.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self.name_owned::<16>() {
+            Some(s) => f.write_str(&s),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
 impl Unit {
     fn from_c(input: u8) -> Option<Self> {
         match input as _ {
@@ -434,7 +685,7 @@ impl Unit {
         }
     }
 
-    fn to_c(input: Option<Self>) -> u8 {
+    pub(crate) fn to_c(input: Option<Self>) -> u8 {
         (match input {
             Some(Unit::None) => riot_sys::UNIT_NONE,
             Some(Unit::TempC) => riot_sys::UNIT_TEMP_C,
@@ -0,0 +1,82 @@
+//! Notifications about changes of SAUL devices through the [saul_observer] module
+//!
+//! Devices that support it (eg. buttons, or sensors with threshold interrupts) notify their
+//! observers when their state changes. An [Observer] is registered with a device through
+//! [`register_static()`], after which its [`Observer::notified()`] method is called on every
+//! change.
+//!
+//! As notifications are typically sent from an interrupt context, the observer should do minimal
+//! work; a typical pattern is to post an event to a queue, or to set a thread flag, and to
+//! [read](super::RegistryEntry::read) the device from a thread when that is processed.
+//!
+//! [saul_observer]: https://doc.riot-os.org/group__drivers__saul__observer.html
+
+use core::mem::MaybeUninit;
+
+use super::RegistryEntry;
+use crate::error::NegativeErrorExt;
+
+/// Storage for everything that is needed to serve a registered [Observer].
+///
+/// It can be created through [Default::default()]. It is used as a `&'static mut` in
+/// [`register_static`], which is most easily obtained through the `static_cell` crate.
+///
+/// ## Internal invariants
+///
+/// When created, all fields are uninitialized; registration places data in there, and the
+/// registration holds on to the slot forever.
+pub struct Slot<O>(MaybeUninit<riot_sys::saul_observer_t>, MaybeUninit<O>);
+
+impl<O> Default for Slot<O> {
+    fn default() -> Self {
+        Self(MaybeUninit::uninit(), MaybeUninit::uninit())
+    }
+}
+
+/// Callback trait for observing SAUL devices
+///
+/// This is expressed as a trait rather than a FnMut because the observer generally needs to be
+/// statically allocated, and a closure (which can not be named) can not.
+pub trait Observer: Send + Sync {
+    /// The observed device indicated a change.
+    ///
+    /// This is usually called in an interrupt context, and takes a `&self` because the same
+    /// observer may be registered with multiple devices.
+    fn notified(&self, entry: &RegistryEntry);
+}
+
+/// Set up an `observer` to be notified whenever the device behind `entry` changes.
+///
+/// This requires a statically allocated [`Slot`], as can conveniently be created by the caller
+/// through the `static_cell` crate.
+///
+/// This returns an error (typically `ENOTSUP`) if the device does not support observation.
+#[doc(alias = "saul_observer_add")]
+pub fn register_static<O: Observer>(
+    slot: &'static mut Slot<O>,
+    entry: &RegistryEntry,
+    observer: O,
+) -> Result<(), crate::error::NumericError> {
+    unsafe extern "C" fn c_callback<O: Observer>(
+        dev: *mut riot_sys::saul_reg_t,
+        arg: *mut riot_sys::libc::c_void,
+    ) {
+        // unsafe: Constructed through the opposite cast, and API promises to deliver that value
+        let observer = unsafe { &*(arg as *const O) };
+        // The registry entry is static, as registrations can't be removed.
+        observer.notified(&RegistryEntry(dev))
+    }
+
+    let observer = slot.1.write(observer);
+
+    // unsafe: All-zero is the initial state for the list node, and all other fields are set
+    // explicitly.
+    let mut c_observer: riot_sys::saul_observer_t = unsafe { core::mem::zeroed() };
+    c_observer.callback = Some(c_callback::<O>);
+    c_observer.arg = observer as *mut O as *mut riot_sys::libc::c_void;
+    let c_observer = slot.0.write(c_observer);
+
+    // unsafe: The observer is static, and the entry is a valid registration
+    unsafe { riot_sys::saul_observer_add(entry.0, c_observer) }.negative_to_error()?;
+    Ok(())
+}
@@ -59,7 +59,7 @@ pub trait Drivable: Sized {
     /// return how many of the entries it actually used.
     fn write(self, _data: &Phydat) -> Result<u8, Error> {
         // See also comment in read()
-        unimplemented!("Sensor writing not implemented; HAS_READ should not have been set.")
+        unimplemented!("Sensor writing not implemented; HAS_WRITE should not have been set.")
     }
 }
 
@@ -79,16 +79,6 @@ where
     _phantom: core::marker::PhantomData<(DEV, DRIV)>,
 }
 
-// While the old supported RIOT version has a `saul_notsup` and the new has
-// `saul_{read,write}_notsup`, it's easier to just implement our own. After the 2022.07 release
-// they can go away again, and users go with riot_sys::saul_[...]_notsup
-extern "C" fn saul_read_notsup(_dev: *const libc::c_void, _dat: *mut riot_sys::phydat_t) -> i32 {
-    -(riot_sys::ENOTSUP as i32)
-}
-extern "C" fn saul_write_notsup(_dev: *const libc::c_void, _dat: *const riot_sys::phydat_t) -> i32 {
-    -(riot_sys::ENOTSUP as i32)
-}
-
 impl<DEV, DRIV> Driver<DEV, DRIV>
 where
     DEV: Sized + Sync + 'static,
@@ -101,12 +91,12 @@ where
                 read: if DRIV::HAS_READ {
                     Some(Self::read_raw)
                 } else {
-                    Some(saul_read_notsup)
+                    Some(riot_sys::saul_read_notsup)
                 },
                 write: if DRIV::HAS_WRITE {
                     Some(Self::write_raw)
                 } else {
-                    Some(saul_write_notsup)
+                    Some(riot_sys::saul_write_notsup)
                 },
                 type_: DRIV::CLASS.to_c(),
             },
@@ -157,6 +147,10 @@ where
 {
 }
 
+/// An entry in the SAUL registry that makes a Rust device available
+///
+/// Once registered, the device can be found through [`RegistryEntry`](super::RegistryEntry) just
+/// like any device registered from C, and is also accessible through the `saul` shell command.
 pub struct Registration<DEV, DRIV = &'static DEV>
 where
     DEV: Sized + Sync + 'static,
@@ -187,7 +181,10 @@ where
     // upgrade -- not for lifetime reasons, but because for `register_static` all is already
     // static anyway, and `build_with` can just as well be called with the components, claim their
     // lifetime first and then go through here.
-    pub fn new(
+    //
+    // Being const, this allows placing the registration in a static, from where it can be obtained
+    // for registration through [`Mutex::try_leak()`](crate::mutex::Mutex::try_leak).
+    pub const fn new(
         driver: &'static Driver<DEV, DRIV>,
         device: &'static DEV,
         name: Option<&'static CStr>,
@@ -196,7 +193,10 @@ where
             reg: riot_sys::saul_reg_t {
                 next: core::ptr::null_mut(),
                 dev: device as *const _ as *mut _,
-                name: name.map(|n| n.as_ptr() as _).unwrap_or(core::ptr::null()),
+                name: match name {
+                    Some(n) => n.as_ptr() as _,
+                    None => core::ptr::null(),
+                },
                 driver: &driver.driver as *const _,
             },
             _phantom: core::marker::PhantomData,
@@ -0,0 +1,222 @@
+//! Observation of context switches through the [scheduler's
+//! callback](https://doc.riot-os.org/group__core__sched.html) (`sched_cb` module)
+//!
+//! An [Observer] that is [registered](register) is told about every context switch the scheduler
+//! performs, which is the basis for tracing, CPU load estimation or custom profilers. A ready-made
+//! observer is the [TraceRecorder], which keeps the latest switches in a ring buffer:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::sched_cb::{self, TraceRecorder};
+//! static TRACE: TraceRecorder<32> = TraceRecorder::new();
+//!
+//! # fn f() {
+//! sched_cb::register(&TRACE);
+//! // ... later ...
+//! TRACE.drain(|record| {
+//!     // ... print record.previous and record.next ...
+//! });
+//! # }
+//! ```
+//!
+//! ## Callback contract
+//!
+//! The observer is called from within the scheduler, with interrupts disabled, and often in an
+//! interrupt context. It must thus return quickly, and must not block, allocate, print, or call
+//! into the scheduler (eg. by locking mutexes, sending messages or waking threads). The
+//! [CriticalSection] passed in attests that interrupts are off, and can be used to access data
+//! shared with other code through critical-section based cells.
+//!
+//! There is only one scheduler callback. When the `schedstatistics` module is active, its
+//! statistics keep being updated before the registered observer is called.
+
+use core::cell::UnsafeCell;
+
+use crate::interrupt::CriticalSection;
+use crate::thread::KernelPID;
+
+/// Callback trait for observing context switches
+///
+/// This is expressed as a trait on a static rather than a FnMut for the same reasons as in
+/// [SAUL observers](crate::saul::observer::Observer).
+pub trait Observer: Send + Sync {
+    /// The scheduler switches from `previous` to `next`
+    ///
+    /// Either is None when no thread is involved, eg. when the previous thread has just exited, or
+    /// when no thread is runnable. See the [module level documentation](self) for what may be done
+    /// in here.
+    fn switched(&self, previous: Option<KernelPID>, next: Option<KernelPID>, cs: &CriticalSection);
+}
+
+struct Registration(UnsafeCell<Option<&'static dyn Observer>>);
+
+// unsafe: The cell is only accessed with interrupts disabled, and thus never concurrently.
+unsafe impl Sync for Registration {}
+
+static REGISTRATION: Registration = Registration(UnsafeCell::new(None));
+
+unsafe extern "C" fn callback(previous: riot_sys::kernel_pid_t, next: riot_sys::kernel_pid_t) {
+    #[cfg(riot_module_schedstatistics)]
+    {
+        // unsafe: Forwarding the callback as RIOT would do without this module's registration
+        unsafe { riot_sys::sched_statistics_cb(previous, next) };
+    }
+
+    // unsafe: The scheduler runs this with interrupts disabled.
+    let cs = unsafe { CriticalSection::new() };
+    // unsafe: Accessed with interrupts disabled
+    if let Some(observer) = unsafe { *REGISTRATION.0.get() } {
+        observer.switched(KernelPID::new(previous), KernelPID::new(next), &cs);
+    }
+}
+
+/// Have `observer` called on every context switch
+///
+/// This replaces any previously registered observer.
+#[doc(alias = "sched_register_cb")]
+pub fn register(observer: &'static dyn Observer) {
+    crate::interrupt::free(|_| {
+        // unsafe: Accessed with interrupts disabled
+        unsafe { *REGISTRATION.0.get() = Some(observer) };
+        // unsafe: C API as documented
+        unsafe { riot_sys::sched_register_cb(Some(callback)) };
+    });
+}
+
+/// Stop calling the registered observer
+pub fn unregister() {
+    crate::interrupt::free(|_| {
+        // unsafe: Accessed with interrupts disabled. The C callback stays registered, so that
+        // schedstatistics (if active) keeps working.
+        unsafe { *REGISTRATION.0.get() = None };
+    });
+}
+
+/// A context switch recorded by a [TraceRecorder]
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct Record {
+    pub previous: Option<KernelPID>,
+    pub next: Option<KernelPID>,
+    /// Time of the switch in microseconds (wrapping), as read from `ZTIMER_USEC`
+    ///
+    /// This is only meaningful if the clock is running, which is the case on most boards, and
+    /// otherwise ensured by acquiring the clock.
+    #[cfg(riot_module_ztimer_usec)]
+    pub timestamp: u32,
+}
+
+struct Ring<const N: usize> {
+    records: [Option<Record>; N],
+    // Index of the oldest record
+    start: usize,
+    len: usize,
+    overwritten: usize,
+}
+
+/// An [Observer] that keeps the most recent `N` context switches in a ring buffer
+///
+/// Records are taken out through [`.drain()`](Self::drain); when more switches happen in between,
+/// the oldest records are overwritten.
+pub struct TraceRecorder<const N: usize> {
+    ring: UnsafeCell<Ring<N>>,
+}
+
+// unsafe: The ring is only accessed with interrupts disabled, and thus never concurrently.
+unsafe impl<const N: usize> Sync for TraceRecorder<N> {}
+
+impl<const N: usize> TraceRecorder<N> {
+    pub const fn new() -> Self {
+        Self {
+            ring: UnsafeCell::new(Ring {
+                records: [None; N],
+                start: 0,
+                len: 0,
+                overwritten: 0,
+            }),
+        }
+    }
+
+    /// Take out the oldest record, if there is any
+    pub fn pop(&self) -> Option<Record> {
+        crate::interrupt::free(|_| {
+            // unsafe: Accessed with interrupts disabled
+            let ring = unsafe { &mut *self.ring.get() };
+            if ring.len == 0 {
+                return None;
+            }
+            let record = ring.records[ring.start].take();
+            ring.start = (ring.start + 1) % N;
+            ring.len -= 1;
+            record
+        })
+    }
+
+    /// Pass all records to `f` in the order they were taken, removing them from the buffer
+    ///
+    /// Records are taken out one at a time, so interrupts are only disabled briefly, and `f` is
+    /// called outside of any critical section (and may thus, eg., print them). Only the records that
+    /// are present when draining starts are passed on; those of context switches that happen while
+    /// draining (eg. caused by `f`) stay for the next call.
+    ///
+    /// Returns the number of records that were overwritten before they could be drained since the
+    /// last call.
+    pub fn drain(&self, mut f: impl FnMut(Record)) -> usize {
+        let present = crate::interrupt::free(|_| {
+            // unsafe: Accessed with interrupts disabled
+            unsafe { (*self.ring.get()).len }
+        });
+        // Records can only have been overwritten since, never removed, so popping can't run dry
+        // (unless someone else pops concurrently).
+        for _ in 0..present {
+            let Some(record) = self.pop() else {
+                break;
+            };
+            f(record);
+        }
+        crate::interrupt::free(|_| {
+            // unsafe: Accessed with interrupts disabled
+            let ring = unsafe { &mut *self.ring.get() };
+            core::mem::take(&mut ring.overwritten)
+        })
+    }
+}
+
+impl<const N: usize> Default for TraceRecorder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Observer for TraceRecorder<N> {
+    fn switched(
+        &self,
+        previous: Option<KernelPID>,
+        next: Option<KernelPID>,
+        _cs: &CriticalSection,
+    ) {
+        if N == 0 {
+            return;
+        }
+        let record = Record {
+            previous,
+            next,
+            #[cfg(riot_module_ztimer_usec)]
+            // unsafe: C API as documented; reading the clock is allowed with interrupts disabled.
+            timestamp: unsafe {
+                riot_sys::inline::ztimer_now(crate::inline_cast_mut(riot_sys::ZTIMER_USEC))
+            } as u32,
+        };
+        // unsafe: Called with interrupts disabled, as attested by the critical section
+        let ring = unsafe { &mut *self.ring.get() };
+        let end = (ring.start + ring.len) % N;
+        ring.records[end] = Some(record);
+        if ring.len == N {
+            ring.start = (ring.start + 1) % N;
+            ring.overwritten += 1;
+        } else {
+            ring.len += 1;
+        }
+    }
+}
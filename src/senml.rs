@@ -0,0 +1,423 @@
+//! Sensor Measurement Lists (SenML, [RFC 8428]) through RIOT's [senml] modules
+//!
+//! Records are encoded into a [nanocbor::Encoder] that the application sets up; a SenML pack is
+//! a CBOR array of records, so a typical encoding of one reading looks like this:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::nanocbor::*;
+//! # use riot_wrappers::senml::*;
+//! # fn f() -> Result<(), Error> {
+//! let mut buf = [0; 64];
+//! let mut encoder = Encoder::new(&mut buf);
+//! encoder.array(1);
+//! let attributes = Attributes {
+//!     name: Some(c"temperature"),
+//!     unit: Some(Unit::from_raw(riot_sys::SENML_UNIT_CELSIUS)),
+//!     ..Default::default()
+//! };
+//! encode(&mut encoder, &attributes, &Value::Numeric(Numeric::Float(21.5)));
+//! let pack = encoder.finish()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Incoming packs can be processed with [`parse()`], which resolves the base fields of RFC 8428
+//! Section 4.5 for each record.
+//!
+//! [RFC 8428]: https://www.rfc-editor.org/rfc/rfc8428
+//! [senml]: https://doc.riot-os.org/group__sys__senml.html
+
+use core::ffi::CStr;
+
+#[cfg(riot_module_nanocbor)]
+use crate::nanocbor;
+
+/// A SenML unit
+///
+/// Units are represented by the numeric values of RIOT's `senml_unit_t`; the constants are
+/// available as `riot_sys::SENML_UNIT_*`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Unit(riot_sys::senml_unit_t);
+
+impl Unit {
+    /// Wrap a unit given as a `senml_unit_t` value
+    pub fn from_raw(unit: riot_sys::senml_unit_t) -> Self {
+        Self(unit)
+    }
+
+    /// The `senml_unit_t` value of the unit
+    pub fn to_raw(self) -> riot_sys::senml_unit_t {
+        self.0
+    }
+
+    /// Name of the unit as used in SenML's `u` field (eg. "Cel" for degrees Celsius)
+    #[doc(alias = "senml_unit_to_str")]
+    pub fn as_str(self) -> &'static str {
+        // unsafe: C API as documented; the returned strings are static.
+        let name = unsafe { CStr::from_ptr(riot_sys::senml_unit_to_str(self.0)) };
+        // SenML unit names are ASCII
+        name.to_str().unwrap_or("")
+    }
+
+    /// Find the SenML unit corresponding to a SAUL unit
+    ///
+    /// Some SAUL units have no SenML equivalent; those are mapped to the unit-less value.
+    #[cfg(riot_module_senml_phydat)]
+    #[doc(alias = "phydat_unit_to_senml_unit")]
+    pub fn from_saul(unit: Option<crate::saul::Unit>) -> Self {
+        // unsafe: C API makes no requirements
+        Self(unsafe { riot_sys::phydat_unit_to_senml_unit(crate::saul::Unit::to_c(unit)) })
+    }
+}
+
+/// A numeric value as it can occur in SenML values, sums and times
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Numeric {
+    Uint(u64),
+    Int(i64),
+    Float(f32),
+    Double(f64),
+    /// A decimal fraction representing `mantissa * 10^exponent`
+    DecimalFraction {
+        exponent: i32,
+        mantissa: i32,
+    },
+}
+
+impl Numeric {
+    /// Approximate the value as a double precision float
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Numeric::Uint(u) => u as f64,
+            Numeric::Int(i) => i as f64,
+            Numeric::Float(f) => f as f64,
+            Numeric::Double(d) => d,
+            Numeric::DecimalFraction { exponent, mantissa } => {
+                let mut result = mantissa as f64;
+                for _ in 0..exponent.unsigned_abs() {
+                    if exponent > 0 {
+                        result *= 10.0;
+                    } else {
+                        result /= 10.0;
+                    }
+                }
+                result
+            }
+        }
+    }
+
+    /// Convert into a numeric for the C API; absent values are all-zero, which the encoder skips.
+    fn to_c(value: Option<Self>) -> riot_sys::senml_numeric_t {
+        // unsafe: All-zero is a valid senml_numeric_t (a float 0)
+        let mut result: riot_sys::senml_numeric_t = unsafe { core::mem::zeroed() };
+        match value {
+            None => (),
+            Some(Numeric::Uint(u)) => {
+                result.type_ = riot_sys::SENML_TYPE_NUMERIC_UINT;
+                result.value.u = u;
+            }
+            Some(Numeric::Int(i)) => {
+                result.type_ = riot_sys::SENML_TYPE_NUMERIC_INT;
+                result.value.i = i;
+            }
+            Some(Numeric::Float(f)) => {
+                result.type_ = riot_sys::SENML_TYPE_NUMERIC_FLOAT;
+                result.value.f = f;
+            }
+            Some(Numeric::Double(d)) => {
+                result.type_ = riot_sys::SENML_TYPE_NUMERIC_DOUBLE;
+                result.value.d = d;
+            }
+            Some(Numeric::DecimalFraction { exponent, mantissa }) => {
+                result.type_ = riot_sys::SENML_TYPE_NUMERIC_DECFRAC;
+                result.value.df.e = exponent;
+                result.value.df.m = mantissa;
+            }
+        }
+        result
+    }
+
+    /// Read a numeric item as it occurs in a SenML record
+    #[cfg(riot_module_nanocbor)]
+    fn decode(decoder: &mut nanocbor::Decoder<'_>) -> Result<Self, nanocbor::Error> {
+        Ok(match decoder.major_type()? {
+            nanocbor::MajorType::Uint => Numeric::Uint(decoder.u64()?),
+            nanocbor::MajorType::Nint => Numeric::Int(decoder.i64()?),
+            nanocbor::MajorType::Tag => {
+                // RFC 8949 Section 3.4.4
+                if decoder.tag()? != 4 {
+                    return Err(nanocbor::Error::InvalidType);
+                }
+                let (exponent, mantissa) =
+                    decoder.array(|items| Ok((items.i32()?, items.i32()?)))?;
+                Numeric::DecimalFraction { exponent, mantissa }
+            }
+            _ => Numeric::Double(decoder.f64()?),
+        })
+    }
+}
+
+/// Attributes of a record to be encoded
+///
+/// All fields are optional; absent fields are not encoded.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Attributes<'a> {
+    pub base_name: Option<&'a CStr>,
+    pub base_time: Option<Numeric>,
+    pub base_unit: Option<Unit>,
+    pub base_value: Option<Numeric>,
+    pub base_sum: Option<Numeric>,
+    pub base_version: Option<u64>,
+    pub name: Option<&'a CStr>,
+    pub unit: Option<Unit>,
+    pub sum: Option<Numeric>,
+    pub time: Option<Numeric>,
+    pub update_time: Option<Numeric>,
+}
+
+impl Attributes<'_> {
+    fn to_c(&self) -> riot_sys::senml_attr_t {
+        let name = |n: Option<&CStr>| n.map(|n| n.as_ptr()).unwrap_or(core::ptr::null());
+        // SENML_UNIT_NONE is 0
+        let unit = |u: Option<Unit>| u.map(|u| u.0).unwrap_or(0);
+        riot_sys::senml_attr_t {
+            base_name: name(self.base_name),
+            base_time: Numeric::to_c(self.base_time),
+            base_unit: unit(self.base_unit),
+            base_value: Numeric::to_c(self.base_value),
+            base_sum: Numeric::to_c(self.base_sum),
+            base_version: self.base_version.unwrap_or(0),
+            name: name(self.name),
+            unit: unit(self.unit),
+            value_sum: Numeric::to_c(self.sum),
+            time: Numeric::to_c(self.time),
+            update_time: Numeric::to_c(self.update_time),
+        }
+    }
+}
+
+/// The value of a SenML record
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Value<'a> {
+    Numeric(Numeric),
+    String(&'a str),
+    Bool(bool),
+    Data(&'a [u8]),
+}
+
+/// Encode a single record into an encoder
+///
+/// Like the encoding methods of the encoder, this does not report errors; running out of buffer
+/// space is reported when finishing the encoder.
+#[cfg(riot_module_senml_cbor)]
+#[doc(alias = "senml_encode_value_cbor")]
+pub fn encode(encoder: &mut nanocbor::Encoder<'_>, attributes: &Attributes<'_>, value: &Value<'_>) {
+    let attr = attributes.to_c();
+    let enc = encoder.as_mut_ptr();
+    // unsafe: C API as documented; all pointers in the value structs are valid for the duration of
+    // the call, and strings and data are passed with explicit lengths.
+    unsafe {
+        match *value {
+            Value::Numeric(n) => {
+                let value = riot_sys::senml_value_t {
+                    attr,
+                    value: Numeric::to_c(Some(n)),
+                };
+                riot_sys::senml_encode_value_cbor(enc, &value);
+            }
+            Value::String(s) => {
+                let value = riot_sys::senml_string_value_t {
+                    attr,
+                    value: s.as_ptr() as _,
+                    len: s.len() as _,
+                };
+                riot_sys::senml_encode_string_cbor(enc, &value);
+            }
+            Value::Bool(b) => {
+                let value = riot_sys::senml_bool_value_t { attr, value: b };
+                riot_sys::senml_encode_bool_cbor(enc, &value);
+            }
+            Value::Data(d) => {
+                let value = riot_sys::senml_data_value_t {
+                    attr,
+                    value: d.as_ptr(),
+                    len: d.len() as _,
+                };
+                riot_sys::senml_encode_data_cbor(enc, &value);
+            }
+        };
+    }
+}
+
+/// Read a SAUL device, and encode its reading as one record per dimension into an encoder
+///
+/// The records are named after the device.
+#[cfg(riot_module_senml_saul)]
+#[doc(alias = "senml_saul_reg_encode_cbor")]
+pub fn encode_saul(
+    encoder: &mut nanocbor::Encoder<'_>,
+    device: &crate::saul::RegistryEntry,
+) -> Result<(), crate::error::NumericError> {
+    use crate::error::NegativeErrorExt;
+
+    // unsafe: C API as documented
+    unsafe { riot_sys::senml_saul_reg_encode_cbor(encoder.as_mut_ptr(), device.as_ptr()) }
+        .negative_to_error()?;
+    Ok(())
+}
+
+/// Express a SAUL reading as a unit and one decimal fraction per dimension
+///
+/// This is equivalent to RIOT's `phydat_to_senml_decimal`, and can be used to encode readings
+/// with custom names or times through [`encode()`].
+#[cfg(riot_module_saul)]
+#[cfg(riot_module_senml_phydat)]
+#[doc(alias = "phydat_to_senml_decimal")]
+pub fn from_phydat(phydat: &crate::saul::Phydat) -> (Unit, impl Iterator<Item = Numeric> + '_) {
+    let exponent = phydat.scale().into();
+    (
+        Unit::from_saul(phydat.unit()),
+        phydat
+            .value()
+            .iter()
+            .map(move |&v| Numeric::DecimalFraction {
+                exponent,
+                mantissa: v.into(),
+            }),
+    )
+}
+
+/// A record of a parsed SenML pack
+///
+/// Base fields are already applied: the name is split into the (possibly inherited) base name and
+/// the record's own name, which are to be concatenated; the time and numeric value are resolved
+/// against the base time and base value.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Record<'a> {
+    pub base_name: Option<&'a str>,
+    pub name: Option<&'a str>,
+    /// The record's unit, or the base unit
+    pub unit: Option<&'a str>,
+    /// Time (relative to now if below 2^28, otherwise seconds since the UNIX epoch)
+    pub time: Option<f64>,
+    pub update_time: Option<f64>,
+    /// The record's value; numeric values have the base value added
+    pub value: Option<Value<'a>>,
+    /// The record's sum, with the base sum added
+    pub sum: Option<f64>,
+}
+
+#[cfg(riot_module_nanocbor)]
+#[derive(Default)]
+struct Base<'a> {
+    name: Option<&'a str>,
+    time: Option<f64>,
+    unit: Option<&'a str>,
+    value: Option<f64>,
+    sum: Option<f64>,
+}
+
+// Labels of RFC 8428 Section 6
+#[cfg(riot_module_nanocbor)]
+mod label {
+    pub const BASE_VERSION: i64 = -1;
+    pub const BASE_NAME: i64 = -2;
+    pub const BASE_TIME: i64 = -3;
+    pub const BASE_UNIT: i64 = -4;
+    pub const BASE_VALUE: i64 = -5;
+    pub const BASE_SUM: i64 = -6;
+    pub const NAME: i64 = 0;
+    pub const UNIT: i64 = 1;
+    pub const VALUE: i64 = 2;
+    pub const STRING_VALUE: i64 = 3;
+    pub const BOOL_VALUE: i64 = 4;
+    pub const SUM: i64 = 5;
+    pub const TIME: i64 = 6;
+    pub const UPDATE_TIME: i64 = 7;
+    pub const DATA_VALUE: i64 = 8;
+}
+
+/// Parse a SenML pack in CBOR representation, calling `f` for every record
+///
+/// Fields with unknown labels are ignored; as the CBOR representation only uses integer labels,
+/// so are any fields with text labels.
+#[cfg(riot_module_nanocbor)]
+pub fn parse<'a>(data: &'a [u8], mut f: impl FnMut(Record<'a>)) -> Result<(), nanocbor::Error> {
+    let mut decoder = nanocbor::Decoder::new(data);
+    decoder.array(|records| {
+        let mut base = Base::default();
+        while !records.at_end() {
+            let record = records.map(|fields| parse_record(fields, &mut base))?;
+            f(record);
+        }
+        Ok(())
+    })
+}
+
+#[cfg(riot_module_nanocbor)]
+fn parse_record<'a>(
+    fields: &mut nanocbor::Decoder<'a>,
+    base: &mut Base<'a>,
+) -> Result<Record<'a>, nanocbor::Error> {
+    let mut name = None;
+    let mut unit = None;
+    let mut time = None;
+    let mut update_time = None;
+    let mut value = None;
+    let mut sum = None;
+
+    while !fields.at_end() {
+        let key = match fields.major_type()? {
+            nanocbor::MajorType::Uint | nanocbor::MajorType::Nint => fields.i64()?,
+            _ => {
+                fields.skip()?;
+                fields.skip()?;
+                continue;
+            }
+        };
+        match key {
+            label::BASE_NAME => base.name = Some(fields.tstr()?),
+            label::BASE_TIME => base.time = Some(Numeric::decode(fields)?.to_f64()),
+            label::BASE_UNIT => base.unit = Some(fields.tstr()?),
+            label::BASE_VALUE => base.value = Some(Numeric::decode(fields)?.to_f64()),
+            label::BASE_SUM => base.sum = Some(Numeric::decode(fields)?.to_f64()),
+            label::NAME => name = Some(fields.tstr()?),
+            label::UNIT => unit = Some(fields.tstr()?),
+            label::VALUE => value = Some(Value::Numeric(Numeric::decode(fields)?)),
+            label::STRING_VALUE => value = Some(Value::String(fields.tstr()?)),
+            label::BOOL_VALUE => value = Some(Value::Bool(fields.bool()?)),
+            label::DATA_VALUE => value = Some(Value::Data(fields.bstr()?)),
+            label::SUM => sum = Some(Numeric::decode(fields)?.to_f64()),
+            label::TIME => time = Some(Numeric::decode(fields)?.to_f64()),
+            label::UPDATE_TIME => update_time = Some(Numeric::decode(fields)?.to_f64()),
+            // The version has no effect on how the fields known here are interpreted
+            label::BASE_VERSION => fields.skip()?,
+            _ => fields.skip()?,
+        }
+    }
+
+    let value = match (value, base.value) {
+        (Some(Value::Numeric(v)), Some(bv)) => {
+            Some(Value::Numeric(Numeric::Double(v.to_f64() + bv)))
+        }
+        // A record without a value (or sum) carries the base value
+        (None, Some(bv)) if sum.is_none() => Some(Value::Numeric(Numeric::Double(bv))),
+        (value, _) => value,
+    };
+    let add_base = |v: Option<f64>, b: Option<f64>| match (v, b) {
+        (None, None) => None,
+        (v, b) => Some(v.unwrap_or(0.0) + b.unwrap_or(0.0)),
+    };
+
+    Ok(Record {
+        base_name: base.name,
+        name,
+        unit: unit.or(base.unit),
+        time: add_base(time, base.time),
+        update_time,
+        value,
+        sum: sum.map(|s| s + base.sum.unwrap_or(0.0)),
+    })
+}
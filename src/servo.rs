@@ -0,0 +1,83 @@
+//! Control of hobby servos through [RIOT's servo
+//! driver](https://doc.riot-os.org/group__drivers__servo.html)
+//!
+//! The range of a servo is given in its parameters as the minimum and maximum pulse length;
+//! positions are always mapped into that range, so a servo can not be driven beyond its
+//! configured end points.
+//!
+//! The device's state needs to be in static memory, as some backends (eg. `servo_timer`) keep a
+//! pointer to it for use in interrupts:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::servo::Servo;
+//! use core::mem::MaybeUninit;
+//! use riot_wrappers::static_init::ConstStaticCell;
+//!
+//! static DEVICE: ConstStaticCell<MaybeUninit<riot_sys::servo_t>> =
+//!     ConstStaticCell::new(MaybeUninit::uninit());
+//!
+//! # fn f(params: &'static riot_sys::servo_params_t) {
+//! let mut servo = Servo::init(DEVICE.take(), params).unwrap();
+//! servo.set_position(128); // center
+//! servo.set_pulse_us(1000);
+//! # }
+//! ```
+
+use core::mem::MaybeUninit;
+use core::ops::RangeInclusive;
+
+use riot_sys::{servo_params_t, servo_t};
+
+use crate::error::{NegativeErrorExt, NumericError};
+
+/// An initialized servo
+pub struct Servo {
+    dev: &'static mut servo_t,
+    params: &'static servo_params_t,
+}
+
+impl Servo {
+    /// Initialize the servo's PWM channel, keeping its state in the given static memory
+    ///
+    /// The servo is not moved until a position is set.
+    #[doc(alias = "servo_init")]
+    pub fn init(
+        device: &'static mut MaybeUninit<servo_t>,
+        params: &'static servo_params_t,
+    ) -> Result<Self, NumericError> {
+        // All-zero is a valid uninitialized device, which is initialized right away
+        let dev = device.write(servo_t::default());
+        // unsafe: C API as documented; the parameters are static, and so is the device, which the
+        // driver may thus keep a pointer to.
+        unsafe { riot_sys::servo_init(dev, params) }.negative_to_error()?;
+        Ok(Self { dev, params })
+    }
+
+    /// Range of pulse lengths (in microseconds) the servo is driven with
+    pub fn pulse_range_us(&self) -> RangeInclusive<u16> {
+        self.params.min_us..=self.params.max_us
+    }
+
+    /// Move the servo to a position between its minimum (0) and maximum (255)
+    #[doc(alias = "servo_set")]
+    pub fn set_position(&mut self, position: u8) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::servo_set(self.dev, position) };
+    }
+
+    /// Move the servo to the position corresponding to the given pulse length
+    ///
+    /// Pulse lengths outside the [configured range](Self::pulse_range_us) are limited to it. The
+    /// pulse is approximated in 256 steps.
+    pub fn set_pulse_us(&mut self, pulse: u16) {
+        let (min, max) = (self.params.min_us as u32, self.params.max_us as u32);
+        let pulse = (pulse as u32).clamp(min, max);
+        let position = match max - min {
+            0 => 0,
+            span => ((pulse - min) * 255 + span / 2) / span,
+        };
+        self.set_position(position as u8);
+    }
+}
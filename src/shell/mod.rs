@@ -2,8 +2,9 @@
 //!
 //! This module can be used in two ways:
 //!
-//! * Declare static commands using [`static_command!`](crate::static_command!); these only take a
-//!   `fn` (not a closure) because shell commands don't have an arg pointer.
+//! * Declare static commands using [`shell_command!`](crate::shell_command!) (or its older form
+//!   [`static_command!`](crate::static_command!)); these only take a `fn` or a non-capturing
+//!   closure because shell commands don't have an arg pointer.
 //!
 //!   This works even in RIOT modules that are included in a C application that starts a shell, and
 //!   show up in shells created through Rust without explicit inclusion.
@@ -20,7 +21,7 @@
 //! argument. This does allow the Rust wrappers to "just so" use a closure as a command handler,
 //! but also needs a lot of code.
 //!
-//! That complexity is not pulled in when only using [`static_command!`](crate::static_command!)
+//! That complexity is not pulled in when only using [`shell_command!`](crate::shell_command!)
 //! and running on an otherwise empty command list.
 
 use crate::{mutex, stdio};
@@ -29,8 +30,12 @@ use riot_sys::libc;
 use riot_sys::{shell_command_t, shell_run_forever, shell_run_once};
 
 mod args;
+mod session;
 
 pub use args::Args;
+#[cfg(riot_module_shell_lock)]
+pub use session::is_locked;
+pub use session::Session;
 // re-exported only as long as users can't just make a TAIT out of the Args return type.
 pub use args::ArgsIterator;
 
@@ -54,7 +59,7 @@ pub unsafe trait CommandListInternals: Sized {
     // It is set to always inline because situations in which both run_once and run_forever are
     // used are expected to be very rare.
     #[inline(always)]
-    fn run_any<R, F: Fn(*const riot_sys::shell_command_t, *mut libc::c_char, i32) -> R>(
+    fn run_any<R, F: FnOnce(*const riot_sys::shell_command_t, *mut libc::c_char, i32) -> R>(
         &mut self,
         linebuffer: &mut [u8],
         cb: F,
@@ -172,6 +177,38 @@ pub trait CommandList<const BUFSIZE: usize = { riot_sys::SHELL_DEFAULT_BUFSIZE a
         self.run_once_with_buf(&mut linebuffer)
     }
 
+    /// Run a shell session with a custom prompt and hooks on stdio
+    ///
+    /// Unlike [`.run_forever()`](CommandList::run_forever), this runs the loop of reading and
+    /// executing lines in Rust; see [Session] for details. The line buffer is allocated like
+    /// there.
+    fn run_session_forever<Pre, Post>(&mut self, session: &mut Session<'_, Pre, Post>) -> !
+    where
+        Pre: FnMut(&str) -> bool,
+        Post: FnMut(),
+    {
+        let mut linebuffer = [0; BUFSIZE];
+        self.run_any(&mut linebuffer, |built, buf, len| {
+            session.run(built, buf, len, true);
+            unreachable!()
+        })
+    }
+
+    /// Run a shell session with a custom prompt and hooks on stdio until EOF is reached
+    ///
+    /// Unlike [`.run_once()`](CommandList::run_once), this runs the loop of reading and executing
+    /// lines in Rust; see [Session] for details. The line buffer is allocated like there.
+    fn run_session_once<Pre, Post>(&mut self, session: &mut Session<'_, Pre, Post>)
+    where
+        Pre: FnMut(&str) -> bool,
+        Post: FnMut(),
+    {
+        let mut linebuffer = [0; BUFSIZE];
+        self.run_any(&mut linebuffer, |built, buf, len| {
+            session.run(built, buf, len, false)
+        })
+    }
+
     #[deprecated(note = "Renamed to run_forever", since = "0.9")]
     fn run_forever_providing_buf(&mut self) -> ! {
         self.run_forever()
@@ -461,3 +498,67 @@ macro_rules! static_command {
         }
     };
 }
+
+/// Register a shell command at link time, making it available in any RIOT shell (even when called
+/// through C), including those of applications that just depend on a library crate that uses this.
+///
+/// The handler is a function or non-capturing closure that takes `&mut `[`Stdio`](stdio::Stdio)
+/// and [`Args`], and returns an `impl `[`Termination`](crate::main::Termination).
+///
+/// This works like [`static_command!`](crate::static_command!), but needs no module name: the
+/// command's entry in the `SHELL_COMMANDS_XFA` cross-file array is named after the command,
+/// whose name needs to be unique in the shell anyway.
+///
+/// Example
+/// -------
+///
+/// ```
+/// # #![no_std]
+/// # #![feature(start)]
+/// use riot_wrappers::println;
+///
+/// riot_wrappers::shell_command!("hello", "Greet the user", |_stdio, args| {
+///     println!("Hello {}", args.get(1).unwrap_or("world"));
+/// });
+/// # #[start]
+/// # fn main(_argc: isize, _argv: *const *const u8) -> isize {
+/// # 0
+/// # }
+/// ```
+#[macro_export]
+macro_rules! shell_command {
+    ( $name:literal, $descr:literal, $fun:expr $(,)? ) => {
+        const _: () = {
+            // See static_command for the details of the struct and the two XFA entries.
+            #[repr(transparent)]
+            struct StaticCommand($crate::riot_sys::shell_command_t);
+
+            // unsafe: OK due to the only construction way (the CStr is created from a literal and
+            // thus static, and the_function is static by construction as well)
+            unsafe impl Sync for StaticCommand {}
+
+            #[link_section = ".roxfa.shell_commands_xfa_v2.5"]
+            #[export_name = concat!("shell_commands_xfa_v2_5_", $name)]
+            static THE_STRUCT: StaticCommand = StaticCommand($crate::riot_sys::shell_command_t {
+                name: $crate::cstr::cstr!($name).as_ptr() as _,
+                desc: $crate::cstr::cstr!($descr).as_ptr() as _,
+                handler: Some(the_function),
+            });
+            #[link_section = ".roxfa.shell_commands_xfa.5"]
+            #[export_name = concat!("shell_commands_xfa_5_", $name)]
+            static THE_POINTER: &StaticCommand = &THE_STRUCT;
+
+            unsafe extern "C" fn the_function(
+                argc: i32,
+                argv: *mut *mut $crate::riot_sys::libc::c_char,
+            ) -> i32 {
+                let marker = ();
+                let args = unsafe { $crate::shell::Args::new(argc, argv as _, &marker) };
+                let mut stdio = $crate::stdio::Stdio {};
+                use $crate::main::Termination;
+                let fun: fn(&mut $crate::stdio::Stdio, $crate::shell::Args<'_>) -> _ = $fun;
+                fun(&mut stdio, args).report()
+            }
+        };
+    };
+}
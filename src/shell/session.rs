@@ -0,0 +1,147 @@
+use core::fmt::Write;
+use riot_sys::libc;
+
+use crate::stdio;
+
+/// Configuration for running a shell through [`CommandList::run_session_forever()`] or
+/// [`CommandList::run_session_once()`]
+///
+/// Unlike the plain run methods (which leave all of the interaction to RIOT's shell), a session
+/// drives the read-and-execute loop from Rust, which allows customizing the prompt and running
+/// hooks around every command. Line editing, tokenization and command lookup are still done by
+/// RIOT.
+///
+/// If the `shell_lock` module is active, a password is requested before the first prompt, and
+/// again after the shell was locked (eg. through its `lock` command).
+///
+/// [`CommandList::run_session_forever()`]: super::CommandList::run_session_forever
+/// [`CommandList::run_session_once()`]: super::CommandList::run_session_once
+pub struct Session<'a, Pre = fn(&str) -> bool, Post = fn()>
+where
+    Pre: FnMut(&str) -> bool,
+    Post: FnMut(),
+{
+    prompt: &'a str,
+    before_command: Pre,
+    after_command: Post,
+}
+
+impl Session<'static> {
+    /// Start configuring a session with the default prompt and no hooks
+    pub fn new() -> Self {
+        Session {
+            prompt: "> ",
+            before_command: |_| true,
+            after_command: || (),
+        }
+    }
+}
+
+impl Default for Session<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Pre, Post> Session<'a, Pre, Post>
+where
+    Pre: FnMut(&str) -> bool,
+    Post: FnMut(),
+{
+    /// Set the prompt that is shown before each line is read
+    pub fn prompt<'b>(self, prompt: &'b str) -> Session<'b, Pre, Post> {
+        Session {
+            prompt,
+            before_command: self.before_command,
+            after_command: self.after_command,
+        }
+    }
+
+    /// Set a hook that is called with every line that was entered, before it is executed
+    ///
+    /// If the hook returns false, the line is not executed (and the `after_command` hook is not
+    /// called either).
+    pub fn before_command<F: FnMut(&str) -> bool>(self, hook: F) -> Session<'a, F, Post> {
+        Session {
+            prompt: self.prompt,
+            before_command: hook,
+            after_command: self.after_command,
+        }
+    }
+
+    /// Set a hook that is called after each executed line
+    pub fn after_command<F: FnMut()>(self, hook: F) -> Session<'a, Pre, F> {
+        Session {
+            prompt: self.prompt,
+            before_command: self.before_command,
+            after_command: hook,
+        }
+    }
+
+    /// Loop of reading and executing lines, with the same semantics as RIOT's `shell_run_once`
+    /// (returning at EOF) or `shell_run_forever` (ignoring EOF)
+    pub(super) fn run(
+        &mut self,
+        commands: *const riot_sys::shell_command_t,
+        buf: *mut libc::c_char,
+        len: i32,
+        forever: bool,
+    ) {
+        loop {
+            #[cfg(riot_module_shell_lock)]
+            lock_checkpoint(buf, len);
+
+            let _ = stdio::Stdio {}.write_str(self.prompt);
+
+            // unsafe: C API as documented, the buffer is exclusively available during the loop
+            let read = unsafe { riot_sys::shell_readline(buf, len as _) };
+            // EOF
+            if read == -1 {
+                if forever {
+                    continue;
+                } else {
+                    return;
+                }
+            }
+            if read < 0 {
+                // Same message RIOT's shell shows
+                let _ = writeln!(stdio::Stdio {}, "shell: maximum line length exceeded");
+                continue;
+            }
+
+            let execute = {
+                // unsafe: shell_readline wrote that many bytes; the slice is dropped before the
+                // buffer is used again.
+                let line = unsafe { core::slice::from_raw_parts(buf as *const u8, read as _) };
+                match core::str::from_utf8(line) {
+                    Ok(line) => (self.before_command)(line),
+                    // RIOT's shell does not care for the encoding, but the hook can't be passed
+                    // such a line
+                    Err(_) => true,
+                }
+            };
+            if execute {
+                // unsafe: C API as documented; the commands are null terminated by construction,
+                // and the buffer contains a null terminated line as left by shell_readline.
+                unsafe { riot_sys::shell_handle_input_line(commands, buf) };
+                (self.after_command)();
+            }
+        }
+    }
+}
+
+/// Ask for the password if the shell is locked, and return once it is unlocked
+#[cfg(riot_module_shell_lock)]
+fn lock_checkpoint(buf: *mut libc::c_char, len: i32) {
+    // unsafe: C API as documented, the buffer is exclusively available in the session loop
+    unsafe { riot_sys::shell_lock_checkpoint(buf, len) };
+}
+
+/// Whether the shell is currently locked by `shell_lock`, and would ask for a password at its
+/// next prompt
+#[cfg(riot_module_shell_lock)]
+#[doc(alias = "shell_lock_is_locked")]
+pub fn is_locked() -> bool {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::shell_lock_is_locked() }
+}
@@ -64,6 +64,181 @@ impl Into<riot_sys::sock_udp_ep_t> for UdpEp {
     }
 }
 
+/// Auxiliary data requested when receiving on a UDP socket
+///
+/// Which data is requested is selected by the `with_*` builder methods (each of which is only
+/// available if the corresponding `sock_aux_*` module is enabled). After reception, the accessors
+/// return the values the network stack provided; they return None if the item was not requested,
+/// or if the stack could not provide it for that particular datagram.
+#[cfg(riot_module_sock_udp)]
+#[doc(alias = "sock_udp_aux_rx_t")]
+pub struct UdpRxAux {
+    aux: riot_sys::sock_udp_aux_rx_t,
+    requested: riot_sys::sock_aux_flags_t,
+}
+
+#[cfg(riot_module_sock_udp)]
+impl UdpRxAux {
+    /// Create an aux object that requests no data
+    pub fn new() -> Self {
+        Self {
+            aux: Default::default(),
+            requested: 0,
+        }
+    }
+
+    fn request(mut self, flag: u32) -> Self {
+        self.requested |= flag as riot_sys::sock_aux_flags_t;
+        self
+    }
+
+    /// Whether the item was requested, and the stack cleared the flag to indicate it was populated
+    fn provided(&self, flag: u32) -> bool {
+        let flag = flag as riot_sys::sock_aux_flags_t;
+        self.requested & flag != 0 && self.aux.flags & flag == 0
+    }
+
+    /// Request the local address the datagram was sent to
+    ///
+    /// This is particularly useful on sockets bound to an unspecified address, to tell which of
+    /// the node's addresses (or which multicast group) the datagram was addressed to.
+    #[cfg(riot_module_sock_aux_local)]
+    #[doc(alias = "SOCK_AUX_GET_LOCAL")]
+    pub fn with_local(self) -> Self {
+        self.request(riot_sys::SOCK_AUX_GET_LOCAL)
+    }
+
+    /// Request the time stamp of the datagram's reception
+    #[cfg(riot_module_sock_aux_timestamp)]
+    #[doc(alias = "SOCK_AUX_GET_TIMESTAMP")]
+    pub fn with_timestamp(self) -> Self {
+        self.request(riot_sys::SOCK_AUX_GET_TIMESTAMP)
+    }
+
+    /// Request the received signal strength of the datagram
+    #[cfg(riot_module_sock_aux_rssi)]
+    #[doc(alias = "SOCK_AUX_GET_RSSI")]
+    pub fn with_rssi(self) -> Self {
+        self.request(riot_sys::SOCK_AUX_GET_RSSI)
+    }
+
+    /// Request the hop limit (TTL) the datagram was received with
+    #[cfg(riot_module_sock_aux_ttl)]
+    #[doc(alias = "SOCK_AUX_GET_TTL")]
+    pub fn with_ttl(self) -> Self {
+        self.request(riot_sys::SOCK_AUX_GET_TTL)
+    }
+
+    /// Pointer to pass into a receive function
+    ///
+    /// This resets the flags to the requested items, so the object can be used repeatedly.
+    pub(crate) fn prepare(&mut self) -> *mut riot_sys::sock_udp_aux_rx_t {
+        self.aux.flags = self.requested;
+        &mut self.aux
+    }
+
+    /// Local address the datagram was sent to
+    #[cfg(riot_module_sock_aux_local)]
+    pub fn local(&self) -> Option<UdpEp> {
+        self.provided(riot_sys::SOCK_AUX_GET_LOCAL)
+            .then(|| self.aux.local.into())
+    }
+
+    /// Time stamp of reception in nanoseconds
+    ///
+    /// The time base depends on the network device; see RIOT's `sock_aux_timestamp`
+    /// documentation.
+    #[cfg(riot_module_sock_aux_timestamp)]
+    pub fn timestamp(&self) -> Option<u64> {
+        self.provided(riot_sys::SOCK_AUX_GET_TIMESTAMP)
+            .then(|| self.aux.timestamp)
+    }
+
+    /// Received signal strength in dBm
+    #[cfg(riot_module_sock_aux_rssi)]
+    pub fn rssi(&self) -> Option<i16> {
+        self.provided(riot_sys::SOCK_AUX_GET_RSSI)
+            .then(|| self.aux.rssi)
+    }
+
+    /// Hop limit (TTL) of the received datagram
+    #[cfg(riot_module_sock_aux_ttl)]
+    pub fn ttl(&self) -> Option<u8> {
+        self.provided(riot_sys::SOCK_AUX_GET_TTL)
+            .then(|| self.aux.ttl)
+    }
+}
+
+#[cfg(riot_module_sock_udp)]
+impl Default for UdpRxAux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Auxiliary data used when sending on a UDP socket
+///
+/// This can set the local address a datagram is sent from, and request the time stamp at which it
+/// was sent, which is available through [`.timestamp()`](Self::timestamp) after sending.
+#[cfg(riot_module_sock_udp)]
+#[doc(alias = "sock_udp_aux_tx_t")]
+pub struct UdpTxAux {
+    aux: riot_sys::sock_udp_aux_tx_t,
+    requested: riot_sys::sock_aux_flags_t,
+}
+
+#[cfg(riot_module_sock_udp)]
+impl UdpTxAux {
+    /// Create an aux object that sets and requests nothing
+    pub fn new() -> Self {
+        Self {
+            aux: Default::default(),
+            requested: 0,
+        }
+    }
+
+    /// Send from the given local address
+    #[cfg(riot_module_sock_aux_local)]
+    #[doc(alias = "SOCK_AUX_SET_LOCAL")]
+    pub fn with_local(mut self, local: UdpEp) -> Self {
+        self.aux.local = local.0;
+        self.requested |= riot_sys::SOCK_AUX_SET_LOCAL as riot_sys::sock_aux_flags_t;
+        self
+    }
+
+    /// Request the time stamp at which the datagram was sent
+    #[cfg(riot_module_sock_aux_timestamp)]
+    #[doc(alias = "SOCK_AUX_GET_TIMESTAMP")]
+    pub fn with_timestamp(mut self) -> Self {
+        self.requested |= riot_sys::SOCK_AUX_GET_TIMESTAMP as riot_sys::sock_aux_flags_t;
+        self
+    }
+
+    /// Pointer to pass into a send function
+    ///
+    /// This resets the flags to the requested items, so the object can be used repeatedly.
+    pub(crate) fn prepare(&mut self) -> *mut riot_sys::sock_udp_aux_tx_t {
+        self.aux.flags = self.requested;
+        &mut self.aux
+    }
+
+    /// Time stamp of transmission in nanoseconds
+    ///
+    /// This is None if no time stamp was requested, or if the network device did not provide one.
+    #[cfg(riot_module_sock_aux_timestamp)]
+    pub fn timestamp(&self) -> Option<u64> {
+        let flag = riot_sys::SOCK_AUX_GET_TIMESTAMP as riot_sys::sock_aux_flags_t;
+        (self.requested & flag != 0 && self.aux.flags & flag == 0).then(|| self.aux.timestamp)
+    }
+}
+
+#[cfg(riot_module_sock_udp)]
+impl Default for UdpTxAux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Gated to its users to avoid dead code warnings
 #[cfg(any(feature = "with_embedded_nal", feature = "with_embedded_nal_async"))]
 macro_rules! implementation_no_std_net {
@@ -5,7 +5,7 @@ use core::convert::TryInto;
 use core::mem::MaybeUninit;
 
 use crate::error::{NegativeErrorExt, NumericError};
-use crate::socket::UdpEp;
+use crate::socket::{UdpEp, UdpRxAux, UdpTxAux};
 
 use embedded_nal::SocketAddr;
 
@@ -140,6 +140,101 @@ impl<'a, const UDPCOUNT: usize> StackAccessor<'a, UDPCOUNT> {
 
         Ok(())
     }
+
+    /// Wrapper around sock_udp_recv_aux that accepts a null aux pointer
+    fn receive_raw(
+        socket: &mut UdpSocket<'a>,
+        buffer: &mut [u8],
+        aux: *mut riot_sys::sock_udp_aux_rx_t,
+    ) -> Result<(usize, SocketAddr), nb::Error<NumericError>> {
+        let socket = socket.access()?;
+
+        let mut remote = MaybeUninit::uninit();
+
+        let read = (unsafe {
+            riot_sys::sock_udp_recv_aux(
+                crate::inline_cast_mut(&mut *socket as *mut _),
+                buffer.as_mut_ptr() as _,
+                buffer.len().try_into().unwrap(),
+                0,
+                crate::inline_cast_mut(remote.as_mut_ptr() as *mut _),
+                crate::inline_cast_mut(aux),
+            )
+        })
+        .negative_to_error()
+        .map(|e| e as usize)
+        .map_err(|e| e.again_is_wouldblock());
+
+        // unsafe: Set by C function
+        let remote = UdpEp(unsafe { remote.assume_init() });
+
+        Ok((read?, remote.into()))
+    }
+
+    /// Wrapper around sock_udp_send_aux that accepts null remote and aux pointers
+    fn send_raw(
+        socket: &mut UdpSocket<'a>,
+        buffer: &[u8],
+        remote: *const riot_sys::sock_udp_ep_t,
+        aux: *mut riot_sys::sock_udp_aux_tx_t,
+    ) -> Result<(), nb::Error<NumericError>> {
+        let socket = socket.access()?;
+
+        (unsafe {
+            riot_sys::inline::sock_udp_send_aux(
+                crate::inline_cast_mut(&mut *socket as *mut _),
+                buffer.as_ptr() as _,
+                buffer.len().try_into().unwrap(),
+                crate::inline_cast(remote),
+                crate::inline_cast_mut(aux),
+            )
+        })
+        .negative_to_error()
+        .map(|_| ())
+        // Sending never blocks in RIOT sockets
+        .map_err(|e| nb::Error::Other(e))
+    }
+
+    /// Like [`receive`](embedded_nal::UdpClientStack::receive), but also populating the
+    /// auxiliary data requested in `aux` (eg. the reception time stamp or the local address)
+    #[doc(alias = "sock_udp_recv_aux")]
+    pub fn receive_with_aux(
+        &mut self,
+        socket: &mut UdpSocket<'a>,
+        buffer: &mut [u8],
+        aux: &mut UdpRxAux,
+    ) -> Result<(usize, SocketAddr), nb::Error<NumericError>> {
+        Self::receive_raw(socket, buffer, aux.prepare())
+    }
+
+    /// Like [`send`](embedded_nal::UdpClientStack::send), but with auxiliary data
+    ///
+    /// Once this returns, any time stamp requested in `aux` is available in there.
+    #[doc(alias = "sock_udp_send_aux")]
+    pub fn send_with_aux(
+        &mut self,
+        socket: &mut UdpSocket<'a>,
+        buffer: &[u8],
+        aux: &mut UdpTxAux,
+    ) -> Result<(), nb::Error<NumericError>> {
+        Self::send_raw(socket, buffer, core::ptr::null(), aux.prepare())
+    }
+
+    /// Like [`send_to`](embedded_nal::UdpFullStack::send_to), but with auxiliary data
+    ///
+    /// Once this returns, any time stamp requested in `aux` is available in there.
+    #[doc(alias = "sock_udp_send_aux")]
+    pub fn send_to_with_aux(
+        &mut self,
+        socket: &mut UdpSocket<'a>,
+        remote: SocketAddr,
+        buffer: &[u8],
+        aux: &mut UdpTxAux,
+    ) -> Result<(), nb::Error<NumericError>> {
+        let remote: UdpEp = remote.into();
+        let remote: &riot_sys::sock_udp_ep_t = remote.as_ref();
+        Self::send_raw(socket, buffer, remote, aux.prepare())
+    }
 }
 
 impl<'a, const UDPCOUNT: usize> embedded_nal::UdpClientStack for StackAccessor<'a, UDPCOUNT> {
@@ -173,47 +268,14 @@ impl<'a, const UDPCOUNT: usize> embedded_nal::UdpClientStack for StackAccessor<'
         socket: &mut Self::UdpSocket,
         buffer: &[u8],
     ) -> Result<(), nb::Error<Self::Error>> {
-        let socket = socket.access()?;
-
-        (unsafe {
-            riot_sys::sock_udp_send(
-                crate::inline_cast_mut(&mut *socket as *mut _),
-                buffer.as_ptr() as _,
-                buffer.len().try_into().unwrap(),
-                0 as *const _,
-            )
-        })
-        .negative_to_error()
-        .map(|_| ())
-        // Sending never blocks in RIOT sockets
-        .map_err(|e| nb::Error::Other(e))
+        Self::send_raw(socket, buffer, core::ptr::null(), core::ptr::null_mut())
     }
     fn receive(
         &mut self,
         socket: &mut Self::UdpSocket,
         buffer: &mut [u8],
     ) -> Result<(usize, SocketAddr), nb::Error<Self::Error>> {
-        let socket = socket.access()?;
-
-        let mut remote = MaybeUninit::uninit();
-
-        let read = (unsafe {
-            riot_sys::sock_udp_recv(
-                crate::inline_cast_mut(&mut *socket as *mut _),
-                buffer.as_mut_ptr() as _,
-                buffer.len().try_into().unwrap(),
-                0,
-                crate::inline_cast_mut(remote.as_mut_ptr() as *mut _),
-            )
-        })
-        .negative_to_error()
-        .map(|e| e as usize)
-        .map_err(|e| e.again_is_wouldblock());
-
-        // unsafe: Set by C function
-        let remote = UdpEp(unsafe { remote.assume_init() });
-
-        Ok((read?, remote.into()))
+        Self::receive_raw(socket, buffer, core::ptr::null_mut())
     }
 
     fn close(&mut self, mut socket: Self::UdpSocket) -> Result<(), Self::Error> {
@@ -235,21 +297,9 @@ impl<'a, const UDPCOUNT: usize> embedded_nal::UdpFullStack for StackAccessor<'a,
         remote: SocketAddr,
         buffer: &[u8],
     ) -> Result<(), nb::Error<Self::Error>> {
-        let socket = handle.access()?;
-
         let remote: UdpEp = remote.into();
+        let remote: &riot_sys::sock_udp_ep_t = remote.as_ref();
 
-        (unsafe {
-            riot_sys::sock_udp_send(
-                crate::inline_cast_mut(&mut *socket as *mut _),
-                buffer.as_ptr() as _,
-                buffer.len().try_into().unwrap(),
-                remote.as_ref(),
-            )
-        })
-        .negative_to_error()
-        .map(|_| ())
-        // Sending never blocks in RIOT sockets
-        .map_err(|e| nb::Error::Other(e))
+        Self::send_raw(handle, buffer, remote, core::ptr::null_mut())
     }
 }
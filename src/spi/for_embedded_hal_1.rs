@@ -203,6 +203,28 @@ impl embedded_hal::spi::SpiBus for SpiBus {
     }
 }
 
+// RIOT's SPI API has no non-blocking transfers, so these perform the transfer synchronously inside
+// the first poll. This still allows using async driver crates; other tasks are just not run while
+// the bus is busy.
+#[cfg(feature = "with_embedded_hal_async")]
+impl embedded_hal_async::spi::SpiBus for SpiBus {
+    async fn read(&mut self, words: &mut [u8]) -> Result<(), Infallible> {
+        embedded_hal::spi::SpiBus::read(self, words)
+    }
+    async fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+        embedded_hal::spi::SpiBus::write(self, words)
+    }
+    async fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Infallible> {
+        embedded_hal::spi::SpiBus::transfer(self, read, write)
+    }
+    async fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Infallible> {
+        embedded_hal::spi::SpiBus::transfer_in_place(self, words)
+    }
+    async fn flush(&mut self) -> Result<(), Infallible> {
+        embedded_hal::spi::SpiBus::flush(self)
+    }
+}
+
 impl SpiDevice {
     /// and its CS GPIO pin
     #[cfg(riot_module_periph_gpio)]
@@ -231,6 +253,14 @@ impl embedded_hal::spi::SpiDevice for SpiDevice {
     }
 }
 
+#[cfg(feature = "with_embedded_hal_async")]
+impl embedded_hal_async::spi::SpiDevice for SpiDevice {
+    async fn transaction(&mut self, ops: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        transaction(&self.bus, self.cs, ops);
+        Ok(())
+    }
+}
+
 fn transaction(bus: &SpiBus, cs: riot_sys::spi_cs_t, ops: &mut [Operation<'_, u8>]) {
     unsafe { riot_sys::spi_acquire(bus.bus, cs, bus.mode, bus.clk) };
     let len = ops.len();
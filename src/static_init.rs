@@ -0,0 +1,164 @@
+//! Containers for objects that need to live for the rest of the program
+//!
+//! Many RIOT objects (thread stacks, event queues, socket structs, timers) are referenced by the
+//! OS after they have been set up, and thus need to be `'static`. The cells in this module hand out
+//! a `&'static mut` to their content exactly once, so neither `static mut` nor a heap are needed:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! use riot_wrappers::static_init::ConstStaticCell;
+//!
+//! static STACK: ConstStaticCell<[u8; 1024]> = ConstStaticCell::zeroed();
+//!
+//! # fn f() {
+//! let stack: &'static mut [u8; 1024] = STACK.take();
+//! # }
+//! ```
+//!
+//! RIOT structs for which all-zero is a valid initial state implement [ZeroInit], and can thus be
+//! created in a const context through [`ConstStaticCell::zeroed()`].
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Set the flag, and return whether it was set before
+///
+/// This does not use atomic swap, which is unavailable on some platforms.
+pub(crate) fn claim(flag: &AtomicBool) -> bool {
+    crate::interrupt::free(|_| {
+        let was_taken = flag.load(Ordering::Relaxed);
+        flag.store(true, Ordering::Relaxed);
+        was_taken
+    })
+}
+
+/// A cell that is populated at runtime, and then provides a `&'static mut` to its content
+pub struct StaticCell<T> {
+    taken: AtomicBool,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// unsafe: The value is only accessed through the single reference handed out, and that is
+// returned to the thread that moved the value in. (The reference itself is only Send if T is).
+unsafe impl<T> Sync for StaticCell<T> {}
+
+impl<T> StaticCell<T> {
+    pub const fn new() -> Self {
+        Self {
+            taken: AtomicBool::new(false),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Store the value in the cell, and return a reference to it
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the cell was initialized before.
+    pub fn init(&'static self, value: T) -> &'static mut T {
+        self.try_init(value)
+            .unwrap_or_else(|_| panic!("StaticCell initialized twice"))
+    }
+
+    /// Store the value in the cell, and return a reference to it
+    ///
+    /// If the cell was initialized before, the value is returned as an error.
+    pub fn try_init(&'static self, value: T) -> Result<&'static mut T, T> {
+        if claim(&self.taken) {
+            return Err(value);
+        }
+        // unsafe: The flag ensures that this is the only reference ever created.
+        Ok(unsafe { (*self.value.get()).write(value) })
+    }
+}
+
+impl<T> Default for StaticCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A cell that is populated at build time, and provides a `&'static mut` to its content once
+pub struct ConstStaticCell<T> {
+    taken: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// unsafe: The value is only accessed through the single reference handed out. Having been created
+// at build time, it was not used by any thread before, so no Send bound is needed for the thread
+// that takes it. (The reference itself is only Send if T is).
+unsafe impl<T> Sync for ConstStaticCell<T> {}
+
+impl<T> ConstStaticCell<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            taken: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Obtain a reference to the content
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the content was taken before.
+    pub fn take(&'static self) -> &'static mut T {
+        self.try_take().expect("ConstStaticCell taken twice")
+    }
+
+    /// Obtain a reference to the content, or None if it was taken before
+    pub fn try_take(&'static self) -> Option<&'static mut T> {
+        if claim(&self.taken) {
+            return None;
+        }
+        // unsafe: The flag ensures that this is the only reference ever created.
+        Some(unsafe { &mut *self.value.get() })
+    }
+}
+
+impl<T: ZeroInit> ConstStaticCell<T> {
+    /// Create a cell containing the all-zero value of the type
+    pub const fn zeroed() -> Self {
+        // unsafe: Guaranteed by the ZeroInit trait
+        Self::new(unsafe { core::mem::zeroed() })
+    }
+}
+
+/// Types for which the all-zero bit pattern is a valid (typically the initial, unused) state
+///
+/// # Safety
+///
+/// Implementing this asserts that [`core::mem::zeroed()`] produces a valid value of the type, and
+/// that this value is suitable as the initial state that RIOT expects for the object before
+/// passing it into its setup function (or, for types without one, before first use).
+pub unsafe trait ZeroInit {}
+
+// unsafe: Plain data
+unsafe impl<const N: usize> ZeroInit for [u8; N] {}
+
+// unsafe: EVENT_QUEUE_INIT_DETACHED and unqueued events are all-zero
+#[cfg(riot_module_event)]
+unsafe impl ZeroInit for riot_sys::event_queue_t {}
+#[cfg(riot_module_event)]
+unsafe impl ZeroInit for riot_sys::event_t {}
+
+// unsafe: Timers are all-zero when not set
+#[cfg(riot_module_ztimer)]
+unsafe impl ZeroInit for riot_sys::ztimer_t {}
+
+// unsafe: MUTEX_INIT is all-zero
+unsafe impl ZeroInit for riot_sys::mutex_t {}
+
+// unsafe: Messages are plain data
+#[cfg(riot_module_core_msg)]
+unsafe impl ZeroInit for riot_sys::msg_t {}
+
+// unsafe: Sockets are fully initialized by their create functions, which take zeroed storage
+#[cfg(riot_module_sock_udp)]
+unsafe impl ZeroInit for riot_sys::sock_udp_t {}
+#[cfg(riot_module_sock_tcp)]
+unsafe impl ZeroInit for riot_sys::sock_tcp_t {}
+#[cfg(riot_module_sock_tcp)]
+unsafe impl ZeroInit for riot_sys::sock_tcp_queue_t {}
@@ -0,0 +1,191 @@
+//! Resource usage statistics as structured data
+//!
+//! This gives programmatic access to the numbers RIOT's `ps` and `heap` shell commands show, so
+//! that they can be reported as telemetry:
+//!
+//! * With the `malloc_monitor` module, [`heap()`] reports the heap usage.
+//! * With the `schedstatistics` module, [`ThreadStats`] report how often and how long threads
+//!   ran, and a [`Reporter`] turns these into per-interval numbers.
+//!
+//! (Heap statistics of the TLSF allocator are only available through its `heap` shell command).
+
+#[cfg(riot_module_schedstatistics)]
+use crate::thread::KernelPID;
+
+/// Heap usage, as tracked by `malloc_monitor`
+#[cfg(riot_module_malloc_monitor)]
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct HeapStats {
+    /// Bytes currently allocated
+    pub current: usize,
+    /// Maximum number of bytes that were allocated at the same time since startup (or since the
+    /// last call to [`reset_heap_high_watermark()`])
+    pub high_watermark: usize,
+}
+
+/// Obtain the current heap usage
+#[cfg(riot_module_malloc_monitor)]
+#[doc(alias = "malloc_monitor_get_usage_current")]
+pub fn heap() -> HeapStats {
+    // unsafe: C API makes no requirements
+    unsafe {
+        HeapStats {
+            current: riot_sys::malloc_monitor_get_usage_current() as _,
+            high_watermark: riot_sys::malloc_monitor_get_usage_high_watermark() as _,
+        }
+    }
+}
+
+/// Reset the heap usage high watermark to the current usage
+#[cfg(riot_module_malloc_monitor)]
+#[doc(alias = "malloc_monitor_reset_high_watermark")]
+pub fn reset_heap_high_watermark() {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::malloc_monitor_reset_high_watermark() }
+}
+
+/// Scheduling statistics of a thread, as tracked by `schedstatistics`
+///
+/// The run time of a thread is only accounted for when it is switched out; the numbers for the
+/// currently running thread are thus lagging.
+#[cfg(riot_module_schedstatistics)]
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct ThreadStats {
+    pub pid: KernelPID,
+    /// Number of times the thread was scheduled
+    pub schedules: u32,
+    /// Time the thread was running, in microseconds
+    pub runtime_us: u64,
+}
+
+#[cfg(riot_module_schedstatistics)]
+impl ThreadStats {
+    /// Obtain the statistics of the thread with the given PID
+    ///
+    /// This returns None if there is no such thread.
+    pub fn for_thread(pid: KernelPID) -> Option<Self> {
+        pid.status().ok()?;
+        let index: riot_sys::kernel_pid_t = pid.into();
+        let index = index as usize;
+        // Read in a critical section as the scheduler updates the 64bit value from interrupts.
+        let (schedules, runtime_us) = crate::interrupt::free(|_| {
+            // unsafe: Reading a field of a global that is only written with interrupts disabled;
+            // the index is in bounds as sched_pidlist has an entry for every valid PID.
+            unsafe {
+                let entry = core::ptr::addr_of!(riot_sys::sched_pidlist[index]);
+                ((*entry).schedules, (*entry).runtime_us)
+            }
+        });
+        Some(Self {
+            pid,
+            schedules: schedules as _,
+            runtime_us,
+        })
+    }
+
+    /// Statistics of all threads that currently exist
+    pub fn all() -> impl Iterator<Item = Self> {
+        KernelPID::all_pids().filter_map(Self::for_thread)
+    }
+
+    /// This thread's share of a total run time, in thousandths
+    ///
+    /// The total is typically the sum of all threads' run times, or the length of an interval.
+    pub fn runtime_permille(&self, total_us: u64) -> u32 {
+        if total_us == 0 {
+            return 0;
+        }
+        (self.runtime_us.saturating_mul(1000) / total_us) as u32
+    }
+}
+
+/// Sum of all threads' run times in microseconds (which is the run time of the system, minus
+/// time spent in interrupts that did not switch threads)
+#[cfg(riot_module_schedstatistics)]
+pub fn total_runtime_us() -> u64 {
+    ThreadStats::all().map(|t| t.runtime_us).sum()
+}
+
+/// Statistics of the idle thread, if there is one
+///
+/// As the idle thread is only identified by its name, this requires `DEVELHELP`.
+#[cfg(riot_module_schedstatistics)]
+pub fn idle() -> Option<ThreadStats> {
+    ThreadStats::all().find(|t| t.pid.get_name() == Some("idle"))
+}
+
+/// Helper for reporting scheduler statistics per interval
+///
+/// Every time [`.sample()`](Reporter::sample) is called, the statistics accumulated since the
+/// previous call are reported. This can be called from any periodic mechanism, or be run in a
+/// thread of its own through [`.run_forever()`](Reporter::run_forever).
+#[cfg(riot_module_schedstatistics)]
+pub struct Reporter {
+    // Indexed by PID; threads are assumed to be the same thread as long as their PID is in use.
+    // Entries of PIDs that are not in use are reset, so that a new thread on the PID starts from 0.
+    previous: [(u32, u64); riot_sys::KERNEL_PID_LAST as usize + 1],
+}
+
+#[cfg(riot_module_schedstatistics)]
+impl Reporter {
+    /// Create a reporter whose first sample covers the time since startup
+    pub fn new() -> Self {
+        Self {
+            previous: [(0, 0); riot_sys::KERNEL_PID_LAST as usize + 1],
+        }
+    }
+
+    /// Call `report` with each existing thread's statistics for the time since the last sample
+    ///
+    /// Along with each thread's statistics, the interval's total run time is passed, as is
+    /// needed for [`.runtime_permille()`](ThreadStats::runtime_permille).
+    pub fn sample(&mut self, mut report: impl FnMut(&ThreadStats, u64)) {
+        let mut total: u64 = 0;
+        let mut current = [None; riot_sys::KERNEL_PID_LAST as usize + 1];
+        for stats in ThreadStats::all() {
+            let index: riot_sys::kernel_pid_t = stats.pid.into();
+            let index = index as usize;
+            let (schedules, runtime_us) = self.previous[index];
+            let delta = ThreadStats {
+                pid: stats.pid,
+                // Saturating in case the PID was reused by a new thread within the interval
+                schedules: stats.schedules.saturating_sub(schedules),
+                runtime_us: stats.runtime_us.saturating_sub(runtime_us),
+            };
+            total = total.saturating_add(delta.runtime_us);
+            current[index] = Some(delta);
+            self.previous[index] = (stats.schedules, stats.runtime_us);
+        }
+        for (previous, current) in self.previous.iter_mut().zip(current.iter()) {
+            if current.is_none() {
+                *previous = (0, 0);
+            }
+        }
+        for delta in current.iter().flatten() {
+            report(delta, total);
+        }
+    }
+
+    /// Sample statistics once per interval, forever
+    #[cfg(riot_module_ztimer)]
+    pub fn run_forever<const HZ: u32>(
+        mut self,
+        clock: crate::thread::ValueInThread<crate::ztimer::Clock<HZ>>,
+        interval: crate::ztimer::Ticks<HZ>,
+        mut report: impl FnMut(&ThreadStats, u64),
+    ) -> ! {
+        loop {
+            clock.sleep(interval);
+            self.sample(&mut report);
+        }
+    }
+}
+
+#[cfg(riot_module_schedstatistics)]
+impl Default for Reporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,6 +1,7 @@
 //! Wrappers for the [stdio](https://doc.riot-os.org/group__sys__stdio.html)
 
 use core::intrinsics::transmute;
+use core::sync::atomic::{AtomicBool, AtomicU16, Ordering::Relaxed};
 use riot_sys::{stdio_read, stdio_write};
 
 use crate::error::NegativeErrorExt;
@@ -43,6 +44,223 @@ impl Stdio {
             .negative_to_error()
             .map(|bytes_read| &mut buffer[..bytes_read as usize])
     }
+
+    fn write_raw(&mut self, data: &[u8]) {
+        // Like in the [core::fmt::Write] implementation, short writes are not expected.
+        let _ = unsafe { stdio_write(transmute(data.as_ptr()), data.len() as _) };
+    }
+
+    /// Read a line of input into the buffer, and return it without the line terminator
+    ///
+    /// Lines can be terminated by CR, LF or CRLF. Backspace (BS or DEL) removes the last character
+    /// from the line. If `echo` is set, typed characters are echoed back, as the shell does on
+    /// most terminals.
+    pub fn read_line<'a>(
+        &mut self,
+        buffer: &'a mut [u8],
+        echo: bool,
+    ) -> Result<&'a str, ReadLineError> {
+        let mut len = 0;
+        loop {
+            let mut c = [0];
+            if let Ok(pending) = u8::try_from(PENDING_BYTE.load(Relaxed)) {
+                PENDING_BYTE.store(NO_PENDING_BYTE, Relaxed);
+                c[0] = pending;
+            } else if self
+                .read_raw(&mut c)
+                .map_err(ReadLineError::Stdio)?
+                .is_empty()
+            {
+                if len == 0 {
+                    return Err(ReadLineError::EndOfInput);
+                }
+                break;
+            }
+            match c[0] {
+                b'\n' if len == 0 && LINE_ENDED_IN_CR.load(Relaxed) => {
+                    LINE_ENDED_IN_CR.store(false, Relaxed);
+                }
+                b'\r' | b'\n' => {
+                    LINE_ENDED_IN_CR.store(c[0] == b'\r', Relaxed);
+                    if echo {
+                        self.write_raw(b"\r\n");
+                    }
+                    break;
+                }
+                0x08 | 0x7f => {
+                    LINE_ENDED_IN_CR.store(false, Relaxed);
+                    if len == 0 {
+                        continue;
+                    }
+                    // Remove a whole UTF-8 sequence
+                    while len > 0 {
+                        len -= 1;
+                        if buffer[len] & 0xc0 != 0x80 {
+                            break;
+                        }
+                    }
+                    if echo {
+                        self.write_raw(b"\x08 \x08");
+                    }
+                }
+                byte => {
+                    LINE_ENDED_IN_CR.store(false, Relaxed);
+                    let Some(slot) = buffer.get_mut(len) else {
+                        PENDING_BYTE.store(byte.into(), Relaxed);
+                        return Err(ReadLineError::TooLong);
+                    };
+                    *slot = byte;
+                    len += 1;
+                    if echo {
+                        self.write_raw(&c);
+                    }
+                }
+            }
+        }
+        core::str::from_utf8(&buffer[..len]).map_err(|_| ReadLineError::InvalidUtf8)
+    }
+}
+
+/// Set when [`Stdio::read_line()`] ended at a CR, so that the LF of a CRLF line ending does not
+/// produce an empty line in the next call
+static LINE_ENDED_IN_CR: AtomicBool = AtomicBool::new(false);
+
+/// Byte that was read by [`Stdio::read_line()`] but did not fit in the buffer any more, and is
+/// processed first in the next call
+static PENDING_BYTE: AtomicU16 = AtomicU16::new(NO_PENDING_BYTE);
+/// Value of [PENDING_BYTE] when there is no pending byte
+const NO_PENDING_BYTE: u16 = u16::MAX;
+
+/// Error type of [`Stdio::read_line()`]
+#[derive(Debug)]
+pub enum ReadLineError {
+    /// Reading from stdio failed
+    Stdio(crate::error::NumericError),
+    /// Input ended before any character of the line was read
+    EndOfInput,
+    /// The line did not fit in the buffer; the rest of the line is still pending
+    ///
+    /// The next call to [`Stdio::read_line()`] continues with the first character that did not
+    /// fit.
+    TooLong,
+    /// The line was read completely, but is not valid UTF-8
+    InvalidUtf8,
+}
+
+#[cfg(feature = "with_embedded_io")]
+impl embedded_io::ErrorType for Stdio {
+    type Error = crate::error::NumericError;
+}
+
+#[cfg(feature = "with_embedded_io")]
+impl embedded_io::Read for Stdio {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        Ok(self.read_raw(buf)?.len())
+    }
+}
+
+#[cfg(feature = "with_embedded_io")]
+impl embedded_io::Write for Stdio {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        unsafe { stdio_write(transmute(buf.as_ptr()), buf.len() as _) }
+            .negative_to_error()
+            .map(|written| written as usize)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A transport that RIOT's stdio can be provided by
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// `stdio_uart`: A UART, typically the one connected to the board's debug adapter
+    Uart,
+    /// `stdio_cdc_acm`: USB CDC ACM on the device's own USB port
+    CdcAcm,
+    /// `stdio_rtt`: SEGGER RTT through the debug adapter
+    Rtt,
+    /// `stdio_semihosting`: Semihosting through the debugger
+    Semihosting,
+    /// `stdio_telnet`: A telnet connection on the network (see the `telnet` module)
+    Telnet,
+    /// `stdio_native`: The host process's stdio on native
+    Native,
+    /// `stdio_null`: Output is discarded, and no input is received
+    Null,
+}
+
+/// The backends stdio is provided by in this build
+///
+/// This is typically a single backend, but can be several when stdio is dispatched (as with
+/// `stdio_dispatch`), or none at all.
+pub const BACKENDS: &[Backend] = &[
+    #[cfg(riot_module_stdio_uart)]
+    Backend::Uart,
+    #[cfg(riot_module_stdio_cdc_acm)]
+    Backend::CdcAcm,
+    #[cfg(riot_module_stdio_rtt)]
+    Backend::Rtt,
+    #[cfg(riot_module_stdio_semihosting)]
+    Backend::Semihosting,
+    #[cfg(riot_module_stdio_telnet)]
+    Backend::Telnet,
+    #[cfg(riot_module_stdio_native)]
+    Backend::Native,
+    #[cfg(riot_module_stdio_null)]
+    Backend::Null,
+];
+
+impl Backend {
+    /// The backend stdio is provided by, or the first one if there are several
+    pub fn active() -> Option<Self> {
+        BACKENDS.first().copied()
+    }
+
+    /// Whether stdio over this backend is only available while a client is attached (eg. a
+    /// debugger or network connection) rather than being available right from boot
+    ///
+    /// Applications can use this to decide whether to wait before printing important output.
+    pub fn needs_client(self) -> bool {
+        matches!(
+            self,
+            Backend::CdcAcm | Backend::Rtt | Backend::Semihosting | Backend::Telnet
+        )
+    }
+}
+
+/// Control of the telnet server that provides stdio with `stdio_telnet`
+#[cfg(riot_module_telnet)]
+pub mod telnet {
+    use crate::error::{NegativeErrorExt, NumericError};
+
+    /// Start listening for a telnet client
+    ///
+    /// The server accepts a single client at a time on `CONFIG_TELNET_PORT`. (With
+    /// `auto_init_telnet`, this is already done at startup.)
+    #[doc(alias = "telnet_server_start")]
+    pub fn start() -> Result<(), NumericError> {
+        // unsafe: C API makes no requirements
+        unsafe { riot_sys::telnet_server_start() }.negative_to_error()?;
+        Ok(())
+    }
+
+    /// Close the connection to the current client
+    ///
+    /// The server keeps listening, so a new client can connect afterwards.
+    #[doc(alias = "telnet_server_disconnect")]
+    pub fn disconnect() {
+        // unsafe: C API makes no requirements
+        unsafe { riot_sys::telnet_server_disconnect() }
+    }
 }
 
 // Copied and adapted from Rust 1.32.0
@@ -0,0 +1,181 @@
+//! Helpers for on-target test applications from RIOT's [test_utils]
+//!
+//! * [`interactive_sync()`] waits for the test runner to be ready, as RIOT's test scripts expect
+//!   with `test_utils_interactive_sync`.
+//! * [`Turo`] produces test results in the machine readable format selected through the
+//!   `test_utils_result_output_*` modules (eg. JSON), as evaluated by RIOT's test tooling.
+//!
+//! [test_utils]: https://doc.riot-os.org/group__test__utils.html
+
+#[cfg(riot_module_test_utils_result_output)]
+use core::ffi::CStr;
+
+/// Wait until the test runner has synchronized with the application
+///
+/// This blocks until `s` is received on stdio; all other input is ignored.
+#[cfg(riot_module_test_utils_interactive_sync)]
+#[doc(alias = "test_utils_interactive_sync")]
+pub fn interactive_sync() {
+    // unsafe: C API makes no requirements
+    unsafe { riot_sys::test_utils_interactive_sync() }
+}
+
+/// Writer of Test Utils Result Output (TURO)
+///
+/// Results are written on stdio as they are produced, forming a single container. Output
+/// methods can be chained:
+///
+/// ```
+/// # #![no_std]
+/// # #![no_main]
+/// # use riot_wrappers::test_utils::Turo;
+/// # fn f() {
+/// let mut turo = Turo::new();
+/// turo.container_open()
+///     .dict_open()
+///     .dict_key(c"result")
+///     .s32(42)
+///     .dict_close();
+/// turo.container_close(0);
+/// # }
+/// ```
+#[cfg(riot_module_test_utils_result_output)]
+pub struct Turo(riot_sys::turo_t);
+
+#[cfg(riot_module_test_utils_result_output)]
+macro_rules! turo_value {
+    ($(#[$meta:meta])* $name:ident, $t:ty, $fn:ident) => {
+        $(#[$meta])*
+        pub fn $name(&mut self, value: $t) -> &mut Self {
+            // unsafe: C API as documented
+            unsafe { riot_sys::$fn(&mut self.0, value) };
+            self
+        }
+    };
+}
+
+#[cfg(riot_module_test_utils_result_output)]
+impl Turo {
+    /// Create a result output context
+    #[doc(alias = "turo_init")]
+    pub fn new() -> Self {
+        let mut ctx = core::mem::MaybeUninit::uninit();
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_init(ctx.as_mut_ptr()) };
+        // unsafe: Initialized by turo_init
+        Self(unsafe { ctx.assume_init() })
+    }
+
+    /// Start the output; this needs to be called first
+    #[doc(alias = "turo_container_open")]
+    pub fn container_open(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_container_open(&mut self.0) };
+        self
+    }
+
+    /// Finish the output with an exit status (0 indicating success)
+    #[doc(alias = "turo_container_close")]
+    pub fn container_close(mut self, exit_status: i32) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_container_close(&mut self.0, exit_status as _) };
+    }
+
+    turo_value!(
+        /// Output a signed integer
+        s32,
+        i32,
+        turo_s32
+    );
+    turo_value!(
+        /// Output an unsigned integer
+        u32,
+        u32,
+        turo_u32
+    );
+    turo_value!(
+        /// Output a signed integer in decimal
+        s64,
+        i64,
+        turo_s64_dec
+    );
+    turo_value!(
+        /// Output an unsigned integer in decimal
+        u64,
+        u64,
+        turo_u64_dec
+    );
+    turo_value!(
+        /// Output an unsigned integer in hex
+        u64_hex,
+        u64,
+        turo_u64_hex
+    );
+    turo_value!(
+        /// Output a floating point number
+        float,
+        f32,
+        turo_float
+    );
+    turo_value!(
+        /// Output a boolean
+        bool,
+        bool,
+        turo_bool
+    );
+
+    /// Output a string
+    #[doc(alias = "turo_string")]
+    pub fn string(&mut self, value: &CStr) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_string(&mut self.0, value.as_ptr() as _) };
+        self
+    }
+
+    /// Start an array; its items are output next
+    #[doc(alias = "turo_array_open")]
+    pub fn array_open(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_array_open(&mut self.0) };
+        self
+    }
+
+    /// End an array
+    #[doc(alias = "turo_array_close")]
+    pub fn array_close(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_array_close(&mut self.0) };
+        self
+    }
+
+    /// Start a dictionary; its entries are output next, each as a key followed by a value
+    #[doc(alias = "turo_dict_open")]
+    pub fn dict_open(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_dict_open(&mut self.0) };
+        self
+    }
+
+    /// Output the key of a dictionary entry
+    #[doc(alias = "turo_dict_key")]
+    pub fn dict_key(&mut self, key: &CStr) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_dict_key(&mut self.0, key.as_ptr() as _) };
+        self
+    }
+
+    /// End a dictionary
+    #[doc(alias = "turo_dict_close")]
+    pub fn dict_close(&mut self) -> &mut Self {
+        // unsafe: C API as documented
+        unsafe { riot_sys::turo_dict_close(&mut self.0) };
+        self
+    }
+}
+
+#[cfg(riot_module_test_utils_result_output)]
+impl Default for Turo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -6,7 +6,7 @@ use crate::helpers::PointerToCStr;
 
 /// Offloaded tools for creation
 mod creation;
-pub use creation::{scope, spawn, CountedThread, CountingThreadScope};
+pub use creation::{scope, spawn, CountedThread, CountingThreadScope, TrackedThread};
 
 /// Wrapper around a valid (not necessarily running, but in-range) [riot_sys::kernel_pid_t] that
 /// provides access to thread details and signaling.
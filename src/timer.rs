@@ -0,0 +1,195 @@
+//! Access to [RIOT's low-level timer peripherals](https://doc.riot-os.org/group__drivers__periph__timer.html)
+//!
+//! Most applications are better served by [ztimer](crate::ztimer), which multiplexes the hardware
+//! timers and handles wrap-arounds and low power modes. This module is for the cases where a
+//! hardware timer is to be used directly (eg. for precise timing of periodic actions). Note that
+//! timers used by ztimer (typically `TIMER_DEV(0)`) must not be used here.
+//!
+//! The callback is run in an interrupt whenever a channel's compare value is matched.
+
+use core::marker::PhantomData;
+
+use riot_sys::libc::{c_int, c_uint, c_void};
+use riot_sys::tim_t;
+
+use crate::error::{NegativeErrorExt, NumericError};
+
+/// An initialized hardware timer
+///
+/// The lifetime `'cb` indicates how long the callback lives; for many cases, that is `'static`.
+#[derive(Debug)]
+pub struct Timer<'cb> {
+    dev: tim_t,
+    // Needed to re-initialize the timer without the callback when dropping
+    freq: u32,
+    // Invariant, so that a scoped timer can not be swapped out against a longer-lived one inside
+    // `new_scoped`'s main function (and then, eg., be leaked with its callback still set)
+    _phantom: PhantomData<fn(&'cb ()) -> &'cb ()>,
+}
+
+impl Timer<'static> {
+    /// Initialize the timer with the given index to run at the given frequency (in Hz), calling
+    /// the callback with the channel number whenever a channel fires
+    ///
+    /// The timer starts running right away. Initialization fails if the timer does not exist or
+    /// does not support the requested frequency.
+    #[doc(alias = "timer_init")]
+    pub fn new_with_static_cb<F>(
+        index: usize,
+        freq: u32,
+        callback: &'static mut F,
+    ) -> Result<Self, NumericError>
+    where
+        F: FnMut(u8) + Send + 'static,
+    {
+        // unsafe: The callback is static
+        unsafe { Self::construct(index, freq, callback) }
+    }
+
+    /// Initialize a timer like [`new_with_static_cb()`](Self::new_with_static_cb), and run a
+    /// `main` function while it is configured
+    ///
+    /// This is the scoped version that can be used with short-lived callbacks, such as closures
+    /// referencing local data. The timer is stopped when `main` returns.
+    pub fn new_scoped<F, Main, RMain>(
+        index: usize,
+        freq: u32,
+        callback: &mut F,
+        main: Main,
+    ) -> Result<RMain, NumericError>
+    where
+        F: FnMut(u8) + Send,
+        Main: for<'brand> FnOnce(&mut Timer<'brand>) -> RMain,
+    {
+        // unsafe: The callback is unset (by dropping the timer) before its reference expires. The
+        // timer can not be leaked by `main`, as it only gets a reference to it, and can not swap it
+        // out as the invariant brand makes it incompatible with any other timer.
+        let mut self_ = unsafe { Timer::construct(index, freq, callback) }?;
+        let result = (main)(&mut self_);
+        drop(self_);
+        Ok(result)
+    }
+}
+
+impl<'cb> Timer<'cb> {
+    /// Initialize the timer
+    ///
+    /// # Unsafety
+    ///
+    /// The caller must ensure that the returned Self is reliably dropped before the callback
+    /// becomes unavailable.
+    unsafe fn construct<F>(
+        index: usize,
+        freq: u32,
+        callback: &'cb mut F,
+    ) -> Result<Self, NumericError>
+    where
+        F: FnMut(u8) + Send + 'cb,
+    {
+        let dev = riot_sys::macro_TIMER_DEV(index as c_uint);
+        riot_sys::timer_init(
+            dev,
+            freq,
+            Some(Self::callback::<F>),
+            callback as *mut _ as *mut c_void,
+        )
+        .negative_to_error()?;
+        Ok(Self {
+            dev,
+            freq,
+            _phantom: PhantomData,
+        })
+    }
+
+    unsafe extern "C" fn callback<F>(arg: *mut c_void, channel: c_int)
+    where
+        F: FnMut(u8) + 'cb,
+    {
+        (*(arg as *mut F))(channel as u8)
+    }
+
+    /// Make the given channel fire `ticks` ticks from now
+    ///
+    /// The channel fires only once.
+    #[doc(alias = "timer_set")]
+    pub fn set(&mut self, channel: u8, ticks: u32) -> Result<(), NumericError> {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_set(self.dev, channel as _, ticks as _) }.negative_to_error()?;
+        Ok(())
+    }
+
+    /// Make the given channel fire when the counter reaches `value`
+    ///
+    /// The channel fires only once.
+    #[doc(alias = "timer_set_absolute")]
+    pub fn set_absolute(&mut self, channel: u8, value: u32) -> Result<(), NumericError> {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_set_absolute(self.dev, channel as _, value as _) }
+            .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Make the given channel fire every time the counter reaches `value`
+    ///
+    /// `flags` is a combination of `TIM_FLAG_RESET_ON_SET` and `TIM_FLAG_RESET_ON_MATCH`.
+    #[cfg(riot_module_periph_timer_periodic)]
+    #[doc(alias = "timer_set_periodic")]
+    pub fn set_periodic(&mut self, channel: u8, value: u32, flags: u8) -> Result<(), NumericError> {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_set_periodic(self.dev, channel as _, value as _, flags) }
+            .negative_to_error()?;
+        Ok(())
+    }
+
+    /// Stop the given channel from firing
+    #[doc(alias = "timer_clear")]
+    pub fn clear(&mut self, channel: u8) -> Result<(), NumericError> {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_clear(self.dev, channel as _) }.negative_to_error()?;
+        Ok(())
+    }
+
+    /// Read the current counter value
+    #[doc(alias = "timer_read")]
+    pub fn read(&self) -> u32 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_read(self.dev) as _ }
+    }
+
+    /// Resume the counter after it was stopped
+    #[doc(alias = "timer_start")]
+    pub fn start(&mut self) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_start(self.dev) }
+    }
+
+    /// Stop the counter
+    ///
+    /// While stopped, the counter does not advance, and no channels fire.
+    #[doc(alias = "timer_stop")]
+    pub fn stop(&mut self) {
+        // unsafe: C API as documented
+        unsafe { riot_sys::timer_stop(self.dev) }
+    }
+}
+
+impl<'cb> Drop for Timer<'cb> {
+    /// The timer is stopped, and its callback is replaced, so that the callback will not be called
+    /// any more.
+    ///
+    /// Merely stopping the timer would not suffice: A compare match that is already pending would
+    /// still call the callback once interrupts are handled.
+    fn drop(&mut self) {
+        unsafe extern "C" fn ignore(_arg: *mut c_void, _channel: c_int) {}
+
+        crate::interrupt::free(|_| {
+            // unsafe: C API as documented. Re-initializing with the same frequency succeeds (as it
+            // did before); it replaces callback and argument, which is done in a critical section
+            // so that no interrupt sees the old callback with the new argument.
+            unsafe {
+                riot_sys::timer_init(self.dev, self.freq, Some(ignore), core::ptr::null_mut());
+                riot_sys::timer_stop(self.dev);
+            }
+        });
+    }
+}
@@ -0,0 +1,223 @@
+//! Access to touch screens through [RIOT's generic touch device
+//! API](https://doc.riot-os.org/group__drivers__touch__dev.html)
+//!
+//! Touch devices are found by the same screen ID as the [display](crate::disp_dev) they cover,
+//! and report coordinates in the display's pixel space.
+//!
+//! Drivers signal touch activity from an interrupt. As reading the touch positions typically
+//! involves bus transfers, that signal is only used to wake a thread (eg. through thread flags or
+//! by posting to an [event](crate::event) queue); the thread then reads the device, and a
+//! [Tracker] turns the readings into press, move and release events:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::touch_dev::{TouchDevice, Tracker, Event};
+//! use core::sync::atomic::{AtomicBool, Ordering};
+//!
+//! static ACTIVITY: AtomicBool = AtomicBool::new(false);
+//!
+//! # fn f() {
+//! let mut touch = TouchDevice::by_screen_id(0).unwrap();
+//! let mut tracker = Tracker::new();
+//! let mut signal = || ACTIVITY.store(true, Ordering::Relaxed);
+//! touch.with_callback(&mut signal, |touch| loop {
+//!     // ... sleep until woken ...
+//!     if ACTIVITY.swap(false, Ordering::Relaxed) {
+//!         if let Some(Event::Pressed(point)) = tracker.update(touch) {
+//!             // ... react to a tap at point.x() / point.y() ...
+//!         }
+//!     }
+//! });
+//! # }
+//! ```
+
+use core::ffi::c_void;
+
+use riot_sys::{touch_dev_reg_t, touch_dev_t, touch_t};
+
+/// Position of a single touch, in display pixels
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+#[doc(alias = "touch_t")]
+pub struct Touch(touch_t);
+
+impl PartialEq for Touch {
+    fn eq(&self, other: &Self) -> bool {
+        (self.x(), self.y()) == (other.x(), other.y())
+    }
+}
+
+impl Eq for Touch {}
+
+impl core::fmt::Debug for Touch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Touch")
+            .field("x", &self.x())
+            .field("y", &self.y())
+            .finish()
+    }
+}
+
+impl Touch {
+    pub fn x(&self) -> u16 {
+        self.0.x
+    }
+
+    pub fn y(&self) -> u16 {
+        self.0.y
+    }
+}
+
+impl From<Touch> for embedded_graphics::geometry::Point {
+    fn from(touch: Touch) -> Self {
+        Self::new(touch.x().into(), touch.y().into())
+    }
+}
+
+/// A touch device registered with the touch device registry
+pub struct TouchDevice {
+    dev: *mut touch_dev_t,
+}
+
+impl TouchDevice {
+    /// Find a touch device by the screen ID of the display it belongs to
+    #[doc(alias = "touch_dev_reg_find_screen")]
+    pub fn by_screen_id(screen_id: u8) -> Option<Self> {
+        // unsafe: C API as documented
+        let reg: *mut touch_dev_reg_t = unsafe { riot_sys::touch_dev_reg_find_screen(screen_id) };
+        if reg.is_null() {
+            return None;
+        }
+        Some(Self {
+            // unsafe: Registry entries are static and valid
+            dev: unsafe { (*reg).dev },
+        })
+    }
+
+    /// Width of the touch area in pixels
+    #[doc(alias = "touch_dev_width")]
+    pub fn width(&self) -> u16 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::touch_dev_width(self.dev) }
+    }
+
+    /// Height of the touch area in pixels
+    #[doc(alias = "touch_dev_height")]
+    pub fn height(&self) -> u16 {
+        // unsafe: C API as documented
+        unsafe { riot_sys::touch_dev_height(self.dev) }
+    }
+
+    /// Read the current touches into the buffer, and return the populated part of it
+    ///
+    /// If more fingers touch the screen than there is space in the buffer, only the first are
+    /// reported.
+    #[doc(alias = "touch_dev_touches")]
+    pub fn touches<'b>(&mut self, buffer: &'b mut [Touch]) -> &'b [Touch] {
+        // unsafe: C API as documented; Touch is a transparent wrapper.
+        let count = unsafe {
+            riot_sys::touch_dev_touches(
+                self.dev,
+                buffer.as_mut_ptr() as *mut touch_t,
+                buffer.len() as _,
+            )
+        } as usize;
+        &buffer[..count.min(buffer.len())]
+    }
+
+    /// Read the position of the first touch, if any
+    pub fn touch(&mut self) -> Option<Touch> {
+        let mut buffer = [Touch(touch_t::default())];
+        self.touches(&mut buffer).first().copied()
+    }
+
+    /// Have `callback` called on touch activity while `main` runs
+    ///
+    /// The callback is called in interrupt context; it should only wake up a thread that then
+    /// reads the device.
+    #[doc(alias = "touch_dev_set_touch_event_callback")]
+    pub fn with_callback<F, M, R>(&mut self, callback: &mut F, main: M) -> R
+    where
+        F: FnMut() + Send,
+        M: FnOnce(&mut Self) -> R,
+    {
+        // Drivers set the callback and its argument in separate steps; the critical sections keep
+        // an interrupt from seeing one without the other.
+        crate::interrupt::free(|_| {
+            // unsafe: C API as documented; the callback is reset before the reference to it
+            // expires.
+            unsafe {
+                riot_sys::touch_dev_set_touch_event_callback(
+                    self.dev,
+                    Some(Self::callback::<F>),
+                    callback as *mut F as *mut c_void,
+                )
+            };
+        });
+        let result = main(self);
+        crate::interrupt::free(|_| {
+            // Not resetting to NULL as not all drivers check the callback before calling it
+            //
+            // unsafe: C API as documented
+            unsafe {
+                riot_sys::touch_dev_set_touch_event_callback(
+                    self.dev,
+                    Some(Self::ignore),
+                    core::ptr::null_mut(),
+                )
+            };
+        });
+        result
+    }
+
+    unsafe extern "C" fn callback<F: FnMut() + Send>(arg: *mut c_void) {
+        // unsafe: The argument was set from a &mut F in with_callback, and is replaced before that
+        // reference expires.
+        unsafe { (*(arg as *mut F))() }
+    }
+
+    unsafe extern "C" fn ignore(_arg: *mut c_void) {}
+}
+
+/// A change in touch state, as detected by a [Tracker]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// The screen was touched at the given position
+    Pressed(Touch),
+    /// The touch moved to the given position
+    Moved(Touch),
+    /// The touch ended; the last known position is given
+    Released(Touch),
+}
+
+/// Helper that turns successive readings of a [TouchDevice] into [events](Event)
+///
+/// Only the first touch is tracked.
+#[derive(Debug, Default)]
+pub struct Tracker {
+    last: Option<Touch>,
+}
+
+impl Tracker {
+    pub const fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Process a reading of the first touch (or its absence)
+    pub fn process(&mut self, current: Option<Touch>) -> Option<Event> {
+        let event = match (self.last, current) {
+            (None, Some(t)) => Some(Event::Pressed(t)),
+            (Some(l), Some(t)) if l != t => Some(Event::Moved(t)),
+            (Some(l), None) => Some(Event::Released(l)),
+            _ => None,
+        };
+        self.last = current;
+        event
+    }
+
+    /// Read the device and process the reading
+    pub fn update(&mut self, device: &mut TouchDevice) -> Option<Event> {
+        self.process(device.touch())
+    }
+}
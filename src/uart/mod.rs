@@ -8,6 +8,9 @@ use crate::error::{NegativeErrorExt, NumericError};
 use riot_sys::libc::{c_uint, c_void};
 use riot_sys::*;
 
+mod rx_buffer;
+pub use rx_buffer::RxBuffer;
+
 /// Error representing the status returned by various `UART`-functions.
 #[derive(Debug)]
 #[non_exhaustive]
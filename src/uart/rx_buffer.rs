@@ -0,0 +1,235 @@
+//! Asynchronous reception through a ring buffer filled from the UART's RX interrupt
+
+use core::cell::UnsafeCell;
+use core::task::{Poll, Waker};
+
+use riot_sys::libc::{c_uint, c_void};
+use riot_sys::{macro_UART_DEV, uart_init};
+
+use super::{UartDevice, UartDeviceError};
+use crate::error::NegativeErrorExt;
+
+/// Ring buffer of received bytes, for use with [`UartDevice::new_with_rx_buffer()`]
+///
+/// The UART's RX interrupt pushes received bytes into the buffer and wakes any pending read
+/// future. Bytes that arrive while the buffer is full are discarded; this is reported once through
+/// [`.take_overrun()`](Self::take_overrun).
+///
+/// The buffer is typically placed in a static:
+///
+/// ```
+/// # #![no_std]
+/// # #![no_main]
+/// # use riot_wrappers::uart::{RxBuffer, UartDevice};
+/// static RX: RxBuffer<128> = RxBuffer::new();
+///
+/// # async fn f() {
+/// let mut uart = UartDevice::new_with_rx_buffer(0, 115200, &RX).unwrap();
+/// uart.write(b"AT\r\n");
+/// let mut response = [0; 6];
+/// RX.read_exact(&mut response).await;
+/// # }
+/// ```
+///
+/// Only one task should read from a buffer at a time; when futures of several tasks are pending
+/// concurrently, only the latest one to be polled is woken.
+pub struct RxBuffer<const N: usize> {
+    // Accessed only with interrupts disabled
+    inner: UnsafeCell<Inner<N>>,
+}
+
+struct Inner<const N: usize> {
+    data: heapless::Deque<u8, N>,
+    waker: Option<Waker>,
+    overrun: bool,
+}
+
+// unsafe: All access to the inner data happens in critical sections.
+unsafe impl<const N: usize> Sync for RxBuffer<N> {}
+
+impl<const N: usize> RxBuffer<N> {
+    pub const fn new() -> Self {
+        Self {
+            inner: UnsafeCell::new(Inner {
+                data: heapless::Deque::new(),
+                waker: None,
+                overrun: false,
+            }),
+        }
+    }
+
+    fn with_inner<R>(&self, f: impl FnOnce(&mut Inner<N>) -> R) -> R {
+        crate::interrupt::free(|_| {
+            // unsafe: Interrupts are disabled, and the reference does not leave the closure, so it
+            // is exclusive.
+            f(unsafe { &mut *self.inner.get() })
+        })
+    }
+
+    /// Add a received byte to the buffer, and wake any pending reader
+    ///
+    /// This is called from the RX interrupt when the buffer is used with
+    /// [`UartDevice::new_with_rx_buffer()`], but can also be called manually (eg. from a custom
+    /// UART callback).
+    pub fn push(&self, byte: u8) {
+        let waker = self.with_inner(|inner| {
+            if inner.data.push_back(byte).is_err() {
+                inner.overrun = true;
+            }
+            inner.waker.take()
+        });
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Number of bytes that are currently buffered
+    pub fn len(&self) -> usize {
+        self.with_inner(|inner| inner.data.len())
+    }
+
+    /// Whether no bytes are currently buffered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discard all buffered bytes
+    pub fn clear(&self) {
+        self.with_inner(|inner| inner.data.clear())
+    }
+
+    /// Return whether bytes were discarded because the buffer was full, and reset that indication
+    pub fn take_overrun(&self) -> bool {
+        self.with_inner(|inner| core::mem::replace(&mut inner.overrun, false))
+    }
+
+    /// Copy buffered bytes into `buf` without waiting, returning how many were copied
+    pub fn try_read(&self, buf: &mut [u8]) -> usize {
+        self.with_inner(|inner| Self::copy_out(inner, buf))
+    }
+
+    fn copy_out(inner: &mut Inner<N>, buf: &mut [u8]) -> usize {
+        let mut count = 0;
+        for slot in buf.iter_mut() {
+            let Some(byte) = inner.data.pop_front() else {
+                break;
+            };
+            *slot = byte;
+            count += 1;
+        }
+        count
+    }
+
+    /// Wait until at least one byte is available, then copy as many buffered bytes as fit into
+    /// `buf`, returning how many were copied
+    ///
+    /// If `buf` is empty, this returns 0 immediately.
+    pub async fn read(&self, buf: &mut [u8]) -> usize {
+        if buf.is_empty() {
+            return 0;
+        }
+        core::future::poll_fn(|ctx| {
+            self.with_inner(|inner| match Self::copy_out(inner, buf) {
+                0 => {
+                    inner.waker = Some(ctx.waker().clone());
+                    Poll::Pending
+                }
+                n => Poll::Ready(n),
+            })
+        })
+        .await
+    }
+
+    /// Wait until `buf` has been filled completely
+    pub async fn read_exact(&self, buf: &mut [u8]) {
+        let mut filled = 0;
+        while filled < buf.len() {
+            filled += self.read(&mut buf[filled..]).await;
+        }
+    }
+
+    /// Read into `buf` until `delimiter` is received or `buf` is full, returning the number of
+    /// bytes read (including the delimiter)
+    ///
+    /// Bytes after the delimiter stay in the buffer.
+    pub async fn read_until(&self, buf: &mut [u8], delimiter: u8) -> usize {
+        let mut filled = 0;
+        while filled < buf.len() {
+            filled += self.read(&mut buf[filled..filled + 1]).await;
+            if buf[filled - 1] == delimiter {
+                break;
+            }
+        }
+        filled
+    }
+
+    /// Wait for data, then read into `buf` until the line has been idle for the given time or
+    /// `buf` is full, returning the number of bytes read
+    ///
+    /// This is useful for protocols whose messages are delimited by pauses in transmission rather
+    /// than by any particular byte.
+    #[cfg(riot_module_ztimer)]
+    pub async fn read_until_idle<const HZ: u32>(
+        &self,
+        buf: &mut [u8],
+        clock: &crate::ztimer::Clock<HZ>,
+        idle: crate::ztimer::Ticks<HZ>,
+    ) -> usize {
+        let mut filled = self.read(buf).await;
+        while filled < buf.len() {
+            match clock
+                .with_timeout(idle, self.read(&mut buf[filled..]))
+                .await
+            {
+                Ok(n) => filled += n,
+                Err(_) => break,
+            }
+        }
+        filled
+    }
+
+    /// Callback for `uart_init`; `arg` needs to point to a `Self`.
+    unsafe extern "C" fn callback(arg: *mut c_void, data: u8) {
+        // unsafe: Pointer was created from a shared reference in new_with_rx_buffer
+        let self_ = unsafe { &*(arg as *const Self) };
+        self_.push(data);
+    }
+}
+
+impl<const N: usize> Default for RxBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UartDevice<'static> {
+    /// Initialize the given `UART` such that received data is stored in an [`RxBuffer`], from
+    /// which it can be read asynchronously.
+    ///
+    /// # Arguments
+    ///
+    /// * `dev` – The index of the hardware device
+    /// * `baud` – The used baud rate
+    /// * `rx_buffer` – The buffer that received bytes are pushed into
+    pub fn new_with_rx_buffer<const N: usize>(
+        index: usize,
+        baud: u32,
+        rx_buffer: &'static RxBuffer<N>,
+    ) -> Result<Self, UartDeviceError> {
+        // unsafe: The buffer is static and Sync, so it can be accessed from the ISR indefinitely.
+        unsafe {
+            let dev = macro_UART_DEV(index as c_uint);
+            uart_init(
+                dev,
+                baud,
+                Some(RxBuffer::<N>::callback),
+                rx_buffer as *const _ as *mut c_void,
+            )
+            .negative_to_error()?;
+            Ok(Self {
+                dev,
+                _phantom: Default::default(),
+            })
+        }
+    }
+}
@@ -0,0 +1,636 @@
+//! Custom USB interfaces implemented in Rust
+//!
+//! An [Interface] wraps a [Handler] and provides the `usbus_handler_t` glue that makes USBUS call
+//! into it. The handler creates its endpoints when the stack starts, and is then informed about
+//! completed transfers and control requests, all in the USBUS thread:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! use riot_wrappers::static_init::ConstStaticCell;
+//! use riot_wrappers::usbus::handler::*;
+//!
+//! static RX_BUFFER: ConstStaticCell<EndpointBuffer<32>> = ConstStaticCell::zeroed();
+//! static TX_BUFFER: ConstStaticCell<EndpointBuffer<32>> = ConstStaticCell::zeroed();
+//! static INTERFACE: Interface<Loopback> = Interface::new(Loopback { rx: None, tx: None });
+//!
+//! /// Echoes back everything it receives
+//! struct Loopback {
+//!     rx: Option<Endpoint>,
+//!     tx: Option<Endpoint>,
+//! }
+//!
+//! impl Handler for Loopback {
+//!     fn init(&mut self, setup: &mut Setup<'_>) {
+//!         self.rx = setup
+//!             .add_endpoint(EndpointType::Bulk, Direction::Out, RX_BUFFER.take())
+//!             .ok();
+//!         self.tx = setup
+//!             .add_endpoint(EndpointType::Bulk, Direction::In, TX_BUFFER.take())
+//!             .ok();
+//!     }
+//!
+//!     fn usb_event(&mut self, context: &mut Context<'_>, event: UsbEvent) {
+//!         if event == UsbEvent::Reset {
+//!             context.receive(self.rx.as_ref().unwrap());
+//!         }
+//!     }
+//!
+//!     fn transfer(&mut self, context: &mut Context<'_>, endpoint: EndpointId, event: TransferEvent) {
+//!         let (rx, tx) = (self.rx.as_ref().unwrap(), self.tx.as_ref().unwrap());
+//!         if event == TransferEvent::Complete && endpoint == rx.id() {
+//!             let len = context.received_len(rx);
+//!             let data: heapless::Vec<u8, 32> =
+//!                 context.buffer(rx).unwrap()[..len].try_into().unwrap();
+//!             if let Some(buffer) = context.buffer(tx) {
+//!                 buffer[..len].copy_from_slice(&data);
+//!                 context.transmit(tx, len);
+//!             }
+//!         } else if endpoint == tx.id() {
+//!             context.receive(rx);
+//!         }
+//!     }
+//! }
+//!
+//! # fn f(usbus: &'static riot_wrappers::usbus::Usbus) {
+//! INTERFACE.register(usbus);
+//! # }
+//! ```
+
+use core::cell::{Cell, UnsafeCell};
+use core::sync::atomic::AtomicBool;
+
+use riot_sys::{
+    usb_setup_t, usbdev_ep_t, usbus_endpoint_t, usbus_handler_t, usbus_interface_t, usbus_t,
+};
+
+use super::Usbus;
+use crate::error::NumericError;
+use crate::static_init::{claim, ZeroInit};
+
+/// The behavior of a custom USB interface
+///
+/// All methods are called in the USBUS thread.
+pub trait Handler: Send {
+    /// Set up the interface's endpoints; this is called when the USBUS thread starts
+    fn init(&mut self, setup: &mut Setup<'_>);
+
+    /// React to a bus event
+    fn usb_event(&mut self, context: &mut Context<'_>, event: UsbEvent) {
+        let _ = (context, event);
+    }
+
+    /// React to the completion (or failure) of a transfer on one of the interface's endpoints
+    fn transfer(&mut self, context: &mut Context<'_>, endpoint: EndpointId, event: TransferEvent) {
+        let _ = (context, endpoint, event);
+    }
+
+    /// Process a control request addressed to the interface
+    ///
+    /// During the [data stage](ControlState::InData) of device-to-host requests, the response is
+    /// written using [`Context::control_respond()`]; during the [data
+    /// stage](ControlState::OutData) of host-to-device requests, the data is available through
+    /// [`Context::control_data()`].
+    ///
+    /// The default implementation rejects all requests.
+    fn control(
+        &mut self,
+        context: &mut Context<'_>,
+        state: ControlState,
+        request: &SetupPacket,
+    ) -> ControlResult {
+        let _ = (context, state, request);
+        ControlResult::Stall
+    }
+}
+
+/// A bus event
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UsbEvent {
+    #[doc(alias = "USBUS_EVENT_USB_RESET")]
+    Reset,
+    #[doc(alias = "USBUS_EVENT_USB_SUSPEND")]
+    Suspend,
+    #[doc(alias = "USBUS_EVENT_USB_RESUME")]
+    Resume,
+    /// Any other event, given by its number
+    Other(u32),
+}
+
+/// Outcome of a transfer
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransferEvent {
+    #[doc(alias = "USBUS_EVENT_TRANSFER_COMPLETE")]
+    Complete,
+    #[doc(alias = "USBUS_EVENT_TRANSFER_FAIL")]
+    Fail,
+    #[doc(alias = "USBUS_EVENT_TRANSFER_STALL")]
+    Stall,
+    /// Any other event, given by its number
+    Other(u32),
+}
+
+/// Stage of a control request
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ControlState {
+    /// The setup packet was received
+    #[doc(alias = "USBUS_CONTROL_REQUEST_STATE_READY")]
+    Ready,
+    /// Device-to-host data is requested
+    #[doc(alias = "USBUS_CONTROL_REQUEST_STATE_INDATA")]
+    InData,
+    /// Host-to-device data was received
+    #[doc(alias = "USBUS_CONTROL_REQUEST_STATE_OUTDATA")]
+    OutData,
+    /// Any other state, given by its number
+    Other(u32),
+}
+
+/// How a control request was processed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ControlResult {
+    /// The request was processed
+    Handled,
+    /// The request is not supported, and is rejected by stalling the control endpoint
+    Stall,
+}
+
+/// A control request's setup packet
+#[derive(Debug, Copy, Clone)]
+pub struct SetupPacket {
+    pub request_type: u8,
+    pub request: u8,
+    pub value: u16,
+    pub index: u16,
+    pub length: u16,
+}
+
+impl From<&usb_setup_t> for SetupPacket {
+    fn from(setup: &usb_setup_t) -> Self {
+        Self {
+            request_type: setup.type_,
+            request: setup.request,
+            value: setup.value,
+            index: setup.index,
+            length: setup.length,
+        }
+    }
+}
+
+/// Kind of an endpoint
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EndpointType {
+    #[doc(alias = "USB_EP_TYPE_BULK")]
+    Bulk,
+    /// An interrupt endpoint with the given polling interval
+    #[doc(alias = "USB_EP_TYPE_INTERRUPT")]
+    Interrupt { interval: u8 },
+}
+
+/// Direction of an endpoint, as seen from the host
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    #[doc(alias = "USB_EP_DIR_IN")]
+    In,
+    #[doc(alias = "USB_EP_DIR_OUT")]
+    Out,
+}
+
+/// An endpoint created in [`Handler::init()`], along with its buffer
+///
+/// The buffer is accessed through the [Context] passed into the handler's callbacks.
+pub struct Endpoint {
+    ep: *mut usbus_endpoint_t,
+    buffer: *mut u8,
+    len: usize,
+}
+
+// unsafe: Only used through the Context, which is only available in the USBUS thread
+unsafe impl Send for Endpoint {}
+
+/// Memory for an endpoint of maximum packet size `N`, aligned for DMA access by the peripheral
+///
+/// This is the equivalent of C's `usbdev_ep_buf_t`. Its alignment is fixed to a value large enough
+/// for all supported CPUs' `USBDEV_CPU_DMA_ALIGNMENT`, as that can not be expressed in a Rust type
+/// attribute.
+#[repr(C, align(32))]
+pub struct EndpointBuffer<const N: usize>(pub [u8; N]);
+
+const _: () = assert!(
+    core::mem::align_of::<EndpointBuffer<0>>() >= riot_sys::USBDEV_CPU_DMA_ALIGNMENT as usize,
+    "EndpointBuffer alignment is insufficient for this CPU"
+);
+
+impl<const N: usize> EndpointBuffer<N> {
+    pub const fn new() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> Default for EndpointBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// unsafe: Plain data
+unsafe impl<const N: usize> ZeroInit for EndpointBuffer<N> {}
+
+/// Identifier of an endpoint, used to tell which endpoint a transfer event refers to
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EndpointId(*mut usbdev_ep_t);
+
+impl Endpoint {
+    pub fn id(&self) -> EndpointId {
+        // unsafe: The endpoint was created by USBUS and lives as long as the stack
+        EndpointId(unsafe { (*self.ep).ep })
+    }
+}
+
+/// Access to the interface during [`Handler::init()`]
+pub struct Setup<'a> {
+    usbus: *mut usbus_t,
+    iface: &'a mut usbus_interface_t,
+}
+
+impl Setup<'_> {
+    /// Set the interface's class triple
+    ///
+    /// The default is the vendor specific class (0xff) with subclass and protocol 0.
+    pub fn set_class(&mut self, class: u8, subclass: u8, protocol: u8) {
+        self.iface.class = class as _;
+        self.iface.subclass = subclass as _;
+        self.iface.protocol = protocol as _;
+    }
+
+    /// Create and enable an endpoint on the interface
+    ///
+    /// The buffer's length is the endpoint's maximum packet size. The buffer is used by the
+    /// peripheral whenever a transfer is in progress, and is only accessible through
+    /// [`Context::buffer()`] otherwise.
+    #[doc(alias = "usbus_add_endpoint")]
+    #[doc(alias = "usbus_enable_endpoint")]
+    pub fn add_endpoint<const N: usize>(
+        &mut self,
+        type_: EndpointType,
+        direction: Direction,
+        buffer: &'static mut EndpointBuffer<N>,
+    ) -> Result<Endpoint, NumericError> {
+        let buffer = &mut buffer.0;
+        let c_type = match type_ {
+            EndpointType::Bulk => riot_sys::usb_ep_type_t_USB_EP_TYPE_BULK,
+            EndpointType::Interrupt { .. } => riot_sys::usb_ep_type_t_USB_EP_TYPE_INTERRUPT,
+        };
+        let c_direction = match direction {
+            Direction::In => riot_sys::usb_ep_dir_t_USB_EP_DIR_IN,
+            Direction::Out => riot_sys::usb_ep_dir_t_USB_EP_DIR_OUT,
+        };
+        // unsafe: C API as documented; this runs in the USBUS thread during initialization.
+        let ep = unsafe {
+            riot_sys::usbus_add_endpoint(
+                self.usbus,
+                self.iface,
+                c_type,
+                c_direction,
+                buffer.len() as _,
+            )
+        };
+        if ep.is_null() {
+            return Err(crate::error::ENOMEM);
+        }
+        // unsafe: The endpoint was just created
+        unsafe {
+            if let EndpointType::Interrupt { interval } = type_ {
+                (*ep).interval = interval;
+            }
+            riot_sys::inline::usbus_enable_endpoint(crate::inline_cast_mut(ep));
+        }
+        Ok(Endpoint {
+            ep,
+            buffer: buffer.as_mut_ptr(),
+            len: buffer.len(),
+        })
+    }
+}
+
+/// Set of endpoints of an interface that have a transfer in progress
+///
+/// This is a bit field indexed by endpoint number and direction. It is only accessed in the USBUS
+/// thread.
+struct Busy(Cell<u32>);
+
+impl Busy {
+    const fn new() -> Self {
+        Self(Cell::new(0))
+    }
+
+    fn bit(ep: *mut usbdev_ep_t) -> u32 {
+        // unsafe: Endpoints are created by USBUS and live as long as the stack
+        let (num, dir) = unsafe { ((*ep).num as u32, (*ep).dir) };
+        let index = num * 2 + (dir == riot_sys::usb_ep_dir_t_USB_EP_DIR_IN) as u32;
+        1 << (index % 32)
+    }
+
+    fn is_busy(&self, ep: *mut usbdev_ep_t) -> bool {
+        self.0.get() & Self::bit(ep) != 0
+    }
+
+    fn set(&self, ep: *mut usbdev_ep_t) {
+        self.0.set(self.0.get() | Self::bit(ep));
+    }
+
+    fn clear(&self, ep: *mut usbdev_ep_t) {
+        self.0.set(self.0.get() & !Self::bit(ep));
+    }
+
+    fn clear_all(&self) {
+        self.0.set(0);
+    }
+}
+
+/// Access to the stack while a [Handler] callback runs
+pub struct Context<'a> {
+    usbus: *mut usbus_t,
+    busy: &'a Busy,
+}
+
+impl Context<'_> {
+    /// The endpoint's buffer, or None while a transfer on the endpoint is in progress
+    ///
+    /// A transfer is in progress from a [`.transmit()`](Self::transmit) or
+    /// [`.receive()`](Self::receive) until the [Handler] is informed of its completion (or
+    /// failure) through [`Handler::transfer()`], or until the bus is reset. During that time, the
+    /// peripheral may access the buffer (eg. through DMA).
+    pub fn buffer<'b>(&'b mut self, endpoint: &'b Endpoint) -> Option<&'b mut [u8]> {
+        if self.busy.is_busy(endpoint.id().0) {
+            return None;
+        }
+        // unsafe: The buffer was 'static mut when the endpoint was created, is not in use by the
+        // peripheral as no transfer is in progress, and is only accessed from the USBUS thread,
+        // which is not running anything else while this context lives.
+        Some(unsafe { core::slice::from_raw_parts_mut(endpoint.buffer, endpoint.len) })
+    }
+
+    /// Send the first `len` bytes of the buffer of an IN endpoint
+    ///
+    /// ## Panics
+    ///
+    /// This panics if `len` exceeds the buffer, or if a transfer on the endpoint is already in
+    /// progress.
+    #[doc(alias = "usbdev_ep_xmit")]
+    pub fn transmit(&mut self, endpoint: &Endpoint, len: usize) {
+        assert!(len <= endpoint.len, "Transmission exceeds endpoint buffer");
+        assert!(
+            !self.busy.is_busy(endpoint.id().0),
+            "Transfer already in progress"
+        );
+        self.busy.set(endpoint.id().0);
+        // unsafe: C API as documented; the buffer stays valid as it is static.
+        unsafe {
+            riot_sys::inline::usbdev_ep_xmit(
+                crate::inline_cast_mut(endpoint.id().0),
+                endpoint.buffer,
+                len as _,
+            )
+        };
+    }
+
+    /// Make an OUT endpoint ready to receive into its buffer
+    ///
+    /// ## Panics
+    ///
+    /// This panics if a transfer on the endpoint is already in progress.
+    #[doc(alias = "usbdev_ep_xmit")]
+    pub fn receive(&mut self, endpoint: &Endpoint) {
+        self.transmit(endpoint, endpoint.len);
+    }
+
+    /// Number of bytes received on an OUT endpoint in its last completed transfer
+    #[doc(alias = "USBOPT_EP_AVAILABLE")]
+    pub fn received_len(&self, endpoint: &Endpoint) -> usize {
+        let mut len: usize = 0;
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::inline::usbdev_ep_get(
+                crate::inline_cast_mut(endpoint.id().0),
+                riot_sys::usbopt_ep_t_USBOPT_EP_AVAILABLE as _,
+                &mut len as *mut _ as *mut _,
+                core::mem::size_of_val(&len) as _,
+            )
+        };
+        len
+    }
+
+    /// Stall or un-stall an endpoint
+    #[doc(alias = "usbdev_ep_stall")]
+    pub fn set_stall(&mut self, endpoint: &Endpoint, stall: bool) {
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::inline::usbdev_ep_stall(crate::inline_cast_mut(endpoint.id().0), stall)
+        };
+    }
+
+    /// Provide (part of) the response to a device-to-host control request
+    ///
+    /// This is only meaningful in the [`ControlState::InData`] stage; the data is sliced into
+    /// packets by USBUS.
+    #[doc(alias = "usbus_control_slicer_put_bytes")]
+    pub fn control_respond(&mut self, data: &[u8]) {
+        // unsafe: C API as documented
+        unsafe {
+            riot_sys::usbus_control_slicer_put_bytes(self.usbus, data.as_ptr(), data.len() as _)
+        };
+    }
+
+    /// The data received in a host-to-device control request
+    ///
+    /// This is only meaningful in the [`ControlState::OutData`] stage.
+    #[doc(alias = "usbus_control_get_out_data")]
+    pub fn control_data(&mut self) -> &[u8] {
+        let mut len = 0;
+        // unsafe: C API as documented
+        let data = unsafe { riot_sys::usbus_control_get_out_data(self.usbus, &mut len) };
+        if data.is_null() {
+            return &[];
+        }
+        // unsafe: Valid until the request is processed, which outlasts the context.
+        unsafe { core::slice::from_raw_parts(data, len as _) }
+    }
+}
+
+/// A [Handler] along with the USBUS data structures that make it an interface of a [Usbus]
+///
+/// This is placed in a static, and [registered](Self::register) with the stack before it is
+/// started.
+#[repr(C)]
+pub struct Interface<H: Handler> {
+    // Must be the first field: The handler pointer is cast back to the interface in the callbacks.
+    handler: UnsafeCell<usbus_handler_t>,
+    iface: UnsafeCell<usbus_interface_t>,
+    inner: UnsafeCell<H>,
+    registered: AtomicBool,
+    busy: Busy,
+}
+
+// unsafe: The handler is only accessed in the USBUS thread after registration, and was Send to
+// get there.
+unsafe impl<H: Handler> Sync for Interface<H> {}
+
+impl<H: Handler> Interface<H> {
+    const DRIVER: riot_sys::usbus_handler_driver_t = riot_sys::usbus_handler_driver_t {
+        init: Some(Self::init),
+        event_handler: Some(Self::event_handler),
+        transfer_handler: Some(Self::transfer_handler),
+        control_handler: Some(Self::control_handler),
+    };
+
+    pub const fn new(handler: H) -> Self {
+        Self {
+            // unsafe: All-zero is the unregistered state
+            handler: UnsafeCell::new(unsafe { core::mem::zeroed() }),
+            // unsafe: All-zero is the state before usbus_add_interface
+            iface: UnsafeCell::new(unsafe { core::mem::zeroed() }),
+            inner: UnsafeCell::new(handler),
+            registered: AtomicBool::new(false),
+            busy: Busy::new(),
+        }
+    }
+
+    /// Add the interface to an initialized but not yet started USBUS instance
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the interface was already registered, or if the stack is not in its setup
+    /// phase.
+    #[doc(alias = "usbus_register_event_handler")]
+    pub fn register(&'static self, usbus: &'static Usbus) {
+        usbus.setup(|usbus| {
+            assert!(!claim(&self.registered), "Interface registered twice");
+            let handler = self.handler.get();
+            // unsafe: Not accessed by anything else until registered
+            unsafe {
+                (*handler).driver = &Self::DRIVER;
+                riot_sys::usbus_register_event_handler(usbus, handler);
+            }
+        })
+    }
+
+    /// Recover the interface and its inner handler from a handler pointer
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be one passed in by USBUS, which thus was registered from a static
+    /// Interface<H>, and this must only be called in the USBUS thread.
+    unsafe fn from_handler<'a>(handler: *mut usbus_handler_t) -> (&'a Self, &'a mut H) {
+        // unsafe: The handler is the first field, and the interface is static.
+        let interface: &Self = unsafe { &*(handler as *const Self) };
+        // unsafe: Only accessed in the USBUS thread, and not reentrantly
+        let inner = unsafe { &mut *interface.inner.get() };
+        (interface, inner)
+    }
+
+    unsafe extern "C" fn init(usbus: *mut usbus_t, handler: *mut usbus_handler_t) {
+        // unsafe: Called by USBUS as per driver documentation
+        let (interface, inner) = unsafe { Self::from_handler(handler) };
+        let iface = interface.iface.get();
+        // unsafe: Only accessed here, in the USBUS thread
+        unsafe {
+            (*iface).class = riot_sys::USB_CLASS_VENDOR as _;
+            (*iface).handler = handler;
+            riot_sys::usbus_add_interface(usbus, iface);
+            riot_sys::inline::usbus_handler_set_flag(
+                crate::inline_cast_mut(handler),
+                riot_sys::USBUS_HANDLER_FLAG_RESET
+                    | riot_sys::USBUS_HANDLER_FLAG_SUSPEND
+                    | riot_sys::USBUS_HANDLER_FLAG_RESUME,
+            );
+        }
+        inner.init(&mut Setup {
+            usbus,
+            // unsafe: As above
+            iface: unsafe { &mut *iface },
+        });
+    }
+
+    unsafe extern "C" fn event_handler(
+        usbus: *mut usbus_t,
+        handler: *mut usbus_handler_t,
+        event: riot_sys::usbus_event_usb_t,
+    ) {
+        // unsafe: Called by USBUS as per driver documentation
+        let (interface, inner) = unsafe { Self::from_handler(handler) };
+        let event = match event {
+            riot_sys::usbus_event_usb_t_USBUS_EVENT_USB_RESET => {
+                // A reset aborts all transfers
+                interface.busy.clear_all();
+                UsbEvent::Reset
+            }
+            riot_sys::usbus_event_usb_t_USBUS_EVENT_USB_SUSPEND => UsbEvent::Suspend,
+            riot_sys::usbus_event_usb_t_USBUS_EVENT_USB_RESUME => UsbEvent::Resume,
+            other => UsbEvent::Other(other as _),
+        };
+        inner.usb_event(&mut Context::new(usbus, &interface.busy), event);
+    }
+
+    unsafe extern "C" fn transfer_handler(
+        usbus: *mut usbus_t,
+        handler: *mut usbus_handler_t,
+        ep: *mut usbdev_ep_t,
+        event: riot_sys::usbus_event_transfer_t,
+    ) {
+        // unsafe: Called by USBUS as per driver documentation
+        let (interface, inner) = unsafe { Self::from_handler(handler) };
+        // Whichever the outcome, the transfer is over
+        interface.busy.clear(ep);
+        let event = match event {
+            riot_sys::usbus_event_transfer_t_USBUS_EVENT_TRANSFER_COMPLETE => {
+                TransferEvent::Complete
+            }
+            riot_sys::usbus_event_transfer_t_USBUS_EVENT_TRANSFER_FAIL => TransferEvent::Fail,
+            riot_sys::usbus_event_transfer_t_USBUS_EVENT_TRANSFER_STALL => TransferEvent::Stall,
+            other => TransferEvent::Other(other as _),
+        };
+        inner.transfer(
+            &mut Context::new(usbus, &interface.busy),
+            EndpointId(ep),
+            event,
+        );
+    }
+
+    unsafe extern "C" fn control_handler(
+        usbus: *mut usbus_t,
+        handler: *mut usbus_handler_t,
+        state: riot_sys::usbus_control_request_state_t,
+        request: *mut usb_setup_t,
+    ) -> i32 {
+        // unsafe: Called by USBUS as per driver documentation
+        let (interface, inner) = unsafe { Self::from_handler(handler) };
+        let state = match state {
+            riot_sys::usbus_control_request_state_t_USBUS_CONTROL_REQUEST_STATE_READY => {
+                ControlState::Ready
+            }
+            riot_sys::usbus_control_request_state_t_USBUS_CONTROL_REQUEST_STATE_INDATA => {
+                ControlState::InData
+            }
+            riot_sys::usbus_control_request_state_t_USBUS_CONTROL_REQUEST_STATE_OUTDATA => {
+                ControlState::OutData
+            }
+            other => ControlState::Other(other as _),
+        };
+        // unsafe: Valid for the duration of the call
+        let request = SetupPacket::from(unsafe { &*request });
+        match inner.control(&mut Context::new(usbus, &interface.busy), state, &request) {
+            ControlResult::Handled => 1,
+            ControlResult::Stall => -1,
+        }
+    }
+}
+
+impl<'a> Context<'a> {
+    fn new(usbus: *mut usbus_t, busy: &'a Busy) -> Self {
+        Self { usbus, busy }
+    }
+}
@@ -0,0 +1,139 @@
+//! Running RIOT's [USB device stack (USBUS)](https://doc.riot-os.org/group__usb__usbus.html)
+//!
+//! When the `auto_init_usbus` module is used, RIOT sets up USBUS with the USB classes that are
+//! configured at build time. For setups in which the application decides which interfaces to
+//! offer, that module is disabled, and USBUS is set up from Rust instead:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! use riot_wrappers::static_init::ConstStaticCell;
+//! use riot_wrappers::usbus::Usbus;
+//!
+//! static USBUS: Usbus = Usbus::new();
+//! static STACK: ConstStaticCell<[u8; 1024]> = ConstStaticCell::zeroed();
+//!
+//! # fn f() {
+//! USBUS.init(0);
+//! // ... register interfaces, eg. a custom [handler::Interface] ...
+//! USBUS.start(STACK.take(), 2, c"usbus");
+//! # }
+//! ```
+
+use core::cell::UnsafeCell;
+use core::ffi::CStr;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use riot_sys::usbus_t;
+
+use crate::static_init::claim;
+
+pub mod handler;
+#[cfg(riot_module_usbus_msc)]
+pub mod msc;
+
+/// A USBUS instance, which is bound to a USB peripheral and runs in its own thread
+pub struct Usbus {
+    inner: UnsafeCell<usbus_t>,
+    initialized: AtomicBool,
+    started: AtomicBool,
+}
+
+// unsafe: Before the stack is started, the usbus_t is only accessed in critical sections, in which
+// the initialized and started flags are checked. After that, it is only accessed through USBUS
+// functions that are safe to call from any thread.
+unsafe impl Sync for Usbus {}
+
+impl Usbus {
+    pub const fn new() -> Self {
+        Self {
+            // unsafe: All-zero is a valid state before usbus_init
+            inner: UnsafeCell::new(unsafe { core::mem::zeroed() }),
+            initialized: AtomicBool::new(false),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Bind the stack to the USB peripheral with the given index
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the stack was initialized before.
+    #[doc(alias = "usbus_init")]
+    #[doc(alias = "usbdev_get_ctx")]
+    pub fn init(&'static self, usbdev_index: usize) {
+        // In a critical section, so that no setup can see the flag before initialization is done
+        crate::interrupt::free(|_| {
+            assert!(!claim(&self.initialized), "USBUS initialized twice");
+            // unsafe: C API as documented
+            unsafe {
+                let usbdev = riot_sys::usbdev_get_ctx(usbdev_index as _);
+                riot_sys::usbus_init(self.inner.get(), usbdev);
+            }
+        })
+    }
+
+    /// Run `f` with access to the stack for adding handlers
+    ///
+    /// `f` runs in a critical section, so that setups from different threads can not interfere
+    /// with each other or with the stack being started.
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the stack was not initialized, or was already started.
+    pub(crate) fn setup<R>(&'static self, f: impl FnOnce(*mut usbus_t) -> R) -> R {
+        crate::interrupt::free(|_| {
+            assert!(
+                self.initialized.load(Ordering::Relaxed),
+                "USBUS needs to be initialized before handlers are added"
+            );
+            assert!(
+                !self.started.load(Ordering::Relaxed),
+                "Handlers need to be added before USBUS is started"
+            );
+            f(self.inner.get())
+        })
+    }
+
+    /// Pointer to the stack, for functions that may be called any time after initialization
+    pub(crate) fn as_ptr(&'static self) -> *mut usbus_t {
+        self.inner.get()
+    }
+
+    /// Start the stack's thread, which enumerates on the bus with all the handlers added so far
+    ///
+    /// Note that in RIOT, lower numbers indicate higher priorities.
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the stack was not initialized, or was already started.
+    #[doc(alias = "usbus_create")]
+    pub fn start(&'static self, stack: &'static mut [u8], priority: u8, name: &'static CStr) {
+        // In a critical section, so that no setup is in progress; once the flag is set, none can
+        // start any more.
+        crate::interrupt::free(|_| {
+            assert!(
+                self.initialized.load(Ordering::Relaxed),
+                "USBUS needs to be initialized before it is started"
+            );
+            assert!(!claim(&self.started), "USBUS started twice");
+        });
+        // unsafe: C API as documented; stack and name are static, and the stack is exclusively
+        // used by the new thread.
+        unsafe {
+            riot_sys::usbus_create(
+                stack.as_mut_ptr() as _,
+                stack.len() as _,
+                priority as _,
+                name.as_ptr(),
+                self.inner.get(),
+            )
+        };
+    }
+}
+
+impl Default for Usbus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
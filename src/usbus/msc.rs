@@ -0,0 +1,117 @@
+//! USB mass storage (MSC) class, exporting MTD devices as logical units
+//!
+//! Each [attached](Msc::attach) MTD device shows up as a separate drive on the host. As the host
+//! accesses the raw device, applications should not use a file system on a device while it is
+//! attached; [detaching](Lun::detach) it gives the device back.
+//!
+//! Partitions of a device can be exported individually using the
+//! [MTD mapper](crate::mtd::mapper).
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+use riot_sys::usbus_msc_device_t;
+
+use super::Usbus;
+use crate::error::{NegativeErrorExt, NumericError};
+use crate::mtd::Mtd;
+use crate::static_init::claim;
+
+/// The mass storage interface of a [Usbus]
+pub struct Msc {
+    inner: UnsafeCell<usbus_msc_device_t>,
+    registered: AtomicBool,
+    // Set (to a &'static Usbus) once registration is complete
+    usbus: AtomicPtr<Usbus>,
+}
+
+// unsafe: The inner cell is only accessed by the C code after it was handed out in register, which
+// is only done once as guarded by the registered flag.
+unsafe impl Sync for Msc {}
+
+impl Msc {
+    pub const fn new() -> Self {
+        Self {
+            // unsafe: All-zero is a valid state before usbus_msc_init
+            inner: UnsafeCell::new(unsafe { core::mem::zeroed() }),
+            registered: AtomicBool::new(false),
+            usbus: AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+
+    /// Add the interface to an initialized but not yet started USBUS instance
+    ///
+    /// If RIOT is built with `CONFIG_USBUS_MSC_AUTO_MTD`, all of the board's MTD devices are
+    /// attached right away.
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the interface was already registered, or if the stack is not in its setup
+    /// phase.
+    #[doc(alias = "usbus_msc_init")]
+    pub fn register(&'static self, usbus: &'static Usbus) {
+        usbus.setup(|usbus_ptr| {
+            assert!(!claim(&self.registered), "MSC interface registered twice");
+            // unsafe: C API as documented; the inner device is not used by anything else before.
+            unsafe { riot_sys::usbus_msc_init(usbus_ptr, self.inner.get()) };
+        });
+        self.usbus
+            .store(usbus as *const Usbus as *mut Usbus, Ordering::Release);
+    }
+
+    fn usbus(&self) -> &'static Usbus {
+        let usbus = self.usbus.load(Ordering::Acquire);
+        assert!(
+            !usbus.is_null(),
+            "MSC interface needs to be registered first"
+        );
+        // unsafe: Only ever set from a &'static Usbus in register
+        unsafe { &*usbus }
+    }
+
+    /// Export an MTD device as a logical unit
+    ///
+    /// The device needs to be initialized already. On error, the device is returned along with
+    /// the error.
+    #[doc(alias = "usbus_msc_add_lu")]
+    pub fn attach(
+        &'static self,
+        mut mtd: Mtd<'static>,
+    ) -> Result<Lun, (NumericError, Mtd<'static>)> {
+        let usbus = self.usbus().as_ptr();
+        // unsafe: C API as documented; the device is static, and exclusively used by the MSC
+        // until it is detached.
+        match unsafe { riot_sys::usbus_msc_add_lu(usbus, mtd.as_ptr()) }.negative_to_error() {
+            Ok(_) => Ok(Lun { msc: self, mtd }),
+            Err(e) => Err((e, mtd)),
+        }
+    }
+}
+
+impl Default for Msc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An MTD device that is exported through a [Msc] interface
+///
+/// Dropping this keeps the device exported forever; use [`.detach()`](Self::detach) to get the
+/// device back.
+#[must_use = "The device can only be used again after detaching"]
+pub struct Lun {
+    msc: &'static Msc,
+    mtd: Mtd<'static>,
+}
+
+impl Lun {
+    /// Stop exporting the device, and give it back for use by the application
+    #[doc(alias = "usbus_msc_remove_lu")]
+    pub fn detach(mut self) -> Mtd<'static> {
+        let usbus = self.msc.usbus().as_ptr();
+        // unsafe: C API as documented; the device was added in attach.
+        let result = unsafe { riot_sys::usbus_msc_remove_lu(usbus, self.mtd.as_ptr()) };
+        debug_assert!(result >= 0, "LUN was not attached");
+        self.mtd
+    }
+}
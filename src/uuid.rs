@@ -0,0 +1,168 @@
+//! Generation and formatting of UUIDs (RFC 4122) through [RIOT's uuid module](https://doc.riot-os.org/group__sys__uuid.html)
+//!
+//! Version 4 UUIDs are generated from RIOT's [random](crate::random) module; version 3 and 5 UUIDs
+//! are derived from a namespace and a name, and are thus stable identifiers:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::uuid::Uuid;
+//! # fn f() {
+//! let id = Uuid::new_v5(&Uuid::namespace_url(), b"coap://device.example.com/sensor");
+//! riot_wrappers::println!("Resource ID: {}", id);
+//! # }
+//! ```
+
+use core::ffi::c_char;
+use core::fmt;
+
+use riot_sys::uuid_t;
+
+use crate::c_str::CStrBuf;
+
+/// Length of the textual representation of a UUID
+const STR_LEN: usize = riot_sys::UUID_STR_LEN as usize;
+
+/// A UUID in its binary (network byte order) representation
+#[derive(Copy, Clone)]
+pub struct Uuid(uuid_t);
+
+/// Error parsing a UUID from a string
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError;
+
+impl Uuid {
+    /// Create a UUID from its 16 bytes in network byte order
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        const { assert!(core::mem::size_of::<uuid_t>() == 16) };
+        // unsafe: Same size, and uuid_t is plain data
+        Self(unsafe { core::mem::transmute(bytes) })
+    }
+
+    /// The 16 bytes of the UUID in network byte order
+    pub fn to_bytes(&self) -> [u8; 16] {
+        // unsafe: Same size, and uuid_t is plain data
+        unsafe { core::mem::transmute(self.0) }
+    }
+
+    /// Generate a random (version 4) UUID
+    #[doc(alias = "uuid_v4")]
+    pub fn new_v4() -> Self {
+        let mut uuid = uuid_t::default();
+        // unsafe: C API as documented
+        unsafe { riot_sys::uuid_v4(&mut uuid) };
+        Self(uuid)
+    }
+
+    /// Generate a version 4 UUID from a given random number generator
+    ///
+    /// This is useful when a source other than RIOT's global random number generator is preferred
+    /// (eg. a hardware RNG).
+    pub fn new_v4_from_rng(rng: &mut impl rand_core_06::RngCore) -> Self {
+        let mut bytes = [0; 16];
+        rng.fill_bytes(&mut bytes);
+        // Version 4
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        // RFC 4122 variant
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Self::from_bytes(bytes)
+    }
+
+    /// Generate a name-based (version 3, MD5) UUID
+    #[doc(alias = "uuid_v3")]
+    pub fn new_v3(namespace: &Uuid, name: &[u8]) -> Self {
+        let mut uuid = uuid_t::default();
+        // unsafe: C API as documented
+        unsafe { riot_sys::uuid_v3(&mut uuid, &namespace.0, name.as_ptr(), name.len() as _) };
+        Self(uuid)
+    }
+
+    /// Generate a name-based (version 5, SHA-1) UUID
+    #[doc(alias = "uuid_v5")]
+    pub fn new_v5(namespace: &Uuid, name: &[u8]) -> Self {
+        let mut uuid = uuid_t::default();
+        // unsafe: C API as documented
+        unsafe { riot_sys::uuid_v5(&mut uuid, &namespace.0, name.as_ptr(), name.len() as _) };
+        Self(uuid)
+    }
+
+    /// Namespace for fully qualified domain names
+    #[doc(alias = "uuid_namespace_dns")]
+    pub fn namespace_dns() -> Self {
+        // unsafe: Constant provided by RIOT
+        Self(unsafe { riot_sys::uuid_namespace_dns })
+    }
+
+    /// Namespace for URLs
+    #[doc(alias = "uuid_namespace_url")]
+    pub fn namespace_url() -> Self {
+        // unsafe: Constant provided by RIOT
+        Self(unsafe { riot_sys::uuid_namespace_url })
+    }
+
+    /// Namespace for ISO OIDs
+    #[doc(alias = "uuid_namespace_iso")]
+    pub fn namespace_iso() -> Self {
+        // unsafe: Constant provided by RIOT
+        Self(unsafe { riot_sys::uuid_namespace_iso })
+    }
+
+    /// Namespace for X.500 DNs
+    #[doc(alias = "uuid_namespace_x500")]
+    pub fn namespace_x500() -> Self {
+        // unsafe: Constant provided by RIOT
+        Self(unsafe { riot_sys::uuid_namespace_x500 })
+    }
+
+    /// The UUID's version number (eg. 4 for random UUIDs)
+    #[doc(alias = "uuid_version")]
+    pub fn version(&self) -> u8 {
+        self.to_bytes()[6] >> 4
+    }
+}
+
+impl PartialEq for Uuid {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Uuid {}
+
+impl core::hash::Hash for Uuid {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state)
+    }
+}
+
+impl fmt::Display for Uuid {
+    #[doc(alias = "uuid_to_string")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; STR_LEN + 1];
+        // unsafe: C API as documented; the buffer has the required size
+        unsafe { riot_sys::uuid_to_string(&self.0, buf.as_mut_ptr() as *mut c_char) };
+        let s = core::str::from_utf8(&buf[..STR_LEN]).map_err(|_| fmt::Error)?;
+        f.write_str(s)
+    }
+}
+
+impl fmt::Debug for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Uuid({})", self)
+    }
+}
+
+impl core::str::FromStr for Uuid {
+    type Err = ParseError;
+
+    #[doc(alias = "uuid_from_string")]
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s: CStrBuf<{ STR_LEN + 1 }> = CStrBuf::try_from_str(s).map_err(|_| ParseError)?;
+        let mut uuid = uuid_t::default();
+        // unsafe: C API as documented
+        match unsafe { riot_sys::uuid_from_string(&mut uuid, s.as_ptr()) } {
+            0 => Ok(Self(uuid)),
+            _ => Err(ParseError),
+        }
+    }
+}
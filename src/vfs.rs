@@ -10,7 +10,8 @@
 //!
 //! ## Incomplete
 //!
-//! So far, only a subset of VFS is implemented; in particular, the file system is read-only.
+//! So far, only a subset of VFS is implemented; in particular, files can only be written as a
+//! whole (through `write_from_slice()`, with the `vfs_util` module).
 
 use core::marker::PhantomData;
 use core::mem::MaybeUninit;
@@ -20,40 +21,12 @@ use pin_project::{pin_project, pinned_drop};
 
 use riot_sys::libc;
 
+use crate::c_str::CStrBuf;
 use crate::error::{NegativeErrorExt, NumericError};
-use crate::helpers::{PointerToCStr, SliceToCStr};
+use crate::helpers::PointerToCStr;
 
-#[derive(Copy, Clone, Debug)]
-struct NameTooLong;
-#[derive(Copy, Clone, Debug)]
-struct NameContainsIllegalNull;
-
-impl From<NameTooLong> for NumericError {
-    fn from(_: NameTooLong) -> NumericError {
-        crate::error::ENOMEM
-    }
-}
-
-impl From<NameContainsIllegalNull> for NumericError {
-    fn from(_: NameContainsIllegalNull) -> NumericError {
-        crate::error::EINVAL
-    }
-}
-struct NameNullTerminated(heapless::String<{ riot_sys::VFS_NAME_MAX as usize + 1 }>);
-
-impl NameNullTerminated {
-    fn new(name: &str) -> Result<Self, NameTooLong> {
-        let mut buf = heapless::String::new();
-        buf.push_str(name).map_err(|_| NameTooLong)?;
-        buf.push_str("\0").map_err(|_| NameTooLong)?;
-        Ok(NameNullTerminated(buf))
-    }
-
-    fn as_cstr(&self) -> Result<&core::ffi::CStr, NameContainsIllegalNull> {
-        core::ffi::CStr::from_bytes_with_nul(self.0.as_str().as_bytes())
-            .map_err(|_| NameContainsIllegalNull)
-    }
-}
+/// Buffer for path names passed into VFS functions
+type NameNullTerminated = CStrBuf<{ riot_sys::VFS_NAME_MAX as usize + 1 }>;
 
 /// A file handle
 #[derive(Debug)]
@@ -92,11 +65,9 @@ pub enum SeekFrom {
 impl File {
     /// Open a file in read-only mode.
     pub fn open(path: &str) -> Result<Self, NumericError> {
-        let path = NameNullTerminated::new(path)?;
-        let fileno = unsafe {
-            riot_sys::vfs_open(path.as_cstr()?.as_ptr() as _, riot_sys::O_RDONLY as _, 0)
-        }
-        .negative_to_error()?;
+        let path = NameNullTerminated::try_from_str(path)?;
+        let fileno = unsafe { riot_sys::vfs_open(path.as_ptr() as _, riot_sys::O_RDONLY as _, 0) }
+            .negative_to_error()?;
         Ok(File {
             fileno,
             _not_send_sync: PhantomData,
@@ -207,10 +178,10 @@ impl<'d> Dir<'d> {
         mut slot: Pin<&'d mut DirSlot>,
     ) -> Result<Self, NumericError> {
         slot.as_mut().close();
-        let name = NameNullTerminated::new(name)?;
+        let name = NameNullTerminated::try_from_str(name)?;
         let dir = { unsafe { Pin::into_inner_unchecked(slot.as_mut().project().0) } }
             .insert(MaybeUninit::uninit());
-        match (unsafe { riot_sys::vfs_opendir(dir.as_mut_ptr(), name.as_cstr()?.as_ptr() as _) })
+        match (unsafe { riot_sys::vfs_opendir(dir.as_mut_ptr(), name.as_ptr() as _) })
             .negative_to_error()
         {
             Ok(_) => (),
@@ -260,10 +231,7 @@ impl Dirent<'_> {
     ///
     /// This will panic if the file name is not encoded in UTF-8.
     pub fn name(&self) -> &str {
-        let mut name = self
-            .0
-            .d_name
-            .to_cstr()
+        let mut name = crate::c_str::from_c_buf(&self.0.d_name)
             // *We* could continue, but it's way more likely to be an error
             .expect("File name does not have a trailing null character")
             .to_str()
@@ -363,3 +331,84 @@ impl<'a> Mount<'a> {
             .expect("Mount point not UTF-8 encoded")
     }
 }
+
+/// Read the content of the file at `path` into `buf`, returning the number of bytes read
+///
+/// If the file is larger than the buffer, the file is not read, and `ENOSPC` is returned.
+#[cfg(riot_module_vfs_util)]
+#[doc(alias = "vfs_file_to_buffer")]
+pub fn read_to_buffer(path: &str, buf: &mut [u8]) -> Result<usize, NumericError> {
+    let path = NameNullTerminated::try_from_str(path)?;
+    // unsafe: C API as documented
+    unsafe {
+        riot_sys::vfs_file_to_buffer(
+            path.as_ptr() as _,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            buf.len() as _,
+        )
+    }
+    .negative_to_error()
+    .map(|len| len as _)
+}
+
+/// Write `data` to the file at `path`, replacing its content if it exists
+#[cfg(riot_module_vfs_util)]
+#[doc(alias = "vfs_file_from_buffer")]
+pub fn write_from_slice(path: &str, data: &[u8]) -> Result<(), NumericError> {
+    let path = NameNullTerminated::try_from_str(path)?;
+    // unsafe: C API as documented
+    unsafe {
+        riot_sys::vfs_file_from_buffer(
+            path.as_ptr() as _,
+            data.as_ptr() as *const libc::c_void,
+            data.len() as _,
+        )
+    }
+    .negative_to_error()?;
+    Ok(())
+}
+
+/// Obtain metadata of the file (or directory) at `path`
+#[doc(alias = "vfs_stat")]
+pub fn stat(path: &str) -> Result<Stat, NumericError> {
+    let path = NameNullTerminated::try_from_str(path)?;
+    let mut stat = MaybeUninit::uninit();
+    // unsafe: C API as documented
+    (unsafe { riot_sys::vfs_stat(path.as_ptr() as _, stat.as_mut_ptr()) }).negative_to_error()?;
+    // unsafe: Initialized by successful vfs_stat
+    Ok(Stat(unsafe { stat.assume_init() }))
+}
+
+/// Check whether anything (a file or a directory) exists at `path`
+#[doc(alias = "vfs_file_exists")]
+pub fn file_exists(path: &str) -> bool {
+    stat(path).is_ok()
+}
+
+/// Usage of a file system, as reported by [usage()]
+#[derive(Debug, Copy, Clone)]
+pub struct FsUsage {
+    /// Size of the file system in bytes
+    pub total: u64,
+    /// Number of bytes not in use
+    pub free: u64,
+    /// Number of bytes available for writing (which may be less than `free`)
+    pub available: u64,
+}
+
+/// Obtain the usage of the file system that `path` is on
+#[doc(alias = "vfs_statvfs")]
+pub fn usage(path: &str) -> Result<FsUsage, NumericError> {
+    let path = NameNullTerminated::try_from_str(path)?;
+    let mut buf = MaybeUninit::uninit();
+    // unsafe: C API as documented
+    (unsafe { riot_sys::vfs_statvfs(path.as_ptr() as _, buf.as_mut_ptr()) }).negative_to_error()?;
+    // unsafe: Initialized by successful vfs_statvfs
+    let buf: riot_sys::statvfs = unsafe { buf.assume_init() };
+    let frsize = buf.f_frsize as u64;
+    Ok(FsUsage {
+        total: buf.f_blocks as u64 * frsize,
+        free: buf.f_bfree as u64 * frsize,
+        available: buf.f_bavail as u64 * frsize,
+    })
+}
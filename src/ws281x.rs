@@ -1,5 +1,22 @@
 //! Tools for using the [WS2812/SK6812 RGB LED
 //! (NeoPixel)](https://doc.riot-os.org/group__drivers__ws281x.html) drivers
+//!
+//! With the `color` module, the LEDs can be set from [Rgb](crate::color::Rgb) values:
+//!
+//! ```
+//! # #![no_std]
+//! # #![no_main]
+//! # use riot_wrappers::ws281x::{BufferedWs281x, GRB};
+//! # use riot_wrappers::color::Rgb;
+//! # fn f(pin: riot_wrappers::gpio::GPIO) {
+//! let mut strip: BufferedWs281x<GRB, 8> = BufferedWs281x::init(pin);
+//! strip.set_brightness(64);
+//! for i in 0..strip.buffer.len() {
+//!     strip.set_pixel(i, Rgb::new(255, 128, 0));
+//! }
+//! strip.write();
+//! # }
+//! ```
 
 use core::convert::TryInto;
 use core::mem::MaybeUninit;
@@ -12,6 +29,7 @@ pub struct BufferedWs281x<C: ChannelType, const N: usize> {
     // The device's linked buffer is left blank -- we don't use the setter functions anyway, and
     // the internal reference would require an internal reference.
     dev: riot_sys::ws281x_t,
+    brightness: u8,
 }
 
 // unsafe: It does contain a dev, but its pointers (by construction) go nowhere
@@ -41,23 +59,98 @@ impl<C: ChannelType + Default + Copy, const N: usize> BufferedWs281x<C, N> {
         Self {
             buffer: [Default::default(); N],
             dev: unsafe { dev.assume_init() },
+            brightness: u8::MAX,
         }
     }
 }
 
 impl<C: ChannelType, const N: usize> BufferedWs281x<C, N> {
+    /// Brightness (from 0 to 255) with which the buffer is sent to the LEDs
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Set the brightness (from 0 to 255) with which the buffer is sent to the LEDs
+    ///
+    /// The buffer keeps its unscaled values; scaling happens on every [.write()](Self::write),
+    /// where the change becomes visible on all LEDs.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    /// Set the color of the LED at `index`
+    ///
+    /// The change becomes visible at the next [.write()](Self::write).
+    ///
+    /// ## Panics
+    ///
+    /// This panics if the index is out of bounds.
+    #[cfg(riot_module_color)]
+    pub fn set_pixel(&mut self, index: usize, color: crate::color::Rgb)
+    where
+        C: From<crate::color::Rgb>,
+    {
+        self.buffer[index] = color.into();
+    }
+
+    /// Set all LEDs to the given color
+    #[cfg(riot_module_color)]
+    pub fn fill(&mut self, color: crate::color::Rgb)
+    where
+        C: From<crate::color::Rgb>,
+    {
+        for led in self.buffer.iter_mut() {
+            *led = color.into();
+        }
+    }
+
+    /// Send the buffer to the LEDs, scaled by the [brightness](Self::set_brightness)
     pub fn write(&mut self) {
+        // unsafe: As documented in ChannelType, the buffer can be viewed as bytes (as
+        // ws281x_write_buffer does anyway); every byte is the value of a single color channel.
+        let bytes = unsafe {
+            core::slice::from_raw_parts(
+                &self.buffer as *const _ as *const u8,
+                N * core::mem::size_of::<C>(),
+            )
+        };
         unsafe {
             riot_sys::inline::ws281x_prepare_transmission(crate::inline_cast_mut(
                 &mut self.dev as *mut _,
             ));
-            riot_sys::ws281x_write_buffer(
-                &mut self.dev,
-                &self.buffer as *const _ as *const core::ffi::c_void,
-                (N * core::mem::size_of::<C>())
-                    .try_into()
-                    .expect("Buffer exceeds experssible range"),
-            );
+        }
+        if self.brightness == u8::MAX {
+            unsafe {
+                riot_sys::ws281x_write_buffer(
+                    &mut self.dev,
+                    bytes.as_ptr() as *const core::ffi::c_void,
+                    bytes
+                        .len()
+                        .try_into()
+                        .expect("Buffer exceeds experssible range"),
+                );
+            }
+        } else {
+            // ws281x_write_buffer may be called repeatedly within a transmission, so the scaled
+            // data is sent in chunks rather than requiring a second buffer. Scaling uses a shift
+            // rather than a division to keep the gaps between chunks short.
+            let factor = self.brightness as u16 + 1;
+            let mut scaled = [0u8; 24];
+            for chunk in bytes.chunks(scaled.len()) {
+                for (s, b) in scaled.iter_mut().zip(chunk) {
+                    *s = ((*b as u16 * factor) >> 8) as u8;
+                }
+                // unsafe: C API as documented
+                unsafe {
+                    riot_sys::ws281x_write_buffer(
+                        &mut self.dev,
+                        scaled.as_ptr() as *const core::ffi::c_void,
+                        chunk.len() as _,
+                    );
+                }
+            }
+        }
+        unsafe {
             riot_sys::inline::ws281x_end_transmission(crate::inline_cast_mut(
                 &mut self.dev as *mut _,
             ));
@@ -78,6 +171,14 @@ pub struct GRBW([u8; 4]);
 
 impl ChannelType for GRBW {}
 
+/// Conversion that leaves the white channel off
+#[cfg(riot_module_color)]
+impl From<crate::color::Rgb> for GRBW {
+    fn from(rgb: crate::color::Rgb) -> Self {
+        Self([rgb.g, rgb.r, rgb.b, 0])
+    }
+}
+
 impl GRBW {
     pub fn rgbw(&self) -> (u8, u8, u8, u8) {
         (self.0[0], self.0[1], self.0[2], self.0[3])
@@ -94,6 +195,13 @@ pub struct GRB([u8; 3]);
 
 impl ChannelType for GRB {}
 
+#[cfg(riot_module_color)]
+impl From<crate::color::Rgb> for GRB {
+    fn from(rgb: crate::color::Rgb) -> Self {
+        Self([rgb.g, rgb.r, rgb.b])
+    }
+}
+
 impl GRB {
     pub fn rgb(&self) -> (u8, u8, u8) {
         (self.0[0], self.0[1], self.0[2])
@@ -0,0 +1,59 @@
+//! Implementation of [embedded_time::Clock] on acquired ZTimer clocks
+//!
+//! The trait is implemented on [LockedClock] rather than on [Clock](super::Clock), as only while
+//! the clock is acquired are its readings guaranteed to be monotonic.
+
+use embedded_time::duration::Generic;
+use embedded_time::fraction::Fraction;
+use embedded_time::{clock, Instant};
+
+use super::{LockedClock, Overflow, Ticks, Timestamp};
+
+impl<const HZ: u32> embedded_time::Clock for LockedClock<HZ> {
+    type T = u32;
+
+    const SCALING_FACTOR: Fraction = Fraction::new(1, HZ);
+
+    #[doc(alias = "ztimer_now")]
+    fn try_now(&self) -> Result<Instant<Self>, clock::Error> {
+        Ok(self.now().into())
+    }
+}
+
+impl<const HZ: u32> From<Timestamp<HZ>> for Instant<LockedClock<HZ>> {
+    fn from(timestamp: Timestamp<HZ>) -> Self {
+        Instant::new(timestamp.0)
+    }
+}
+
+impl<const HZ: u32> From<Instant<LockedClock<HZ>>> for Timestamp<HZ> {
+    fn from(instant: Instant<LockedClock<HZ>>) -> Self {
+        Timestamp(*instant.duration_since_epoch().integer())
+    }
+}
+
+impl<const HZ: u32> From<Ticks<HZ>> for Generic<u32> {
+    fn from(ticks: Ticks<HZ>) -> Self {
+        Generic::new(ticks.0, Fraction::new(1, HZ))
+    }
+}
+
+impl<const HZ: u32> TryFrom<Generic<u32>> for Ticks<HZ> {
+    type Error = Overflow;
+
+    /// Convert a duration of any scaling factor into ticks of this clock
+    ///
+    /// Like all conversions into ticks, this rounds up.
+    fn try_from(duration: Generic<u32>) -> Result<Self, Overflow> {
+        let scaling = duration.scaling_factor();
+        let numerator = (*duration.integer() as u64 * *scaling.numerator() as u64)
+            .checked_mul(HZ as u64)
+            .ok_or(Overflow)?;
+        let denominator = *scaling.denominator() as u64;
+        numerator
+            .div_ceil(denominator)
+            .try_into()
+            .map(Ticks)
+            .map_err(|_| Overflow)
+    }
+}
@@ -0,0 +1,58 @@
+//! Conversions between [fugit] durations and rates and [Ticks]
+//!
+//! Fugit durations whose base is exactly one tick of a clock convert into [Ticks] of that clock
+//! infallibly, so they can be passed to any function that takes `impl Into<Ticks<HZ>>` (such as
+//! [`.sleep()`](crate::thread::ValueInThread::sleep) or
+//! [`.with_timeout()`](super::Clock::with_timeout)). Durations of other bases are converted
+//! through [`Ticks::from_fugit()`], which is const, so that conversion happens at build time for
+//! constant durations.
+
+use super::{Overflow, Ticks};
+
+impl<const HZ: u32> From<fugit::Duration<u32, 1, HZ>> for Ticks<HZ> {
+    fn from(duration: fugit::Duration<u32, 1, HZ>) -> Self {
+        Ticks(duration.ticks())
+    }
+}
+
+impl<const HZ: u32> From<Ticks<HZ>> for fugit::Duration<u32, 1, HZ> {
+    fn from(ticks: Ticks<HZ>) -> Self {
+        fugit::Duration::from_ticks(ticks.0)
+    }
+}
+
+impl<const HZ: u32> Ticks<HZ> {
+    /// Convert a fugit duration of any base into ticks, rounding up
+    pub const fn from_fugit<const NOM: u32, const DENOM: u32>(
+        duration: fugit::Duration<u32, NOM, DENOM>,
+    ) -> Result<Self, Overflow> {
+        // Can not overflow, being a product of two u32
+        let scaled = duration.ticks() as u64 * NOM as u64;
+        let Some(numerator) = scaled.checked_mul(HZ as u64) else {
+            return Err(Overflow);
+        };
+        let ticks = numerator.div_ceil(DENOM as u64);
+        if ticks > u32::MAX as u64 {
+            return Err(Overflow);
+        }
+        Ok(Ticks(ticks as u32))
+    }
+
+    /// Length of one period at the given rate, rounding up
+    ///
+    /// This is useful to configure a [periodic timer](super::periodic::Timer) from a frequency.
+    /// A rate of zero has an infinitely long period, and is reported as an overflow.
+    pub const fn period_of<const NOM: u32, const DENOM: u32>(
+        rate: fugit::Rate<u32, NOM, DENOM>,
+    ) -> Result<Self, Overflow> {
+        let denominator = rate.raw() as u64 * NOM as u64;
+        if denominator == 0 {
+            return Err(Overflow);
+        }
+        let ticks = (HZ as u64 * DENOM as u64).div_ceil(denominator);
+        if ticks > u32::MAX as u64 {
+            return Err(Overflow);
+        }
+        Ok(Ticks(ticks as u32))
+    }
+}
@@ -9,8 +9,15 @@
 //! because sleeping for a Duration works infallibly (even if the duration exceeds the maximum
 //! number of ticks a timer can sleep) by sleeping in repetitions.
 
+#[cfg(feature = "with_embedded_time")]
+mod for_embedded_time;
+#[cfg(feature = "with_fugit")]
+mod for_fugit;
 #[cfg(riot_module_ztimer_periodic)]
 pub mod periodic;
+mod timeout;
+
+pub use timeout::{Ticker, Timeout};
 
 use core::convert::TryInto;
 use core::mem::ManuallyDrop;
@@ -63,8 +70,8 @@ impl<const HZ: u32> ValueInThread<Clock<HZ>> {
     ///
     /// Wraps [ztimer_sleep](https://doc.riot-os.org/group__sys__ztimer.html#gade98636e198f2d571c8acd861d29d360)
     #[doc(alias = "ztimer_sleep")]
-    pub fn sleep(&self, duration: Ticks<HZ>) {
-        unsafe { riot_sys::ztimer_sleep(self.0, duration.0) };
+    pub fn sleep(&self, duration: impl Into<Ticks<HZ>>) {
+        unsafe { riot_sys::ztimer_sleep(self.0, duration.into().0) };
     }
 
     /// Keep the current thread in a busy loop until the duration of ticks in the timer's tim scale
@@ -79,8 +86,8 @@ impl<const HZ: u32> ValueInThread<Clock<HZ>> {
     /// is doable in an ISR), but it's so discouraged that the Rust wrapper takes the position that
     /// it's best done using a [ValueInThread].
     #[doc(alias = "ztimer_spin")]
-    pub fn spin(&self, duration: Ticks<HZ>) {
-        unsafe { riot_sys::ztimer_spin(crate::inline_cast_mut(self.0), duration.0) };
+    pub fn spin(&self, duration: impl Into<Ticks<HZ>>) {
+        unsafe { riot_sys::ztimer_spin(crate::inline_cast_mut(self.0), duration.into().0) };
     }
 
     /// Pause the current thread for the given duration, possibly exceeding values expressible in
@@ -149,10 +156,10 @@ impl<const HZ: u32> Clock<HZ> {
     ///
     /// Note that time starts running only when this is polled, for otherwise there's no pinned
     /// Self around.
-    pub async fn sleep_async(&self, duration: Ticks<HZ>) {
+    pub async fn sleep_async(&self, duration: impl Into<Ticks<HZ>>) {
         AsyncSleep::NeverPolled(NascentAsyncSleep {
             clock: *self,
-            ticks: duration,
+            ticks: duration.into(),
         })
         .await
     }
@@ -236,6 +243,11 @@ impl<const HZ: u32> Clock<HZ> {
         result
     }
 
+    /// Pointer to the underlying C clock, for use with ztimer functions not wrapped here
+    pub(crate) fn as_ptr(&self) -> *mut ztimer_clock_t {
+        self.0
+    }
+
     /// Keep the clock being shut down or reset for low power modes
     ///
     /// While the clock is locked, its [`LockedClock::now()`] method is available, and its values
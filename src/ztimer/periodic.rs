@@ -68,7 +68,7 @@ pub struct Timer<H: Handler, const HZ: u32> {
 }
 
 impl<H: Handler, const HZ: u32> Timer<H, HZ> {
-    pub fn new(clock: super::Clock<HZ>, handler: H, ticks: super::Ticks<HZ>) -> Self {
+    pub fn new(clock: super::Clock<HZ>, handler: H, ticks: impl Into<super::Ticks<HZ>>) -> Self {
         let mut timer = MaybeUninit::uninit();
 
         // Leaving the arg blank for the moment, to be set later when we have a Pin<&mut self>
@@ -81,7 +81,7 @@ impl<H: Handler, const HZ: u32> Timer<H, HZ> {
                 timer.as_mut_ptr(),
                 Some(Self::callback),
                 core::ptr::null_mut(),
-                ticks.0,
+                ticks.into().0,
             );
             timer.assume_init()
         };
@@ -0,0 +1,105 @@
+//! Async utilities built on the [`Clock::sleep_async()`] futures
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+use super::{Clock, LockedClock, Ticks, Timestamp};
+
+/// Error returned by [`Clock::with_timeout()`] when the timeout expired before the future
+/// completed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Timeout;
+
+impl core::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Timeout")
+    }
+}
+
+#[pin_project]
+struct WithTimeout<F, S> {
+    #[pin]
+    future: F,
+    #[pin]
+    sleep: S,
+}
+
+impl<F: Future, S: Future<Output = ()>> Future for WithTimeout<F, S> {
+    type Output = Result<F::Output, Timeout>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        // The future is polled first, so that a result that is ready is never discarded.
+        if let Poll::Ready(output) = this.future.poll(ctx) {
+            return Poll::Ready(Ok(output));
+        }
+        match this.sleep.poll(ctx) {
+            Poll::Ready(()) => Poll::Ready(Err(Timeout)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<const HZ: u32> Clock<HZ> {
+    /// Run a future, but give up on it if it does not complete within the given duration
+    ///
+    /// Like with [`.sleep_async()`](Self::sleep_async), time starts running only when this is
+    /// polled. If the timeout expires, the future is dropped.
+    pub async fn with_timeout<F: Future>(
+        &self,
+        duration: impl Into<Ticks<HZ>>,
+        future: F,
+    ) -> Result<F::Output, Timeout> {
+        WithTimeout {
+            future,
+            sleep: self.sleep_async(duration),
+        }
+        .await
+    }
+
+    /// Create a [Ticker] that fires periodically with the given period
+    ///
+    /// The clock is acquired for as long as the ticker exists.
+    pub fn ticker(&self, period: impl Into<Ticks<HZ>>) -> Ticker<HZ> {
+        let clock = self.acquire();
+        let next = clock.now();
+        Ticker {
+            clock,
+            period: period.into(),
+            next,
+        }
+    }
+}
+
+/// Stream of periodic ticks, created through [`Clock::ticker()`]
+///
+/// Unlike repeatedly sleeping for the period, the ticks do not drift with the time spent between
+/// awaiting them. If ticks are missed (because [`.next()`](Ticker::next) was not awaited in time),
+/// the following calls return immediately until the ticker has caught up.
+pub struct Ticker<const HZ: u32> {
+    clock: LockedClock<HZ>,
+    period: Ticks<HZ>,
+    next: Timestamp<HZ>,
+}
+
+impl<const HZ: u32> Ticker<HZ> {
+    /// Wait until the next tick
+    ///
+    /// The first tick is one period after the ticker was created or last [reset](Self::reset).
+    pub async fn next(&mut self) {
+        self.next = Timestamp(self.next.0.wrapping_add(self.period.0));
+        let remaining = self.next - self.clock.now();
+        // Deadlines in the past show up as wrapped-around (and thus huge) remaining durations.
+        if remaining.0 as i32 > 0 {
+            self.clock.0.sleep_async(remaining).await;
+        }
+    }
+
+    /// Restart counting periods from the current time
+    pub fn reset(&mut self) {
+        self.next = self.clock.now();
+    }
+}
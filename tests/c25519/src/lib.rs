@@ -0,0 +1,59 @@
+#![no_std]
+
+use riot_wrappers::c25519::*;
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+    let mut result = [0; N];
+    hex::decode_to_slice(hex, &mut result).unwrap();
+    result
+}
+
+fn main() {
+    // RFC 8032 Section 7.1, TEST 1
+    let secret = SecretKey::from_bytes(bytes(
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+    ));
+    let public = secret.public_key();
+    assert!(
+        public.as_bytes()
+            == &bytes("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+    );
+    let signature = secret.sign(b"");
+    assert!(
+        signature.as_bytes()
+            == &bytes(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+            )
+    );
+    assert!(public.verify(b"", &signature).is_ok());
+    assert!(public.verify(b"x", &signature) == Err(InvalidSignature));
+
+    // RFC 7748 Section 6.1
+    let alice = X25519SecretKey::from_bytes(bytes(
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+    ));
+    let bob = X25519SecretKey::from_bytes(bytes(
+        "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+    ));
+    assert!(
+        alice.public_key().as_bytes()
+            == &bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert!(
+        bob.public_key().as_bytes()
+            == &bytes("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+    let shared = bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert!(alice.diffie_hellman(&bob.public_key()).as_bytes() == &shared);
+    assert!(bob.diffie_hellman(&alice.public_key()).as_bytes() == &shared);
+
+    assert!(ct_eq(b"abc", b"abc"));
+    assert!(!ct_eq(b"abc", b"abd"));
+    assert!(!ct_eq(b"abc", b"ab"));
+
+    println!("SUCCESS");
+}
@@ -0,0 +1,43 @@
+#![no_std]
+
+use core::hash::Hasher;
+use riot_wrappers::checksum::*;
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+/// Feed data in two parts through the Hasher interface
+fn via_hasher(mut hasher: impl Hasher, data: &[u8]) -> u64 {
+    let (a, b) = data.split_at(4);
+    hasher.write(a);
+    hasher.write(b);
+    hasher.finish()
+}
+
+fn main() {
+    // Check values from the CRC catalogue
+    let data = b"123456789";
+
+    assert!(Crc16CcittKermit::checksum(data) == 0x2189);
+    assert!(via_hasher(Crc16CcittKermit::new(), data) == 0x2189);
+
+    assert!(Crc16CcittFcs::checksum(data) == 0x906e);
+    assert!(via_hasher(Crc16CcittFcs::new(), data) == 0x906e);
+
+    assert!(Crc16CcittFalse::checksum(data) == 0x29b1);
+    assert!(via_hasher(Crc16CcittFalse::new(), data) == 0x29b1);
+
+    // CRC-8/SMBUS
+    let mut crc8 = Crc8::new(0x07, 0);
+    crc8.update(data);
+    assert!(crc8.finish() == 0xf4);
+    assert!(via_hasher(Crc8::new(0x07, 0), data) == 0xf4);
+
+    assert!(Crc32::checksum(data) == 0xcbf43926);
+    assert!(via_hasher(Crc32::new(), data) == 0xcbf43926);
+
+    assert!(fletcher16(b"abcde") == 0xc8f0);
+
+    println!("SUCCESS");
+}
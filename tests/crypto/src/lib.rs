@@ -0,0 +1,99 @@
+#![no_std]
+
+use cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use riot_wrappers::crypto::aes::Aes128;
+use riot_wrappers::crypto::modes;
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+fn main() {
+    // Test vector from FIPS-197 Appendix C.1
+    let key = b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f";
+    let plaintext = *b"\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff";
+    let ciphertext = *b"\x69\xc4\xe0\xd8\x6a\x7b\x04\x30\xd8\xcd\xb7\x80\x70\xb4\xc5\x5a";
+
+    let aes = Aes128::new(key.into());
+
+    let mut block = plaintext.into();
+    aes.encrypt_block(&mut block);
+    assert!(block.as_slice() == ciphertext);
+
+    aes.decrypt_block(&mut block);
+    assert!(block.as_slice() == plaintext);
+
+    test_ccm();
+    test_cbc_ctr();
+
+    println!("SUCCESS");
+}
+
+fn test_ccm() {
+    // Packet vector #1 from RFC 3610
+    let key = b"\xc0\xc1\xc2\xc3\xc4\xc5\xc6\xc7\xc8\xc9\xca\xcb\xcc\xcd\xce\xcf";
+    let nonce = b"\x00\x00\x00\x03\x02\x01\x00\xa0\xa1\xa2\xa3\xa4\xa5";
+    let aad = b"\x00\x01\x02\x03\x04\x05\x06\x07";
+    let plaintext = b"\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13\x14\x15\x16\x17\
+                      \x18\x19\x1a\x1b\x1c\x1d\x1e";
+    let ciphertext = b"\x58\x8c\x97\x9a\x61\xc6\x63\xd2\xf0\x66\xd0\xc2\xc0\xf9\x89\x80\
+                       \x6d\x5f\x6b\x61\xda\xc3\x84\x17\xe8\xd1\x2c\xfd\xf9\x26\xe0";
+
+    let aes = Aes128::new(key.into());
+    let ccm = modes::Ccm::new(aes.as_cipher(), 8, nonce.len()).unwrap();
+
+    let mut buffer = [0; 64];
+    buffer[..plaintext.len()].copy_from_slice(plaintext);
+    let len = ccm
+        .encrypt_in_place(nonce, aad, &mut buffer, plaintext.len())
+        .unwrap();
+    assert!(&buffer[..len] == ciphertext);
+
+    let len = ccm
+        .decrypt_in_place(nonce, aad, &mut buffer[..len])
+        .unwrap();
+    assert!(&buffer[..len] == plaintext);
+
+    let mut tampered = *ciphertext;
+    tampered[0] ^= 1;
+    assert!(
+        ccm.decrypt_in_place(nonce, aad, &mut tampered) == Err(modes::Error::AuthenticationFailed)
+    );
+
+    assert!(
+        ccm.encrypt_in_place(&nonce[1..], aad, &mut buffer, plaintext.len())
+            == Err(modes::Error::InvalidLength)
+    );
+}
+
+fn test_cbc_ctr() {
+    // Test vectors from NIST SP 800-38A, F.2.1 and F.5.1
+    let key = b"\x2b\x7e\x15\x16\x28\xae\xd2\xa6\xab\xf7\x15\x88\x09\xcf\x4f\x3c";
+    let plaintext = *b"\x6b\xc1\xbe\xe2\x2e\x40\x9f\x96\xe9\x3d\x7e\x11\x73\x93\x17\x2a\
+                       \xae\x2d\x8a\x57\x1e\x03\xac\x9c\x9e\xb7\x6f\xac\x45\xaf\x8e\x51";
+    let iv = b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f";
+    let cbc_ciphertext = *b"\x76\x49\xab\xac\x81\x19\xb2\x46\xce\xe9\x8e\x9b\x12\xe9\x19\x7d\
+                            \x50\x86\xcb\x9b\x50\x72\x19\xee\x95\xdb\x11\x3a\x91\x76\x78\xb2";
+    let counter = *b"\xf0\xf1\xf2\xf3\xf4\xf5\xf6\xf7\xf8\xf9\xfa\xfb\xfc\xfd\xfe\xff";
+    let ctr_ciphertext = *b"\x87\x4d\x61\x91\xb6\x20\xe3\x26\x1b\xef\x68\x64\x99\x0d\xb6\xce";
+
+    let aes = Aes128::new(key.into());
+
+    let mut buffer = plaintext;
+    modes::cbc_encrypt_in_place(aes.as_cipher(), iv, &mut buffer).unwrap();
+    assert!(buffer == cbc_ciphertext);
+    modes::cbc_decrypt_in_place(aes.as_cipher(), iv, &mut buffer).unwrap();
+    assert!(buffer == plaintext);
+    assert!(
+        modes::cbc_encrypt_in_place(aes.as_cipher(), iv, &mut buffer[1..])
+            == Err(modes::Error::InvalidLength)
+    );
+
+    let mut buffer = plaintext;
+    let mut ctr = modes::Ctr::new(aes.as_cipher(), counter, 8).unwrap();
+    ctr.apply_keystream(&mut buffer).unwrap();
+    assert!(buffer[..16] == ctr_ciphertext);
+    let mut ctr = modes::Ctr::new(aes.as_cipher(), counter, 8).unwrap();
+    ctr.apply_keystream(&mut buffer).unwrap();
+    assert!(buffer == plaintext);
+}
@@ -0,0 +1,54 @@
+#![no_std]
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use riot_wrappers::event::{Executor, Task};
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+use riot_wrappers::static_init::StaticCell;
+
+riot_main!(main);
+
+static EXECUTOR: Executor = Executor::new();
+
+static TASK_A: StaticCell<Task<Countdown>> = StaticCell::new();
+static TASK_B: StaticCell<Task<Countdown>> = StaticCell::new();
+
+/// A future that yields to the executor a few times, waking itself every time
+struct Countdown {
+    name: &'static str,
+    remaining: u32,
+}
+
+impl Future for Countdown {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        println!("{} {}", self.name, self.remaining);
+        if self.remaining == 0 {
+            println!("{} done", self.name);
+            return Poll::Ready(());
+        }
+        self.remaining -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn main() -> ! {
+    EXECUTOR.run(async {
+        let a: &'static Task<Countdown> = TASK_A.init(Task::new(Countdown {
+            name: "A",
+            remaining: 2,
+        }));
+        let b: &'static Task<Countdown> = TASK_B.init(Task::new(Countdown {
+            name: "B",
+            remaining: 2,
+        }));
+        EXECUTOR.spawn(a);
+        EXECUTOR.spawn(b);
+        println!("Tasks spawned");
+    })
+}
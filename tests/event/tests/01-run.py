@@ -0,0 +1,14 @@
+#!/usr/bin/env python3
+
+import sys
+from testrunner import run
+
+def test(child):
+    child.expect_exact("Tasks spawned")
+    # Both tasks wake themselves on every poll, and the queue is handled in order, so they take
+    # turns.
+    for line in ["A 2", "B 2", "A 1", "B 1", "A 0", "A done", "B 0", "B done"]:
+        child.expect_exact(line)
+
+if __name__ == "__main__":
+    sys.exit(run(test))
@@ -0,0 +1,87 @@
+#![no_std]
+
+use riot_wrappers::bloom::BloomFilter;
+use riot_wrappers::hash::non_crypto;
+use riot_wrappers::hash::{hkdf_sha256_expand, hkdf_sha256_extract, HmacSha256};
+use riot_wrappers::hash::{Md5, Sha1, Sha224, Sha256};
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+/// Run a hash through the digest traits, in two parts
+fn via_digest<D: digest::Digest>(data: &[u8]) -> digest::Output<D> {
+    let mut hash = D::new();
+    let (a, b) = data.split_at(data.len() / 2);
+    hash.update(a);
+    hash.update(b);
+    hash.finalize()
+}
+
+fn main() {
+    let data = b"abc";
+
+    let sha256 = Sha256::digest(data);
+    assert!(
+        sha256
+            == *b"\xba\x78\x16\xbf\x8f\x01\xcf\xea\x41\x41\x40\xde\x5d\xae\x22\x23\
+                  \xb0\x03\x61\xa3\x96\x17\x7a\x9c\xb4\x10\xff\x61\xf2\x00\x15\xad"
+    );
+    assert!(via_digest::<Sha256>(data).as_slice() == sha256);
+
+    let sha224 = Sha224::digest(data);
+    assert!(
+        sha224
+            == *b"\x23\x09\x7d\x22\x34\x05\xd8\x22\x86\x42\xa4\x77\xbd\xa2\x55\xb3\
+                  \x2a\xad\xbc\xe4\xbd\xa0\xb3\xf7\xe3\x6c\x9d\xa7"
+    );
+    assert!(via_digest::<Sha224>(data).as_slice() == sha224);
+
+    let sha1 = Sha1::digest(data);
+    assert!(
+        sha1 == *b"\xa9\x99\x3e\x36\x47\x06\x81\x6a\xba\x3e\x25\x71\x78\x50\xc2\x6c\
+                   \x9c\xd0\xd8\x9d"
+    );
+    assert!(via_digest::<Sha1>(data).as_slice() == sha1);
+
+    let md5 = Md5::digest(data);
+    assert!(md5 == *b"\x90\x01\x50\x98\x3c\xd2\x4f\xb0\xd6\x96\x3f\x7d\x28\xe1\x7f\x72");
+    assert!(via_digest::<Md5>(data).as_slice() == md5);
+
+    // RFC 4231 test case 2
+    let key = b"Jefe";
+    let data = b"what do ya want for nothing?";
+    let expected = *b"\x5b\xdc\xc1\x46\xbf\x60\x75\x4e\x6a\x04\x24\x26\x08\x95\x75\xc7\
+                      \x5a\x00\x3f\x08\x9d\x27\x39\x83\x9d\xec\x58\xb9\x64\xec\x38\x43";
+    assert!(HmacSha256::mac(key, data) == expected);
+    let mut mac = <HmacSha256 as digest::Mac>::new_from_slice(key).unwrap();
+    digest::Mac::update(&mut mac, data);
+    digest::Mac::verify_slice(mac, &expected).unwrap();
+
+    // RFC 5869 test case 3
+    let prk = hkdf_sha256_extract(&[], &[0x0b; 22]);
+    assert!(
+        prk == *b"\x19\xef\x24\xa3\x2c\x71\x7b\x16\x7f\x33\xa9\x1d\x6f\x64\x8b\xdf\
+                  \x96\x59\x67\x76\xaf\xdb\x63\x77\xac\x43\x4c\x1c\x29\x3c\xcb\x04"
+    );
+    let mut okm = [0; 42];
+    hkdf_sha256_expand(&prk, &[], &mut okm).unwrap();
+    assert!(
+        okm == *b"\x8d\xa4\xe7\x75\xa5\x63\xc1\x8f\x71\x5f\x80\x2a\x06\x3c\x5a\x31\
+                  \xb8\xa1\x1f\x5c\x5e\xe1\x87\x9e\xc3\x45\x4e\x5f\x3c\x73\x8d\x2d\
+                  \x9d\x20\x13\x95\xfa\xa4\xb6\x1a\x96\xc8"
+    );
+
+    assert!(non_crypto::djb2(b"abc") == 193485963);
+
+    let mut seen: BloomFilter<64> = BloomFilter::new(4);
+    assert!(!seen.check_and_add(b"packet 1"));
+    assert!(!seen.check_and_add(b"packet 2"));
+    assert!(seen.check(b"packet 1"));
+    assert!(seen.check_and_add(b"packet 2"));
+    assert!(!seen.check(b"packet 3"));
+    seen.clear();
+    assert!(!seen.check(b"packet 1"));
+
+    println!("SUCCESS");
+}
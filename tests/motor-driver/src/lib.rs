@@ -0,0 +1,28 @@
+#![no_std]
+
+use riot_wrappers::motor_driver::{Direction, MotorDriver};
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+/// Run all motors briefly; this is what an application would do with parameters matching its
+/// hardware
+#[allow(dead_code)]
+fn exercise(params: &'static riot_sys::motor_driver_params_t) {
+    let mut driver = MotorDriver::init(params).unwrap();
+    let half = driver.max_speed() / 2;
+    for id in 0..driver.motor_count() {
+        let mut motor = driver.motor(id).unwrap();
+        motor.enable();
+        motor.set_speed(Direction::Clockwise, half).unwrap();
+        motor.set_signed(-(half as i32)).unwrap();
+        motor.brake();
+        motor.disable();
+    }
+    assert!(driver.motor(driver.motor_count()).is_none());
+}
+
+fn main() {
+    println!("SUCCESS");
+}
@@ -0,0 +1,43 @@
+#![no_std]
+
+use riot_wrappers::mtd::{mapper, Mtd};
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+fn main() {
+    // unsafe: Index 0 exists on all boards this test is run on, and is not used by anything else
+    let mut dev = unsafe { Mtd::from_index(0) };
+    dev.init().unwrap();
+    let sectors = dev.sector_count();
+    println!(
+        "Device has {} sectors of {} bytes",
+        sectors,
+        dev.sector_size()
+    );
+    assert!(sectors >= 2, "Test requires at least two sectors");
+
+    let parent = mapper::Parent::new(dev);
+    assert!(parent.region(sectors - 1, 2).is_err());
+    let mut first = parent.region(0, 1).unwrap();
+    let mut second = parent.region(1, 1).unwrap();
+
+    let mut first = first.as_mtd();
+    let mut second = second.as_mtd();
+    first.init().unwrap();
+    second.init().unwrap();
+
+    first.erase_sectors(0, 1).unwrap();
+    second.erase_sectors(0, 1).unwrap();
+    first.write_raw(0, b"first").unwrap();
+    second.write_raw(0, b"second").unwrap();
+
+    let mut buf = [0; 6];
+    first.read(0, &mut buf[..5]).unwrap();
+    assert!(&buf[..5] == b"first");
+    second.read(0, &mut buf).unwrap();
+    assert!(&buf == b"second");
+
+    println!("SUCCESS");
+}
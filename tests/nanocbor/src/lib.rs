@@ -0,0 +1,82 @@
+#![no_std]
+
+use riot_wrappers::nanocbor::{Decoder, Encoder, Error, MajorType};
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Reading<'a> {
+    name: &'a str,
+    value: i32,
+    unit: Option<Unit>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+enum Unit {
+    Celsius,
+    Scaled(i8),
+}
+
+fn main() {
+    let mut buf = [0; 64];
+    let mut encoder = Encoder::new(&mut buf);
+    encoder
+        .array(4)
+        .uint(42)
+        .int(-1000)
+        .tstr("hello")
+        .map(1)
+        .bstr(b"key")
+        .bool(true);
+    let encoded = encoder.finish().unwrap();
+    assert!(encoded == b"\x84\x18\x2a\x39\x03\xe7\x65hello\xa1\x43key\xf5");
+
+    let mut decoder = Decoder::new(encoded);
+    assert!(decoder.major_type() == Ok(MajorType::Array));
+    decoder
+        .array(|items| {
+            assert!(items.u8()? == 42);
+            // Type mismatches leave the decoder unchanged
+            assert!(items.u32() == Err(Error::InvalidType));
+            assert!(items.i16()? == -1000);
+            assert!(items.tstr()? == "hello");
+            items.map(|entries| {
+                assert!(entries.bstr()? == b"key");
+                assert!(entries.bool()?);
+                assert!(entries.at_end());
+                Ok(())
+            })?;
+            assert!(items.at_end());
+            Ok(())
+        })
+        .unwrap();
+    assert!(decoder.at_end());
+
+    let mut small = [0; 4];
+    let mut encoder = Encoder::new(&mut small);
+    encoder.tstr("too long");
+    assert!(encoder.encoded_len() == 9);
+    assert!(encoder.finish() == Err(Error::End));
+
+    let reading = Reading {
+        name: "temperature",
+        value: 2150,
+        unit: Some(Unit::Scaled(-2)),
+    };
+    let mut buf = [0; 64];
+    let mut encoder = Encoder::new(&mut buf);
+    encoder.serialize(&reading).unwrap();
+    let encoded = encoder.finish().unwrap();
+    let decoded: Reading = Decoder::new(encoded).deserialize().unwrap();
+    assert!(decoded == reading);
+
+    let mut encoder = Encoder::new(&mut buf);
+    encoder.serialize(&Unit::Celsius).unwrap();
+    let encoded = encoder.finish().unwrap();
+    assert!(encoded == b"\x67Celsius");
+    assert!(Decoder::new(encoded).deserialize::<Unit>() == Ok(Unit::Celsius));
+
+    println!("SUCCESS");
+}
@@ -10,6 +10,12 @@ fn main() {
 
     let msec = Clock::msec();
 
+    // LLMNR group, joined as an example of a service discovery group
+    let group: riot_wrappers::gnrc::ipv6::Address = "ff02::1:3".parse().unwrap();
+    for netif in riot_wrappers::gnrc::Netif::all() {
+        netif.ipv6_group_join(&group).unwrap();
+    }
+
     loop {
         for netif in riot_wrappers::gnrc::Netif::all() {
             println!(
@@ -20,6 +26,9 @@ fn main() {
             for addr in &netif.ipv6_addrs().unwrap() {
                 println!("- Address {:?}", addr);
             }
+            for group in &netif.ipv6_groups().unwrap() {
+                println!("- Group {:?}", group);
+            }
         }
 
         println!("Cache entries:");
@@ -9,6 +9,8 @@ def test(child):
     # should not crash.
     for _ in range(3):
         child.expect("Netif at ")
+        # The joined group is listed
+        child.expect("- Group ff02::1:3")
         child.expect("Cache entries")
 
 if __name__ == "__main__":
@@ -0,0 +1,50 @@
+#![no_std]
+
+use riot_wrappers::oscore::*;
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+/// AES-CCM-16-64-128
+const AEAD: i32 = 10;
+/// HKDF SHA-256
+const HKDF: i32 = 5;
+
+// Input of RFC 8613 Appendix C.1.1 (client side)
+const MASTER_SECRET: &[u8] = &[
+    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10,
+];
+const MASTER_SALT: &[u8] = &[0x9e, 0x7c, 0xa9, 0x22, 0x23, 0x78, 0x63, 0x40];
+
+fn main() {
+    let parameters =
+        ContextParameters::derive(AEAD, HKDF, MASTER_SECRET, MASTER_SALT, None, &[], &[0x01])
+            .unwrap();
+
+    let context = SecurityContext::new(&parameters);
+    assert!(context.sender_sequence_number() == 0);
+
+    // As after a reboot with a stored sequence number
+    let context = SecurityContext::with_sequence_number(&parameters, 20);
+    assert!(context.sender_sequence_number() == 20);
+
+    assert!(
+        ContextParameters::derive(-1234, HKDF, MASTER_SECRET, MASTER_SALT, None, &[], &[0x01])
+            .err()
+            == Some(Error::UnsupportedAlgorithm)
+    );
+    assert!(
+        ContextParameters::derive(AEAD, -1234, MASTER_SECRET, MASTER_SALT, None, &[], &[0x01])
+            .err()
+            == Some(Error::UnsupportedAlgorithm)
+    );
+    // No ID can be longer than the nonce
+    assert!(
+        ContextParameters::derive(AEAD, HKDF, MASTER_SECRET, MASTER_SALT, None, &[0; 64], &[])
+            .err()
+            == Some(Error::Derivation)
+    );
+
+    println!("SUCCESS");
+}
@@ -0,0 +1,79 @@
+#![no_std]
+
+use riot_wrappers::println;
+use riot_wrappers::psa_crypto::*;
+use riot_wrappers::riot_main;
+
+riot_main!(main);
+
+fn main() {
+    init().unwrap();
+
+    let data = b"abc";
+    let expected = b"\xba\x78\x16\xbf\x8f\x01\xcf\xea\x41\x41\x40\xde\x5d\xae\x22\x23\
+                     \xb0\x03\x61\xa3\x96\x17\x7a\x9c\xb4\x10\xff\x61\xf2\x00\x15\xad";
+    let mut hash = [0; 32];
+    assert!(hash_compute(HashAlgorithm::Sha256, data, &mut hash).unwrap() == expected);
+    hash_compare(HashAlgorithm::Sha256, data, expected).unwrap();
+    assert!(hash_compare(HashAlgorithm::Sha256, b"abd", expected) == Err(Error::InvalidSignature));
+
+    let aes = Key::import(
+        &KeyAttributes::new(KeyType::Aes, 128)
+            .usage(Usage::ENCRYPT | Usage::DECRYPT)
+            .algorithm(AeadAlgorithm::Ccm),
+        &[0x2a; 16],
+    )
+    .unwrap();
+    let nonce = [0; 13];
+    let mut ciphertext = [0; 64];
+    let ciphertext = aes
+        .aead_encrypt(
+            AeadAlgorithm::Ccm,
+            &nonce,
+            b"aad",
+            b"Hello",
+            &mut ciphertext,
+        )
+        .unwrap();
+    assert!(ciphertext.len() == 5 + 16);
+    let mut plaintext = [0; 64];
+    assert!(
+        aes.aead_decrypt(
+            AeadAlgorithm::Ccm,
+            &nonce,
+            b"aad",
+            ciphertext,
+            &mut plaintext
+        )
+        .unwrap()
+            == b"Hello"
+    );
+    assert!(
+        aes.aead_decrypt(
+            AeadAlgorithm::Ccm,
+            &nonce,
+            b"AAD",
+            ciphertext,
+            &mut plaintext
+        ) == Err(Error::InvalidSignature)
+    );
+
+    let ecdsa = SignatureAlgorithm::Ecdsa(HashAlgorithm::Sha256);
+    let keypair = Key::generate(
+        &KeyAttributes::new(KeyType::EccKeyPair(EccFamily::SecpR1), 256)
+            .usage(Usage::SIGN_MESSAGE | Usage::VERIFY_MESSAGE)
+            .algorithm(ecdsa),
+    )
+    .unwrap();
+    let mut signature = [0; 64];
+    let signature = keypair
+        .sign_message(ecdsa, b"message", &mut signature)
+        .unwrap();
+    keypair
+        .verify_message(ecdsa, b"message", signature)
+        .unwrap();
+    assert!(keypair.verify_message(ecdsa, b"massage", signature) == Err(Error::InvalidSignature));
+    keypair.destroy().unwrap();
+
+    println!("SUCCESS");
+}
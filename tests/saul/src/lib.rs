@@ -0,0 +1,63 @@
+#![no_std]
+
+use riot_wrappers::mutex::Mutex;
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+use riot_wrappers::saul::registration::{self, Drivable, Driver, Registration};
+use riot_wrappers::saul::{ActuatorClass, Class, Phydat, RegistryEntry, Unit};
+
+riot_main!(main);
+
+struct Dimmer(Mutex<i16>);
+
+impl Drivable for &Dimmer {
+    const CLASS: Class = Class::Actuator(Some(ActuatorClass::Dimmer));
+    const HAS_READ: bool = true;
+    const HAS_WRITE: bool = true;
+
+    fn read(self) -> Result<Phydat, registration::Error> {
+        Ok(Phydat::new(&[*self.0.lock()], Some(Unit::Percent), 0))
+    }
+
+    fn write(self, data: &Phydat) -> Result<u8, registration::Error> {
+        *self.0.lock() = data.value()[0];
+        Ok(1)
+    }
+}
+
+static DIMMER: Dimmer = Dimmer(Mutex::new(0));
+static DRIVER: Driver<Dimmer> = Driver::new();
+static REGISTRATION: Mutex<Registration<Dimmer>> =
+    Mutex::new(Registration::new(&DRIVER, &DIMMER, Some(c"dimmer")));
+
+fn main() {
+    REGISTRATION.try_leak().unwrap().register_static();
+
+    let entry = RegistryEntry::find_name(c"dimmer").expect("Registered device not found");
+    println!("Found {:?}", entry);
+    assert!(entry.type_() == Some(Class::Actuator(Some(ActuatorClass::Dimmer))));
+    assert!(RegistryEntry::all_of_class(Class::Actuator(None)).any(|e| e.name() == Some("dimmer")));
+    assert!(RegistryEntry::all_of_class(Class::Sensor(None)).all(|e| e.name() != Some("dimmer")));
+
+    let value = Phydat::builder()
+        .value(42)
+        .unit(Unit::Percent)
+        .build()
+        .unwrap();
+    entry.write(value).unwrap();
+    let read = entry.read().unwrap();
+    println!("Read back {}", read);
+    assert!(read.value() == &[42]);
+    assert!(read.unit() == Some(Unit::Percent));
+
+    assert!(Phydat::builder().build().is_err());
+    assert!(Phydat::builder().values(&[1, 2, 3, 4]).build().is_err());
+
+    let temperature = Phydat::from_f32(&[21.456], Some(Unit::TempC), -2);
+    println!("Converted from float: {}", temperature);
+    assert!(temperature.value() == &[2146]);
+    assert!(temperature.value_scaled(-3).eq([Some(21460)]));
+    assert!(temperature.value_scaled(0).eq([Some(21)]));
+
+    println!("SUCCESS");
+}
@@ -0,0 +1,29 @@
+#![no_std]
+
+use core::mem::MaybeUninit;
+
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+use riot_wrappers::servo::Servo;
+use riot_wrappers::static_init::ConstStaticCell;
+
+riot_main!(main);
+
+static DEVICE: ConstStaticCell<MaybeUninit<riot_sys::servo_t>> =
+    ConstStaticCell::new(MaybeUninit::uninit());
+
+/// Sweep a servo once; this is what an application would do with parameters matching its hardware
+#[allow(dead_code)]
+fn sweep(params: &'static riot_sys::servo_params_t) {
+    let mut servo = Servo::init(DEVICE.take(), params).unwrap();
+    for position in [0, 128, 255] {
+        servo.set_position(position);
+    }
+    let range = servo.pulse_range_us();
+    servo.set_pulse_us(*range.start());
+    servo.set_pulse_us(*range.end());
+}
+
+fn main() {
+    println!("SUCCESS");
+}
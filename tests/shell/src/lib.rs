@@ -38,3 +38,7 @@ riot_wrappers::static_command!(
     "Print the arguments in separate lines",
     do_echo
 );
+
+riot_wrappers::shell_command!("greet", "Greet someone", |_stdio, args| {
+    println!("Hello {}", args.get(1).unwrap_or("world"));
+});
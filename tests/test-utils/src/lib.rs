@@ -0,0 +1,22 @@
+#![no_std]
+
+use riot_wrappers::riot_main;
+use riot_wrappers::test_utils::{interactive_sync, Turo};
+
+riot_main!(main);
+
+fn main() {
+    interactive_sync();
+
+    let mut turo = Turo::new();
+    turo.container_open()
+        .dict_open()
+        .dict_key(c"answer")
+        .s32(42)
+        .dict_key(c"large")
+        .u64(1 << 40)
+        .dict_key(c"passed")
+        .bool(true)
+        .dict_close();
+    turo.container_close(0);
+}
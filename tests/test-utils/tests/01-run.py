@@ -0,0 +1,12 @@
+#!/usr/bin/env python3
+
+import sys
+from testrunner import run
+
+def test(child):
+    child.expect_exact('"answer": 42')
+    child.expect_exact('"large": 1099511627776')
+    child.expect_exact('"passed": true')
+
+if __name__ == "__main__":
+    sys.exit(run(test))
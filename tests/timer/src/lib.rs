@@ -0,0 +1,38 @@
+#![no_std]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+use riot_wrappers::timer::Timer;
+
+riot_main!(main);
+
+/// Index of the timer used; this test does not use ztimer, so the first one is free
+const TIMER: usize = 0;
+
+/// Run a scoped timer with a callback that counts on the stack, firing it three times
+fn run_once() {
+    let fired = AtomicU32::new(0);
+    let mut callback = |_channel: u8| {
+        fired.fetch_add(1, Ordering::Relaxed);
+    };
+    Timer::new_scoped(TIMER, 1_000_000, &mut callback, |timer| {
+        for i in 1..=3 {
+            timer.set(0, 10_000).unwrap();
+            while fired.load(Ordering::Relaxed) < i {
+                core::hint::spin_loop();
+            }
+        }
+    })
+    .unwrap();
+    println!("Fired {} times", fired.load(Ordering::Relaxed));
+}
+
+fn main() {
+    // Running twice shows that the timer can be used again after the scoped version dropped it
+    run_once();
+    run_once();
+
+    println!("SUCCESS");
+}
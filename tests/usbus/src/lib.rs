@@ -0,0 +1,68 @@
+#![no_std]
+
+use riot_wrappers::println;
+use riot_wrappers::riot_main;
+use riot_wrappers::static_init::ConstStaticCell;
+use riot_wrappers::usbus::handler::*;
+use riot_wrappers::usbus::Usbus;
+
+riot_main!(main);
+
+static USBUS: Usbus = Usbus::new();
+static STACK: ConstStaticCell<[u8; 2048]> = ConstStaticCell::zeroed();
+
+static RX_BUFFER: ConstStaticCell<EndpointBuffer<64>> = ConstStaticCell::zeroed();
+static TX_BUFFER: ConstStaticCell<EndpointBuffer<64>> = ConstStaticCell::zeroed();
+static INTERFACE: Interface<Loopback> = Interface::new(Loopback { rx: None, tx: None });
+
+/// Echoes back everything it receives on a pair of bulk endpoints
+struct Loopback {
+    rx: Option<Endpoint>,
+    tx: Option<Endpoint>,
+}
+
+impl Handler for Loopback {
+    fn init(&mut self, setup: &mut Setup<'_>) {
+        self.rx = setup
+            .add_endpoint(EndpointType::Bulk, Direction::Out, RX_BUFFER.take())
+            .ok();
+        self.tx = setup
+            .add_endpoint(EndpointType::Bulk, Direction::In, TX_BUFFER.take())
+            .ok();
+    }
+
+    fn usb_event(&mut self, context: &mut Context<'_>, event: UsbEvent) {
+        if event == UsbEvent::Reset {
+            if let Some(rx) = self.rx.as_ref() {
+                context.receive(rx);
+            }
+        }
+    }
+
+    fn transfer(&mut self, context: &mut Context<'_>, endpoint: EndpointId, event: TransferEvent) {
+        let (Some(rx), Some(tx)) = (self.rx.as_ref(), self.tx.as_ref()) else {
+            return;
+        };
+        if event == TransferEvent::Complete && endpoint == rx.id() {
+            let len = context.received_len(rx);
+            let mut data = [0; 64];
+            data[..len].copy_from_slice(
+                &context.buffer(rx).expect("Receive transfer just completed")[..len],
+            );
+            // Reception is only restarted once the previous echo was sent
+            let buffer = context.buffer(tx).expect("Transmit endpoint is idle");
+            buffer[..len].copy_from_slice(&data[..len]);
+            context.transmit(tx, len);
+        } else if endpoint == tx.id() {
+            context.receive(rx);
+        }
+    }
+}
+
+fn main() {
+    USBUS.init(0);
+    INTERFACE.register(&USBUS);
+    USBUS.start(STACK.take(), 2, c"usbus");
+
+    println!("USBUS started");
+}
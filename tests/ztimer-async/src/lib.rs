@@ -7,9 +7,9 @@ riot_main!(main);
 
 fn main() -> ! {
     // Could also use static-on-stack crate instead
-    static EXECUTOR: static_cell::StaticCell<embassy_executor_riot::Executor> =
+    static EXECUTOR: static_cell::StaticCell<riot_wrappers::embassy::Executor> =
         static_cell::StaticCell::new();
-    let executor: &'static mut _ = EXECUTOR.init(embassy_executor_riot::Executor::new());
+    let executor: &'static mut _ = EXECUTOR.init(riot_wrappers::embassy::Executor::new());
     executor.run(|spawner| {
         spawner
             .spawn(amain(spawner))